@@ -0,0 +1,169 @@
+use crate::{LoadedSave, SaveFormat, TiValue, statics};
+use anyhow::Context;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A single edit applied to an object's `Value` in a batch run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchOp {
+    Set {
+        group: String,
+        id: i64,
+        prop: String,
+        value: TiValue,
+    },
+    Remove {
+        group: String,
+        id: i64,
+        prop: String,
+    },
+    Merge {
+        group: String,
+        id: i64,
+        value: TiValue,
+    },
+}
+
+/// Result of processing one save: the written output path, or the error that stopped it.
+#[derive(Debug)]
+pub struct BatchOutcome {
+    pub input: PathBuf,
+    pub result: anyhow::Result<PathBuf>,
+}
+
+/// Parse a batch edit file: a JSON5 array of `{ op, group, id, prop?, value? }` objects.
+/// `group` may be the full group name or the short name without the common namespace.
+pub fn parse_ops(text: &str) -> anyhow::Result<Vec<BatchOp>> {
+    let root = TiValue::parse_json5(text).context("parsing batch file")?;
+    let items = root
+        .as_array()
+        .context("batch file must be an array of operations")?;
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| parse_op(item).with_context(|| format!("operation #{}", i + 1)))
+        .collect()
+}
+
+fn parse_op(item: &TiValue) -> anyhow::Result<BatchOp> {
+    let field = |key: &str| item.get(key).with_context(|| format!("missing {key:?}"));
+    let str_field = |key: &str| {
+        field(key)?
+            .as_str()
+            .map(str::to_string)
+            .with_context(|| format!("{key:?} must be a string"))
+    };
+
+    let op = str_field(statics::BATCH_KEY_OP)?;
+    let group = str_field(statics::BATCH_KEY_GROUP)?;
+    let id = match field(statics::BATCH_KEY_ID)? {
+        TiValue::Number(n) => n.as_i64(),
+        _ => None,
+    }
+    .with_context(|| format!("{:?} must be an integer", statics::BATCH_KEY_ID))?;
+
+    Ok(match op.as_str() {
+        statics::BATCH_OP_SET => BatchOp::Set {
+            group,
+            id,
+            prop: str_field(statics::BATCH_KEY_PROP)?,
+            value: field(statics::BATCH_KEY_VALUE)?.clone(),
+        },
+        statics::BATCH_OP_REMOVE => BatchOp::Remove {
+            group,
+            id,
+            prop: str_field(statics::BATCH_KEY_PROP)?,
+        },
+        statics::BATCH_OP_MERGE => BatchOp::Merge {
+            group,
+            id,
+            value: field(statics::BATCH_KEY_VALUE)?.clone(),
+        },
+        other => anyhow::bail!("unknown op {other:?}"),
+    })
+}
+
+/// Apply all operations to a save in order, stopping at the first failure.
+pub fn apply_ops(save: &mut LoadedSave, ops: &[BatchOp]) -> anyhow::Result<()> {
+    for (i, op) in ops.iter().enumerate() {
+        let res = match op {
+            BatchOp::Set {
+                group,
+                id,
+                prop,
+                value,
+            } => save
+                .set_property(group, *id, prop, value.clone())
+                .map(|_| ()),
+            BatchOp::Remove { group, id, prop } => {
+                save.remove_property(group, *id, prop).map(|_| ())
+            }
+            BatchOp::Merge { group, id, value } => save.deep_merge(group, *id, value),
+        };
+        res.with_context(|| format!("operation #{}", i + 1))?;
+    }
+    save.rebuild_index();
+    Ok(())
+}
+
+/// Where the patched copy of `input` is written: alongside it, with `suffix` appended to the stem.
+/// A `.json.gz` save keeps its double extension (`Save_patched.json.gz`).
+pub fn output_path_for(input: &Path, suffix: &str) -> PathBuf {
+    let file_name = input.file_name().unwrap_or_default().to_string_lossy();
+    let (stem, ext) = SaveFormat::split_file_name(&file_name);
+    let mut name = format!("{stem}{suffix}");
+    if !ext.is_empty() {
+        name.push('.');
+        name.push_str(ext);
+    }
+    input.with_file_name(name)
+}
+
+/// Save files in `dir` that a batch run should process, sorted by path.
+/// Files that already carry `suffix` are skipped so re-running doesn't patch its own outputs.
+pub fn find_saves(dir: &Path, suffix: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let mut saves = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("reading {dir:?}"))? {
+        let path = entry?.path();
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        let file_name = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let (stem, _) = SaveFormat::split_file_name(file_name);
+        if path.is_file()
+            && statics::BATCH_SAVE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+            && !stem.ends_with(suffix)
+        {
+            saves.push(path);
+        }
+    }
+    saves.sort();
+    Ok(saves)
+}
+
+/// Apply `ops` to every save in `dir`, writing each result alongside its input.
+/// A failure in one save is recorded in its outcome and does not stop the others.
+pub fn run_batch(dir: &Path, ops: &[BatchOp], suffix: &str) -> anyhow::Result<Vec<BatchOutcome>> {
+    let outcomes = find_saves(dir, suffix)?
+        .into_iter()
+        .map(|input| {
+            let result = process_one(&input, ops, suffix);
+            BatchOutcome { input, result }
+        })
+        .collect();
+    Ok(outcomes)
+}
+
+fn process_one(input: &Path, ops: &[BatchOp], suffix: &str) -> anyhow::Result<PathBuf> {
+    let mut save = LoadedSave::load_path(input)?;
+    apply_ops(&mut save, ops)?;
+    let output = output_path_for(input, suffix);
+    save.save_to_path(&output)?;
+    Ok(output)
+}
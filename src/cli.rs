@@ -0,0 +1,132 @@
+use crate::{LoadedSave, batch, scramble, statics};
+use anyhow::Context;
+use std::path::Path;
+
+/// Run a command-line subcommand if one was given.
+/// Returns `None` when the first argument isn't a subcommand (none at all, or e.g. a save path
+/// passed by "Open with"), meaning the caller should start the GUI.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    let (cmd, rest) = args.split_first()?;
+    let run: fn(&[String]) -> anyhow::Result<()> = match cmd.as_str() {
+        statics::CLI_CMD_SCRAMBLE => cmd_scramble,
+        statics::CLI_CMD_BATCH => cmd_batch,
+        _ => return None,
+    };
+    attach_parent_console();
+    let result = run(rest);
+
+    Some(match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{e:#}");
+            1
+        }
+    })
+}
+
+/// Release builds on Windows use the GUI subsystem (see main.rs) and start without a console,
+/// so a subcommand attaches to the console it was started from; otherwise its output is lost.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // SAFETY: takes no pointers. If it fails (e.g. started from Explorer), output stays unattached.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+/// `tise scramble <input> <output> [--seed N]`
+fn cmd_scramble(args: &[String]) -> anyhow::Result<()> {
+    let mut paths = Vec::new();
+    let mut seed = 0u64;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        if arg == statics::CLI_FLAG_SEED {
+            let value = it
+                .next()
+                .ok_or_else(|| anyhow::anyhow!(statics::EN_CLI_USAGE))?;
+            seed = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid seed: {value}"))?;
+        } else {
+            paths.push(arg);
+        }
+    }
+    let [input, output] = paths.as_slice() else {
+        anyhow::bail!(statics::EN_CLI_USAGE);
+    };
+
+    let mut save = LoadedSave::load_path(Path::new(input))?;
+    scramble::scramble(&mut save.root, seed);
+    save.rebuild_index();
+    save.mark_dirty();
+    save.save_to_path(Path::new(output))?;
+
+    println!("Scrambled {input} -> {output}");
+    Ok(())
+}
+
+/// `tise batch <dir> <ops.json5> [--suffix S]`
+fn cmd_batch(args: &[String]) -> anyhow::Result<()> {
+    let mut paths = Vec::new();
+    let mut suffix = statics::BATCH_DEFAULT_SUFFIX.to_string();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        if arg == statics::CLI_FLAG_SUFFIX {
+            suffix = it
+                .next()
+                .ok_or_else(|| anyhow::anyhow!(statics::EN_CLI_USAGE))?
+                .clone();
+        } else {
+            paths.push(arg);
+        }
+    }
+    let [dir, ops_path] = paths.as_slice() else {
+        anyhow::bail!(statics::EN_CLI_USAGE);
+    };
+
+    let ops_text =
+        std::fs::read_to_string(ops_path).with_context(|| format!("reading {ops_path}"))?;
+    let ops = batch::parse_ops(&ops_text)?;
+    let outcomes = batch::run_batch(Path::new(dir), &ops, &suffix)?;
+
+    let mut failed = 0usize;
+    for outcome in &outcomes {
+        match &outcome.result {
+            Ok(output) => println!("OK    {} -> {}", outcome.input.display(), output.display()),
+            Err(e) => {
+                failed += 1;
+                println!("FAIL  {}: {e:#}", outcome.input.display());
+            }
+        }
+    }
+    println!(
+        "{} of {} saves patched",
+        outcomes.len() - failed,
+        outcomes.len()
+    );
+
+    if failed > 0 {
+        anyhow::bail!("{failed} save(s) failed");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_cli;
+
+    #[test]
+    fn only_subcommands_are_handled_on_the_command_line() {
+        assert_eq!(run_cli(&[]), None);
+        assert_eq!(run_cli(&["Autosave.gz".to_string()]), None);
+        assert_eq!(run_cli(&["scramble".to_string()]), Some(1));
+    }
+}
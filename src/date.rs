@@ -0,0 +1,206 @@
+//! Terra Invicta dates. Saves store every date/time (`dateBorn`, `currentDateTime`, `launchDate`,
+//! ...) as an object of seven integers, `{year, month, day, hour, minute, second, millisecond}`,
+//! so dates are recognised by that shape rather than by property name.
+
+use crate::{TiValue, statics, value::TiNumber};
+use indexmap::IndexMap;
+
+/// Field names in the order the game writes them.
+const DATE_FIELDS: [&str; 7] = [
+    statics::TI_DATE_YEAR,
+    statics::TI_DATE_MONTH,
+    statics::TI_DATE_DAY,
+    statics::TI_DATE_HOUR,
+    statics::TI_DATE_MINUTE,
+    statics::TI_DATE_SECOND,
+    statics::TI_DATE_MILLISECOND,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiDate {
+    pub year: i64,
+    pub month: i64,
+    pub day: i64,
+    pub hour: i64,
+    pub minute: i64,
+    pub second: i64,
+    pub millisecond: i64,
+}
+
+impl TiDate {
+    /// Read a date object. Returns `None` unless the value has exactly the seven integer fields.
+    pub fn from_value(val: &TiValue) -> Option<TiDate> {
+        let map = val.as_object()?;
+        if map.len() != DATE_FIELDS.len() {
+            return None;
+        }
+        let field = |key: &str| match map.get(key)? {
+            TiValue::Number(n) => n.as_i64(),
+            _ => None,
+        };
+        Some(TiDate {
+            year: field(statics::TI_DATE_YEAR)?,
+            month: field(statics::TI_DATE_MONTH)?,
+            day: field(statics::TI_DATE_DAY)?,
+            hour: field(statics::TI_DATE_HOUR)?,
+            minute: field(statics::TI_DATE_MINUTE)?,
+            second: field(statics::TI_DATE_SECOND)?,
+            millisecond: field(statics::TI_DATE_MILLISECOND)?,
+        })
+    }
+
+    /// The date object as the game writes it.
+    pub fn to_value(&self) -> TiValue {
+        let map: IndexMap<String, TiValue> = DATE_FIELDS
+            .iter()
+            .zip(self.fields())
+            .map(|(k, v)| (k.to_string(), TiValue::Number(TiNumber::I64(v))))
+            .collect();
+        TiValue::Object(map)
+    }
+
+    fn fields(&self) -> [i64; 7] {
+        [
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            self.millisecond,
+        ]
+    }
+
+    /// Just the calendar day, `YYYY-MM-DD`.
+    pub fn format_day(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// `YYYY-MM-DD HH:MM:SS.mmm`, which `parse` reads back.
+    pub fn format(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second, self.millisecond
+        )
+    }
+
+    /// Parse `YYYY-MM-DD`, optionally followed by `HH:MM`, `HH:MM:SS` or `HH:MM:SS.mmm`.
+    /// Omitted time parts are zero. The result is checked with `validate`.
+    pub fn parse(text: &str) -> anyhow::Result<TiDate> {
+        let text = text.trim();
+        let (date, time) = match text.split_once([' ', 'T']) {
+            Some((d, t)) => (d, t.trim()),
+            None => (text, ""),
+        };
+
+        let num = |s: &str| {
+            s.trim()
+                .parse::<i64>()
+                .map_err(|_| anyhow::anyhow!("invalid number {s:?} in date {text:?}"))
+        };
+        let date_parts: Vec<&str> = date.split('-').collect();
+        let [year, month, day] = date_parts.as_slice() else {
+            anyhow::bail!("expected YYYY-MM-DD, got {text:?}");
+        };
+
+        let (hms, millisecond) = match time.split_once('.') {
+            Some((hms, ms)) => (hms, num(ms)?),
+            None => (time, 0),
+        };
+        let mut time_parts = [0i64; 3];
+        if !hms.is_empty() {
+            let parts: Vec<&str> = hms.split(':').collect();
+            if !(2..=3).contains(&parts.len()) {
+                anyhow::bail!("expected HH:MM or HH:MM:SS, got {time:?}");
+            }
+            for (slot, part) in time_parts.iter_mut().zip(parts) {
+                *slot = num(part)?;
+            }
+        }
+
+        let date = TiDate {
+            year: num(year)?,
+            month: num(month)?,
+            day: num(day)?,
+            hour: time_parts[0],
+            minute: time_parts[1],
+            second: time_parts[2],
+            millisecond,
+        };
+        date.validate()?;
+        Ok(date)
+    }
+
+    /// Check every field is in range, including the day for the month (leap years count).
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let checks = [
+            (statics::TI_DATE_MONTH, self.month, 1, 12),
+            (
+                statics::TI_DATE_DAY,
+                self.day,
+                1,
+                days_in_month(self.year, self.month),
+            ),
+            (statics::TI_DATE_HOUR, self.hour, 0, 23),
+            (statics::TI_DATE_MINUTE, self.minute, 0, 59),
+            (statics::TI_DATE_SECOND, self.second, 0, 59),
+            (statics::TI_DATE_MILLISECOND, self.millisecond, 0, 999),
+        ];
+        for (name, value, min, max) in checks {
+            if !(min..=max).contains(&value) {
+                anyhow::bail!("{name} {value} is out of range {min}..={max}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Days in `month` (1-12) of `year`; 31 for an out-of-range month so callers can still clamp.
+pub fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TiDate, days_in_month};
+    use crate::TiValue;
+
+    #[test]
+    fn date_object_round_trips_in_game_key_order() {
+        let text = r#"{"year":1970, "month":1, "day":1, "hour":0, "minute":0, "second":0, "millisecond":0}"#;
+        let val = TiValue::parse_json5(text).unwrap();
+        let date = TiDate::from_value(&val).expect("date");
+        assert_eq!(date.to_value().to_json5_compact(), text);
+
+        let shuffled = TiValue::parse_json5(
+            "{day:25, month:4, year:2031, hour:11, minute:34, second:49, millisecond:71}",
+        )
+        .unwrap();
+        let date = TiDate::from_value(&shuffled).expect("date");
+        assert_eq!(date.format(), "2031-04-25 11:34:49.071");
+
+        assert!(TiDate::from_value(&TiValue::parse_json5("{year:1, month:1}").unwrap()).is_none());
+    }
+
+    #[test]
+    fn parse_and_format_round_trip() {
+        let date = TiDate::parse("2031-04-25 11:34:49.071").unwrap();
+        assert_eq!(TiDate::parse(&date.format()).unwrap(), date);
+
+        let short = TiDate::parse("2024-02-29").unwrap();
+        assert_eq!(short.format(), "2024-02-29 00:00:00.000");
+        assert_eq!(TiDate::parse("2024-02-29T08:30").unwrap().minute, 30);
+
+        assert!(TiDate::parse("2023-02-29").is_err());
+        assert!(TiDate::parse("2031-13-01").is_err());
+        assert!(TiDate::parse("2031-04-01 24:00").is_err());
+        assert!(TiDate::parse("April 1st").is_err());
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+    }
+}
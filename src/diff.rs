@@ -0,0 +1,328 @@
+//! Minimal line diff used to show exactly which lines of a save will change.
+//! Saves can have millions of lines but edits usually touch a handful, so this trims the common
+//! prefix/suffix and runs Myers' O(ND) diff on what remains, giving up on very large edit sets.
+
+/// Above this many differing lines, the changed region is reported as one replace block
+/// instead of running the full diff (keeps time bounded on huge rewrites).
+const MAX_EDIT_DISTANCE: usize = 4000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+/// A group of nearby changes with surrounding context. Line numbers are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub new_start: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Compute the changed lines between `old` and `new`, grouped into hunks with up to
+/// `context` unchanged lines on each side. Returns no hunks when the texts are equal.
+pub fn changed_lines(old: &str, new: &str, context: usize) -> Vec<DiffHunk> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mid_a = &a[prefix..a.len() - suffix];
+    let mid_b = &b[prefix..b.len() - suffix];
+
+    let mut ops = vec![Op::Equal; prefix];
+    match myers(mid_a, mid_b) {
+        Some(mid_ops) => ops.extend(mid_ops),
+        None => {
+            ops.extend(std::iter::repeat_n(Op::Delete, mid_a.len()));
+            ops.extend(std::iter::repeat_n(Op::Insert, mid_b.len()));
+        }
+    }
+    ops.extend(std::iter::repeat_n(Op::Equal, suffix));
+
+    build_hunks(&a, &b, &ops, context)
+}
+
+/// Myers' shortest edit script, in the linear-space divide-and-conquer form: each step finds
+/// the middle snake of an optimal path and recurses on the two sides, so memory stays
+/// proportional to the input rather than to the edit distance squared. Returns `None` if the
+/// edit distance exceeds `MAX_EDIT_DISTANCE`.
+fn myers(a: &[&str], b: &[&str]) -> Option<Vec<Op>> {
+    let mut ops = Vec::with_capacity(a.len() + b.len());
+    diff_into(a, b, MAX_EDIT_DISTANCE.div_ceil(2), &mut ops)?;
+    Some(ops)
+}
+
+/// Append the edit script of `a` to `b` to `ops`, giving up (`None`) if the middle snake isn't
+/// found within `rounds` rounds, i.e. if the edit distance is above `2 * rounds`.
+fn diff_into(a: &[&str], b: &[&str], rounds: usize, ops: &mut Vec<Op>) -> Option<()> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+
+    ops.extend(std::iter::repeat_n(Op::Equal, prefix));
+    if a.is_empty() || b.is_empty() {
+        ops.extend(std::iter::repeat_n(Op::Delete, a.len()));
+        ops.extend(std::iter::repeat_n(Op::Insert, b.len()));
+    } else {
+        // With the common ends trimmed the distance is at least 2, so both halves are smaller.
+        let snake = middle_snake(a, b, rounds)?;
+        diff_into(&a[..snake.x], &b[..snake.y], usize::MAX, ops)?;
+        ops.extend(std::iter::repeat_n(Op::Equal, snake.u - snake.x));
+        diff_into(&a[snake.u..], &b[snake.v..], usize::MAX, ops)?;
+    }
+    ops.extend(std::iter::repeat_n(Op::Equal, suffix));
+    Some(())
+}
+
+/// The diagonal run from `(x, y)` to `(u, v)` that an optimal path crosses halfway through.
+struct Snake {
+    x: usize,
+    y: usize,
+    u: usize,
+    v: usize,
+}
+
+/// Run the forward search from `(0, 0)` and the backward search from the end at once until
+/// they overlap; the snake where they do lies on an optimal path.
+fn middle_snake(a: &[&str], b: &[&str], rounds: usize) -> Option<Snake> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = ((n + m + 1) / 2).min(rounds.min(isize::MAX as usize) as isize);
+    let offset = max + 1;
+    // Furthest x reached on each diagonal; backward x counts from the end of `a`.
+    let mut fwd = vec![0isize; (2 * offset + 1) as usize];
+    let mut bwd = vec![0isize; (2 * offset + 1) as usize];
+    let furthest = |v: &[isize], d: isize, k: isize| {
+        let idx = (offset + k) as usize;
+        if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+            v[idx + 1]
+        } else {
+            v[idx - 1] + 1
+        }
+    };
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let x0 = furthest(&fwd, d, k);
+            let (mut x, mut y) = (x0, x0 - k);
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            fwd[(offset + k) as usize] = x;
+            let back_k = delta - k;
+            if odd && back_k.abs() < d && x + bwd[(offset + back_k) as usize] >= n {
+                return Some(Snake {
+                    x: x0 as usize,
+                    y: (x0 - k) as usize,
+                    u: x as usize,
+                    v: y as usize,
+                });
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let x0 = furthest(&bwd, d, k);
+            let (mut x, mut y) = (x0, x0 - k);
+            while x < n && y < m && a[(n - 1 - x) as usize] == b[(m - 1 - y) as usize] {
+                x += 1;
+                y += 1;
+            }
+            bwd[(offset + k) as usize] = x;
+            let fwd_k = delta - k;
+            if !odd && fwd_k.abs() <= d && x + fwd[(offset + fwd_k) as usize] >= n {
+                return Some(Snake {
+                    x: (n - x) as usize,
+                    y: (m - y) as usize,
+                    u: (n - x0) as usize,
+                    v: (m - x0 + k) as usize,
+                });
+            }
+        }
+    }
+    None
+}
+
+fn build_hunks(a: &[&str], b: &[&str], ops: &[Op], context: usize) -> Vec<DiffHunk> {
+    // Positions (in both files) at the start of each op.
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut i, mut j) = (0usize, 0usize);
+    for op in ops {
+        positions.push((i, j));
+        match op {
+            Op::Equal => {
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+
+    let mut hunks = Vec::new();
+    let mut idx = 0;
+    while idx < ops.len() {
+        if ops[idx] == Op::Equal {
+            idx += 1;
+            continue;
+        }
+        // Extend the hunk while the gap between changes is small enough to share context.
+        let start = idx.saturating_sub(context);
+        let mut end = idx;
+        loop {
+            while end < ops.len() && ops[end] != Op::Equal {
+                end += 1;
+            }
+            let next_change = ops[end..].iter().position(|op| *op != Op::Equal);
+            match next_change {
+                Some(gap) if gap <= context * 2 => end += gap,
+                _ => break,
+            }
+        }
+        let stop = (end + context).min(ops.len());
+
+        let (old_start, new_start) = positions[start];
+        let lines = (start..stop)
+            .map(|p| {
+                let (i, j) = positions[p];
+                match ops[p] {
+                    Op::Equal => DiffLine::Context(a[i].to_string()),
+                    Op::Delete => DiffLine::Removed(a[i].to_string()),
+                    Op::Insert => DiffLine::Added(b[j].to_string()),
+                }
+            })
+            .collect();
+        hunks.push(DiffHunk {
+            old_start: old_start + 1,
+            new_start: new_start + 1,
+            lines,
+        });
+        idx = stop;
+    }
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffLine, Op, changed_lines, myers};
+
+    #[test]
+    fn equal_texts_have_no_hunks() {
+        assert!(changed_lines("a\nb\nc", "a\nb\nc", 3).is_empty());
+    }
+
+    #[test]
+    fn single_line_change_is_reported_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng";
+        let new = "a\nb\nc\nD\ne\nf\ng";
+        let hunks = changed_lines(old, new, 1);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].old_start, 3);
+        assert_eq!(hunks[0].new_start, 3);
+        assert_eq!(
+            hunks[0].lines,
+            vec![
+                DiffLine::Context("c".into()),
+                DiffLine::Removed("d".into()),
+                DiffLine::Added("D".into()),
+                DiffLine::Context("e".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn distant_changes_make_separate_hunks_and_inserts_align() {
+        let old: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+        let mut new = old.clone();
+        new.insert(2, "x".into());
+        new.remove(18);
+        let hunks = changed_lines(&old.join("\n"), &new.join("\n"), 1);
+        assert_eq!(hunks.len(), 2);
+        assert!(hunks[0].lines.contains(&DiffLine::Added("x".into())));
+        assert!(hunks[1].lines.contains(&DiffLine::Removed("17".into())));
+        assert_eq!(hunks[1].old_start, 17);
+    }
+
+    #[test]
+    fn myers_finds_a_shortest_edit_script() {
+        // Small pseudo-random texts over a few letters, checked against a plain LCS table.
+        let mut seed = 12345u32;
+        let mut next = |len: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) % len
+        };
+        let letters = ["a", "b", "c", "d"];
+        for _ in 0..200 {
+            let a: Vec<&str> = (0..next(12)).map(|_| letters[next(4) as usize]).collect();
+            let b: Vec<&str> = (0..next(12)).map(|_| letters[next(4) as usize]).collect();
+            let ops = myers(&a, &b).unwrap();
+
+            let (mut i, mut j, mut rebuilt) = (0, 0, Vec::new());
+            for op in &ops {
+                match op {
+                    Op::Equal => {
+                        assert_eq!(a[i], b[j]);
+                        rebuilt.push(a[i]);
+                        i += 1;
+                        j += 1;
+                    }
+                    Op::Delete => i += 1,
+                    Op::Insert => {
+                        rebuilt.push(b[j]);
+                        j += 1;
+                    }
+                }
+            }
+            assert_eq!((i, j), (a.len(), b.len()));
+            assert_eq!(rebuilt, b);
+
+            let mut lcs = vec![vec![0; b.len() + 1]; a.len() + 1];
+            for i in (0..a.len()).rev() {
+                for j in (0..b.len()).rev() {
+                    lcs[i][j] = if a[i] == b[j] {
+                        lcs[i + 1][j + 1] + 1
+                    } else {
+                        lcs[i + 1][j].max(lcs[i][j + 1])
+                    };
+                }
+            }
+            let edits = ops.iter().filter(|op| **op != Op::Equal).count();
+            assert_eq!(edits, a.len() + b.len() - 2 * lcs[0][0], "{a:?} -> {b:?}");
+        }
+    }
+
+    #[test]
+    fn myers_gives_up_past_the_edit_distance_cap() {
+        let old: Vec<String> = (0..3000).map(|i| format!("old {i}")).collect();
+        let new: Vec<String> = (0..3000).map(|i| format!("new {i}")).collect();
+        let a: Vec<&str> = old.iter().map(String::as_str).collect();
+        let b: Vec<&str> = new.iter().map(String::as_str).collect();
+        assert_eq!(myers(&a, &b), None);
+        assert_eq!(
+            myers(&a[..1000], &b[..1000]).map(|ops| ops.len()),
+            Some(2000)
+        );
+    }
+}
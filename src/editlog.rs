@@ -0,0 +1,187 @@
+//! Plain-text log of the edits made in a session. Each edit is written as `#` comment lines a
+//! person can read, followed by one compact JSON5 record holding the exact before/after values.
+
+use crate::{LoadedSave, TiValue, statics};
+use anyhow::Context;
+use indexmap::IndexMap;
+
+/// Start of every comment line in a log.
+pub const LOG_COMMENT: &str = "#";
+
+/// One property edit. `None` means the property was absent (added or removed by the edit).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub group: String,
+    pub id: i64,
+    pub prop: String,
+    pub before: Option<TiValue>,
+    pub after: Option<TiValue>,
+}
+
+impl LogEntry {
+    /// The machine-readable line for this edit, e.g.
+    /// `{"group":"PlayerState", "id":7, "prop":"name", "before":"a", "after":"b"}`.
+    /// Absent values are left out rather than written as `null`.
+    pub fn to_record(&self) -> String {
+        let mut map = IndexMap::new();
+        map.insert(
+            statics::BATCH_KEY_GROUP.to_string(),
+            TiValue::String(self.group.clone()),
+        );
+        map.insert(
+            statics::BATCH_KEY_ID.to_string(),
+            TiValue::Number(crate::value::TiNumber::I64(self.id)),
+        );
+        map.insert(
+            statics::BATCH_KEY_PROP.to_string(),
+            TiValue::String(self.prop.clone()),
+        );
+        if let Some(before) = &self.before {
+            map.insert(statics::LOG_KEY_BEFORE.to_string(), before.clone());
+        }
+        if let Some(after) = &self.after {
+            map.insert(statics::LOG_KEY_AFTER.to_string(), after.clone());
+        }
+        TiValue::Object(map).to_json5_compact()
+    }
+}
+
+/// Parse a log written with `LogEntry::to_record` lines. Blank and comment lines are ignored.
+/// Returns each record with its 1-based line number, plus a message for every line that did not parse.
+pub fn parse_log(text: &str) -> (Vec<(usize, LogEntry)>, Vec<String>) {
+    let mut entries = Vec::new();
+    let mut problems = Vec::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with(LOG_COMMENT) {
+            continue;
+        }
+        match parse_record(trimmed) {
+            Ok(entry) => entries.push((line, entry)),
+            Err(e) => problems.push(format!("line {line}: {e:#}")),
+        }
+    }
+    (entries, problems)
+}
+
+fn parse_record(text: &str) -> anyhow::Result<LogEntry> {
+    let record = TiValue::parse_json5(text).context("invalid record")?;
+    let str_field = |key: &str| {
+        record
+            .get(key)
+            .and_then(TiValue::as_str)
+            .map(str::to_string)
+            .with_context(|| format!("{key:?} must be a string"))
+    };
+    let id = match record.get(statics::BATCH_KEY_ID) {
+        Some(TiValue::Number(n)) => n.as_i64(),
+        _ => None,
+    }
+    .with_context(|| format!("{:?} must be an integer", statics::BATCH_KEY_ID))?;
+
+    Ok(LogEntry {
+        group: str_field(statics::BATCH_KEY_GROUP)?,
+        id,
+        prop: str_field(statics::BATCH_KEY_PROP)?,
+        before: record.get(statics::LOG_KEY_BEFORE).cloned(),
+        after: record.get(statics::LOG_KEY_AFTER).cloned(),
+    })
+}
+
+/// Replay parsed entries against `save` in order, via `set_property`/`remove_property`.
+/// An entry is only applied while the property still holds its recorded `before` value; entries
+/// whose object is gone or whose property has since changed are skipped and reported, as are
+/// whole-object entries (empty `prop`), which can't be replayed.
+/// Returns the applied entries (group resolved to its full name) and the problems.
+pub fn replay(
+    save: &mut LoadedSave,
+    entries: &[(usize, LogEntry)],
+) -> (Vec<LogEntry>, Vec<String>) {
+    let mut applied = Vec::new();
+    let mut problems = Vec::new();
+    for (line, entry) in entries {
+        if entry.prop.is_empty() {
+            problems.push(format!(
+                "line {line}: whole-object edits can't be replayed: {} {}",
+                entry.group, entry.id
+            ));
+            continue;
+        }
+        let target = format!("{} {}.{}", entry.group, entry.id, entry.prop);
+        let Some(group) = save.resolve_group(&entry.group).map(str::to_string) else {
+            problems.push(format!("line {line}: group not found: {target}"));
+            continue;
+        };
+        let Some(obj) = save.get_object_value(&group, entry.id) else {
+            problems.push(format!("line {line}: object not found: {target}"));
+            continue;
+        };
+        if !same_value(obj.get(&entry.prop), entry.before.as_ref()) {
+            problems.push(format!("line {line}: value no longer matches: {target}"));
+            continue;
+        }
+
+        let res = match &entry.after {
+            Some(value) => save
+                .set_property(&group, entry.id, &entry.prop, value.clone())
+                .map(|_| ()),
+            None => save
+                .remove_property(&group, entry.id, &entry.prop)
+                .map(|_| ()),
+        };
+        match res {
+            Ok(()) => applied.push(LogEntry {
+                group,
+                ..entry.clone()
+            }),
+            Err(e) => problems.push(format!("line {line}: {e:#}")),
+        }
+    }
+    if !applied.is_empty() {
+        save.rebuild_index();
+    }
+    (applied, problems)
+}
+
+/// Compare by serialized form, so e.g. a parsed `5` matches a stored unsigned `5`.
+fn same_value(a: Option<&TiValue>, b: Option<&TiValue>) -> bool {
+    a.map(TiValue::to_json5_compact) == b.map(TiValue::to_json5_compact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogEntry, parse_log};
+    use crate::TiValue;
+
+    #[test]
+    fn record_omits_absent_values() {
+        let entry = LogEntry {
+            group: "G".to_string(),
+            id: 3,
+            prop: "p".to_string(),
+            before: None,
+            after: Some(TiValue::String("x".to_string())),
+        };
+        assert_eq!(
+            entry.to_record(),
+            r#"{"group":"G", "id":3, "prop":"p", "after":"x"}"#
+        );
+    }
+
+    #[test]
+    fn parse_log_reads_records_back_and_reports_bad_lines() {
+        let entry = LogEntry {
+            group: "G".to_string(),
+            id: 3,
+            prop: "p".to_string(),
+            before: Some(TiValue::Null),
+            after: None,
+        };
+        let text = format!("# header\n\n{}\nnot json\n", entry.to_record());
+        let (entries, problems) = parse_log(&text);
+        assert_eq!(entries, vec![(3, entry)]);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("line 4:"));
+    }
+}
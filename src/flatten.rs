@@ -0,0 +1,145 @@
+//! Flattened `path = value` view of a save, one leaf per line, for grepping and diffing with
+//! standard text tools. Paths are JSON Pointers (see `TiValue::pointer`), values are compact JSON5.
+
+use crate::{TiValue, value::escape_pointer_token};
+
+/// Separator between the path and the value on each line.
+pub const FLAT_SEPARATOR: &str = " = ";
+
+/// Write every primitive leaf of `root` as a `path = value` line.
+/// Empty arrays and objects are emitted as leaves too, so the output covers the whole structure.
+pub fn export_flat(root: &TiValue) -> String {
+    let mut out = String::new();
+    let mut path = String::new();
+    write_flat(root, &mut path, &mut out);
+    out
+}
+
+fn write_flat(val: &TiValue, path: &mut String, out: &mut String) {
+    let len = path.len();
+    match val {
+        TiValue::Array(values) if !values.is_empty() => {
+            for (i, v) in values.iter().enumerate() {
+                path.push('/');
+                path.push_str(&i.to_string());
+                write_flat(v, path, out);
+                path.truncate(len);
+            }
+        }
+        TiValue::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                path.push('/');
+                path.push_str(&escape_pointer_token(k));
+                write_flat(v, path, out);
+                path.truncate(len);
+            }
+        }
+        _ => {
+            out.push_str(path);
+            out.push_str(FLAT_SEPARATOR);
+            out.push_str(&val.to_json5_compact());
+            out.push('\n');
+        }
+    }
+}
+
+/// One `path = value` line read back from a flattened file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatEdit {
+    /// 1-based line number in the source text, for error reports.
+    pub line: usize,
+    pub path: String,
+    pub value: TiValue,
+}
+
+/// Parse a flattened file. Blank lines and lines starting with `#` are ignored.
+/// Returns the edits that parsed, plus a message for every line that did not.
+pub fn parse_flat(text: &str) -> (Vec<FlatEdit>, Vec<String>) {
+    let mut edits = Vec::new();
+    let mut problems = Vec::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let Some((path, value_text)) = trimmed.split_once(FLAT_SEPARATOR) else {
+            problems.push(format!("line {line}: expected `path{FLAT_SEPARATOR}value`"));
+            continue;
+        };
+        match TiValue::parse_json5(value_text.trim()) {
+            Ok(value) => edits.push(FlatEdit {
+                line,
+                path: path.trim().to_string(),
+                value,
+            }),
+            Err(e) => problems.push(format!("line {line}: invalid value: {e:#}")),
+        }
+    }
+    (edits, problems)
+}
+
+/// Set each edit's path to its value via `TiValue::pointer_mut`. Only existing paths are set;
+/// returns a message for every edit whose path does not resolve.
+pub fn apply_flat(root: &mut TiValue, edits: &[FlatEdit]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for edit in edits {
+        match root.pointer_mut(&edit.path) {
+            Some(target) => *target = edit.value.clone(),
+            None => problems.push(format!(
+                "line {}: path does not resolve: {}",
+                edit.line, edit.path
+            )),
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_flat, export_flat, parse_flat};
+    use crate::TiValue;
+
+    #[test]
+    fn export_flat_lists_every_leaf_with_pointer_paths() {
+        let v = TiValue::parse_json5(
+            r#"{ gamestates: { "A.B": [ { Value: { po: { Resist: 0.3 }, name: "x/y", e: [] } } ] } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            export_flat(&v),
+            "/gamestates/A.B/0/Value/po/Resist = 0.3\n\
+             /gamestates/A.B/0/Value/name = \"x/y\"\n\
+             /gamestates/A.B/0/Value/e = []\n"
+        );
+        assert_eq!(
+            v.pointer("/gamestates/A.B/0/Value/po/Resist")
+                .map(TiValue::to_json5_compact)
+                .as_deref(),
+            Some("0.3")
+        );
+    }
+
+    #[test]
+    fn flat_import_roundtrips_export_and_reports_bad_lines() {
+        let mut v = TiValue::parse_json5(r#"{ a: { b: 1, c: "x" }, d: [true] }"#).unwrap();
+        let exported = export_flat(&v);
+        let (edits, problems) = parse_flat(&exported);
+        assert!(problems.is_empty());
+        assert_eq!(edits.len(), 3);
+
+        let text =
+            "# comment\n/a/b = 2\n\n/d/0 = false\n/a/missing = 3\nno separator\n/a/c = {oops\n";
+        let (edits, mut problems) = parse_flat(text);
+        assert_eq!(edits.len(), 3);
+        problems.extend(apply_flat(&mut v, &edits));
+        assert_eq!(
+            v.to_json5_compact(),
+            r#"{"a":{"b":2, "c":"x"}, "d":[false]}"#
+        );
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].starts_with("line 6:"));
+        assert!(problems[1].starts_with("line 7: invalid value"));
+        assert_eq!(problems[2], "line 5: path does not resolve: /a/missing");
+    }
+}
@@ -1,8 +1,16 @@
+use crate::date::{TiDate, days_in_month};
+use crate::diff::DiffLine;
+use crate::notes::ObjectNotes;
+use crate::save::{MissingRefTarget, NonFiniteNumber, ObjectSummary};
+use crate::settings::{Settings, UI_SCALE_RANGE};
 use crate::statics;
-use crate::{LoadedSave, TiValue};
+use crate::{LoadedSave, SaveFormat, TiValue};
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
-use std::{path::PathBuf, sync::OnceLock};
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 #[derive(Clone, Debug)]
 enum PublicOpinionDrag {
@@ -26,9 +34,17 @@ pub fn run_gui() -> eframe::Result {
     eframe::run_native(
         &title,
         options,
-        Box::new(|_cc| {
+        Box::new(|cc| {
+            let settings = Settings::load();
+            cc.egui_ctx.set_visuals(theme_visuals(&settings));
+            let ui_scale_input = settings.clamped_ui_scale();
             Ok(Box::new(TiseApp {
-                theme_dark: true,
+                collapse_ref_types: true,
+                copy_remap_refs: true,
+                raw_editor_wrap: true,
+                settings,
+                ui_scale_input,
+                list_insert_type: statics::EN_TYPE_NULL,
                 ..Default::default()
             }))
         }),
@@ -60,6 +76,10 @@ struct TiseApp {
     history_back: Vec<i64>,
     history_forward: Vec<i64>,
     sort_objects_by_id: bool,
+    // Objects list: property shown after each object's name, per group.
+    object_list_columns: std::collections::HashMap<String, String>,
+    // Float editor: "Edit as %" choices by property name, overriding `percent_edit_default`.
+    percent_edit: std::collections::HashMap<String, bool>,
     go_to_id_open: bool,
     go_to_id_input: String,
     go_to_id_request_focus: bool,
@@ -68,16 +88,34 @@ struct TiseApp {
     undo_stack: Vec<EditAction>,
     redo_stack: Vec<EditAction>,
     changes_open: bool,
+    changes_filter: String,
+    changes_by_object: bool,
+    next_batch_id: u64,
+
+    // Flatten import and log replay: window title and problems from the last import or replay,
+    // shown until dismissed.
+    flat_import_report: Option<(&'static str, Vec<String>)>,
 
     // Feature parity: About dialog.
     about_open: bool,
 
+    // Recent errors and status messages, oldest first (see `set_status`/`set_error`), and the
+    // Log window.
+    message_log: std::collections::VecDeque<LoggedMessage>,
+    message_log_open: bool,
+
     // Feature: Search & Reference Browser.
     search_ref_browser_open: bool,
     search_ref_browser_query: String,
     search_ref_browser_request_focus: bool,
+    search_ref_min_id: String,
+    search_ref_max_id: String,
     search_ref_cache: Option<Vec<i64>>,
     search_ref_cache_query: String,
+    search_ref_cache_range: (String, String),
+    // Ref browser: list reference targets no object has, instead of searching objects.
+    search_ref_missing_mode: bool,
+    search_ref_missing_cache: Option<Vec<MissingRefTarget>>,
 
     // Feature: Search Items (scan all keys/values).
     search_items_open: bool,
@@ -93,14 +131,174 @@ struct TiseApp {
     public_opinion_remainder: Option<f64>,
     public_opinion_drag: Option<PublicOpinionDrag>,
 
+    // Editor: friendly form shown above the property table for councilors.
+    councilor_form: Option<CouncilorForm>,
+
+    // Editor: "Match Key Order" window for the selected object; holds the reference ID typed.
+    key_order_ref_input: Option<String>,
+
+    // Editor: value type used by list Add/Insert and Add property (a type label, see
+    // LIST_INSERT_TYPES). Object lists always add empty objects.
+    list_insert_type: &'static str,
+
     // Editor: change property type popup.
     change_type_open: bool,
     change_type_preview: Option<TiValue>,
+    change_type_loss: Option<String>,
+    change_type_loss_confirmed: bool,
+    /// Set instead of `change_type_loss` for a lossless but risky conversion; also needs confirming.
+    change_type_warning: Option<String>,
+
+    // Preferences saved between sessions (theme, panel layout, UI scale, ...).
+    settings: Settings,
+    // UI scale slider position; only stored in `settings` (and applied) once the drag ends.
+    ui_scale_input: f32,
+
+    // Comparison: a second save shown read-only beside the main one, following its selection
+    // unless another object of the selected group is picked there.
+    other_save: Option<LoadedSave>,
+    other_object_id: Option<i64>,
+    copy_remap_refs: bool,
+
+    // Display: show references as "id: name" rather than their `{value, $type}` object.
+    collapse_ref_types: bool,
+
+    // Editor: wrap long lines in the raw multiline editor (otherwise it scrolls sideways).
+    raw_editor_wrap: bool,
+
+    // Editor: use the multiline string editor even for short strings, so line breaks can be typed.
+    string_editor_multiline: bool,
+
+    // Diagnostics: set by every edit; with `settings.validate_after_edit` the whole save is then
+    // checked to still round-trip at the end of the frame.
+    validate_pending: bool,
+
+    // Selection: property to reselect once the newly selected object can be looked up in the save
+    // (see `apply_pending_property`).
+    pending_property: Option<String>,
+
+    // Diagnostics: NaN/Infinity numbers found by the last scan; the window is open while `Some`.
+    non_finite: Option<Vec<NonFiniteNumber>>,
+
+    // What the last opened file turned out to be, shown until dismissed.
+    load_summary: Option<String>,
+
+    // Find Property window; open while `Some`.
+    property_query: Option<PropertyQuery>,
+
+    // Name typed into the selected object's Add property row.
+    add_property_name: String,
+
+    // Diagnostics: group whose skipped (malformed) entries are shown in a window.
+    malformed_entries_group: Option<String>,
+
+    // Diagnostics: flag property values whose serialized text isn't stable across a re-parse.
+    show_round_trip_column: bool,
+    // Diagnostics: show floats' exact stored value in previews instead of the shortest form.
+    show_full_float_precision: bool,
+
+    // Save: confirmation showing the line diff before writing, see `Settings::preview_before_save`.
+    save_preview: Option<SavePreview>,
+
+    // Notes: per-object notes for the loaded save, stored in a sidecar file (see `notes`), and
+    // the save whose sidecar is behind them (see `flush_notes`).
+    notes: ObjectNotes,
+    notes_unsaved: Option<PathBuf>,
+
+    // Save: path chosen in Save As, waiting for the user to confirm the format it will be
+    // written in (or to pick one, if its extension implies a different format than the save's).
+    save_as_confirm_path: Option<PathBuf>,
+}
+
+/// Types offered for new list items; each is created via `coerce_value_to_type` from null.
+const LIST_INSERT_TYPES: [&str; 5] = [
+    statics::EN_TYPE_NULL,
+    statics::EN_TYPE_I64,
+    statics::EN_TYPE_F64,
+    statics::EN_TYPE_STRING,
+    statics::EN_TYPE_BOOL,
+];
+
+/// Strings longer than this (in chars) are edited in a multiline box instead of a single line.
+const LONG_STRING_CHARS: usize = 80;
+
+/// Properties listed in a reference's hover popup.
+const REF_PEEK_PROPS: usize = 8;
+
+/// Lines of unchanged context shown around each change in the save preview.
+const SAVE_PREVIEW_CONTEXT: usize = 3;
+
+/// Messages the Log window keeps; older ones are dropped.
+const MESSAGE_LOG_LIMIT: usize = 200;
+
+/// A pending save waiting for the user to confirm the previewed changes.
+struct SavePreview {
+    path: PathBuf,
+    rows: Vec<SavePreviewRow>,
+    removed: usize,
+    added: usize,
+}
+
+enum SavePreviewRow {
+    Header(String),
+    Line(DiffLine),
+}
+
+/// Councilor properties covered by the councilor editor.
+const COUNCILOR_FORM_PROPS: [&str; 5] = [
+    statics::TI_PROP_PERSONAL_NAME,
+    statics::TI_PROP_FAMILY_NAME,
+    statics::TI_PROP_DISPLAY_NAME,
+    statics::TI_PROP_ATTRIBUTES,
+    statics::TI_PROP_TRAIT_TEMPLATE_NAMES,
+];
+
+/// An entry in the Log window: a status message, or an error shown in the error bar.
+#[derive(Clone, Debug, PartialEq)]
+struct LoggedMessage {
+    error: bool,
+    text: String,
+}
+
+/// Buffered inputs of the councilor editor. Rebuilt whenever the object's values change
+/// underneath it (selection, undo/redo, edits made through the property table).
+#[derive(Clone, Debug, PartialEq)]
+struct CouncilorForm {
+    object_id: i64,
+    source: Vec<Option<TiValue>>,
+    personal_name: String,
+    family_name: String,
+    display_name: String,
+    attributes: Vec<(String, i64)>,
+    traits: Vec<String>,
+    new_trait: String,
+}
+
+/// Keyboard/button navigation within the objects panel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ObjectNav {
+    First,
+    Last,
+    PageUp,
+    PageDown,
+    Prev,
+    Next,
+}
 
-    // Theme.
-    theme_dark: bool,
+/// Row operations shared by the list editors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ListOp {
+    Delete(usize),
+    Insert(usize),
+    Duplicate(usize),
+    MoveUp(usize),
+    MoveDown(usize),
+    Move { from: usize, to: usize },
 }
 
+/// Drag-and-drop payload for list editor rows: the index of the row being dragged.
+struct ListDragPayload(usize);
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 enum ItemSortKey {
     Group,
@@ -119,14 +317,29 @@ struct ItemSearchHit {
     value_preview: String,
 }
 
+/// Structural search: which objects have a property at all, whatever its value.
+#[derive(Clone, Debug, Default)]
+struct PropertyQuery {
+    prop: String,
+    // `None` searches every group, including the root pseudo-object.
+    group: Option<String>,
+    // From the last Find; `None` until then.
+    results: Option<Vec<(String, i64)>>,
+    request_focus: bool,
+}
+
 #[derive(Clone, Debug)]
 struct EditAction {
     group: String,
     object_id: i64,
+    // Empty for actions on the whole object (e.g. copying one in): `before`/`after` then hold
+    // the object's value, or `None` while it doesn't exist.
     prop: String,
     before: Option<TiValue>,
     after: Option<TiValue>,
     description: String,
+    // Actions sharing a batch id (e.g. from one import) are undone/redone together.
+    batch: Option<u64>,
 }
 
 impl TiseApp {
@@ -180,7 +393,8 @@ impl TiseApp {
         remainder: &mut f64,
         enabled: bool,
     ) -> bool {
-        let side = ui.available_width().clamp(160.0, 260.0);
+        // Shrinks with the panel (e.g. at a large UI scale) rather than overflowing it.
+        let side = ui.available_width().clamp(80.0, 260.0);
         let (rect, response) = ui.allocate_exact_size(
             egui::vec2(side, side),
             if enabled {
@@ -528,7 +742,9 @@ impl TiseApp {
         save: &LoadedSave,
         query: &str,
         max_results: usize,
+        collapse_refs: bool,
     ) -> Vec<ItemSearchHit> {
+        let names = collapse_refs.then_some(&save.index.id_to_display_name);
         let query = query.trim();
         if query.is_empty() {
             return Vec::new();
@@ -557,7 +773,7 @@ impl TiseApp {
                         group_display: group_display.clone(),
                         object_id: obj.id,
                         prop: k.clone(),
-                        value_preview: value_preview(v),
+                        value_preview: value_preview_refs(v, names),
                     });
 
                     if hits.len() >= max_results {
@@ -614,14 +830,10 @@ impl TiseApp {
             return;
         };
 
-        let (obj_clone, val_clone) = {
-            let Some(obj) = save.get_object_value(&group, object_id) else {
-                return;
-            };
-            (obj.clone(), obj.get(&prop).cloned())
+        let Some(obj) = save.get_object_value(&group, object_id) else {
+            return;
         };
-
-        let Some(val) = val_clone.as_ref() else {
+        let Some(val) = obj.get(&prop) else {
             // Property no longer exists.
             self.selected_property = None;
             self.edit_buffer.clear();
@@ -650,12 +862,14 @@ impl TiseApp {
         // The change-type preview (if open) is tied to the current value.
         self.change_type_preview = None;
 
-        self.refresh_public_opinion_editor(&obj_clone, &prop);
+        self.refresh_public_opinion_editor(obj, &prop);
     }
 
     fn navigate_to_action_target(&mut self, save: &LoadedSave, action: &EditAction) {
         self.select_object_programmatic(&action.group, action.object_id, true, true);
-        self.selected_property = Some(action.prop.clone());
+        self.selected_property = (!action.prop.is_empty()).then(|| action.prop.clone());
+        // The target property wins over the one sticky selection carried over.
+        self.pending_property = None;
         self.scroll_properties_to_selected = true;
         self.scroll_align_center = true;
         self.refresh_selected_property_from_save(save);
@@ -677,6 +891,7 @@ impl TiseApp {
         }
     }
 
+    /// Write an action's before/after value into the save. The caller reindexes afterwards.
     fn apply_action_to_save(save: &mut LoadedSave, action: &EditAction, use_after: bool) -> bool {
         let target = if use_after {
             action.after.clone()
@@ -684,6 +899,25 @@ impl TiseApp {
             action.before.clone()
         };
 
+        if action.prop.is_empty() {
+            let res = match target.and_then(|v| v.as_object().cloned()) {
+                // Present before and after (e.g. keys reordered): replace it where it is.
+                Some(value)
+                    if let Some(obj) =
+                        save.get_object_value_mut(&action.group, action.object_id) =>
+                {
+                    *obj = value;
+                    save.mark_dirty();
+                    Ok(())
+                }
+                Some(value) => save.insert_object(&action.group, action.object_id, value),
+                None => save
+                    .remove_object(&action.group, action.object_id)
+                    .map(|_| ()),
+            };
+            return res.is_ok();
+        }
+
         let Some(obj) = save.get_object_value_mut(&action.group, action.object_id) else {
             return false;
         };
@@ -696,73 +930,191 @@ impl TiseApp {
                 obj.shift_remove(&action.prop);
             }
         }
-
-        save.rebuild_index();
-        save.refresh_dirty();
         true
     }
 
     fn record_action(&mut self, action: EditAction) {
+        self.validate_pending = true;
         self.undo_stack.push(action);
         self.redo_stack.clear();
     }
 
-    fn undo(&mut self) {
-        let Some(action) = self.undo_stack.pop() else {
-            return;
+    /// Split off the last history entry: a single action, or every trailing action of its batch.
+    fn take_history_step(stack: &mut Vec<EditAction>) -> Vec<EditAction> {
+        let Some(last) = stack.last() else {
+            return Vec::new();
         };
-
-        if self.save.is_none() {
-            self.undo_stack.push(action);
-            return;
+        let count = match last.batch {
+            Some(batch) => stack
+                .iter()
+                .rev()
+                .take_while(|a| a.batch == Some(batch))
+                .count(),
+            None => 1,
         };
+        stack.split_off(stack.len() - count)
+    }
 
-        let applied = {
-            let save = self.save.as_mut().expect("checked above");
-            Self::apply_action_to_save(save, &action, false)
-        };
+    /// Take one history step, or every step with `all`, in the order they should be applied.
+    /// Extending the opposite stack with the steps in this order keeps single-step redo/undo
+    /// working afterwards.
+    fn take_history_steps(stack: &mut Vec<EditAction>, all: bool) -> Vec<Vec<EditAction>> {
+        let mut steps = Vec::new();
+        loop {
+            let step = Self::take_history_step(stack);
+            if step.is_empty() {
+                break;
+            }
+            steps.push(step);
+            if !all {
+                break;
+            }
+        }
+        steps
+    }
 
-        if applied {
-            self.status = format!("{} {}", statics::EN_PREFIX_UNDO, action.description);
-            self.last_error = None;
-            let save = self.save.take().unwrap();
-            self.navigate_to_action_target(&save, &action);
-            self.save = Some(save);
-            self.redo_stack.push(action);
-        } else {
-            self.last_error = Some(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
-            // put it back so we don't lose history on failure
-            self.undo_stack.push(action);
+    /// Whether every property action in `steps` finds its object when they are applied in the
+    /// order `step_history` uses, counting objects added or removed by earlier whole-object
+    /// actions of the same sequence. Whole-object actions are checked when applied: their object
+    /// is meant to come and go.
+    fn history_steps_resolvable(
+        save: &LoadedSave,
+        steps: &[Vec<EditAction>],
+        forward: bool,
+    ) -> bool {
+        let mut exists: std::collections::HashMap<(&str, i64), bool> =
+            std::collections::HashMap::new();
+        for step in steps {
+            let mut actions: Vec<&EditAction> = step.iter().collect();
+            if !forward {
+                actions.reverse();
+            }
+            for a in actions {
+                let key = (a.group.as_str(), a.object_id);
+                if a.prop.is_empty() {
+                    let target = if forward { &a.after } else { &a.before };
+                    exists.insert(key, target.is_some());
+                } else if !*exists
+                    .entry(key)
+                    .or_insert_with(|| save.get_object_value(&a.group, a.object_id).is_some())
+                {
+                    return false;
+                }
+            }
         }
+        true
+    }
+
+    fn undo(&mut self) {
+        self.step_history(false, false);
     }
 
     fn redo(&mut self) {
-        let Some(action) = self.redo_stack.pop() else {
-            return;
-        };
+        self.step_history(true, false);
+    }
 
+    /// Undo (`forward == false`) or redo one history step, moving it to the opposite stack.
+    /// With `all`, every step on the stack is applied in turn, reindexing only once at the end.
+    fn step_history(&mut self, forward: bool, all: bool) {
         if self.save.is_none() {
-            self.redo_stack.push(action);
             return;
+        }
+        let (from, to) = if forward {
+            (&mut self.redo_stack, &mut self.undo_stack)
+        } else {
+            (&mut self.undo_stack, &mut self.redo_stack)
         };
+        let steps = Self::take_history_steps(from, all);
+        if steps.is_empty() {
+            return;
+        }
+
+        let save = self.save.as_mut().expect("checked above");
+        if !Self::history_steps_resolvable(save, &steps, forward) {
+            // put it back so we don't lose history on failure
+            for step in steps.into_iter().rev() {
+                from.extend(step);
+            }
+            self.set_error(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
+            return;
+        }
+
+        // Undo restores "before" values newest-first; redo re-applies "after" values in order.
+        for step in &steps {
+            if forward {
+                for a in step {
+                    Self::apply_action_to_save(save, a, true);
+                }
+            } else {
+                for a in step.iter().rev() {
+                    Self::apply_action_to_save(save, a, false);
+                }
+            }
+        }
+        save.rebuild_index();
+        save.refresh_dirty();
+        self.validate_pending = true;
 
-        let applied = {
-            let save = self.save.as_mut().expect("checked above");
-            Self::apply_action_to_save(save, &action, true)
+        let prefix = if forward {
+            statics::EN_PREFIX_REDO
+        } else {
+            statics::EN_PREFIX_UNDO
+        };
+        let count: usize = steps.iter().map(Vec::len).sum();
+        let first = steps[0][0].clone();
+        let status = if all {
+            format!("{prefix} all ({count} changes)")
+        } else if count > 1 {
+            format!("{prefix} {} ({count} changes)", first.description)
+        } else {
+            format!("{prefix} {}", first.description)
         };
 
-        if applied {
-            self.status = format!("{} {}", statics::EN_PREFIX_REDO, action.description);
-            self.last_error = None;
-            let save = self.save.take().unwrap();
-            self.navigate_to_action_target(&save, &action);
-            self.save = Some(save);
-            self.undo_stack.push(action);
+        // The last action applied is where the save ends up; show that object.
+        let last_step = steps.last().expect("non-empty");
+        let target = if forward {
+            last_step.last()
         } else {
-            self.last_error = Some(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
-            self.redo_stack.push(action);
+            last_step.first()
+        }
+        .expect("steps are never empty")
+        .clone();
+        for step in steps {
+            to.extend(step);
+        }
+        self.set_status(status);
+        self.last_error = None;
+
+        let save = self.save.take().unwrap();
+        self.navigate_to_action_target(&save, &target);
+        // Undoing a copy (or redoing its removal) leaves the target gone.
+        self.revalidate_selection(&save);
+        self.save = Some(save);
+    }
+
+    /// Check the selection against the rebuilt index after a structural edit: follow the object
+    /// to its current group, or clear the selection if it no longer exists, so no edit lands on
+    /// a stale ID.
+    fn revalidate_selection(&mut self, save: &LoadedSave) {
+        let Some(id) = self.selected_object_id else {
+            return;
+        };
+        match save.object_group(id) {
+            Some(group) => {
+                if self.selected_group.as_deref() != Some(group) {
+                    self.selected_group = Some(group.to_string());
+                }
+            }
+            None => {
+                self.selected_object_id = None;
+                self.selected_property = None;
+                self.pending_property = None;
+                self.edit_buffer.clear();
+                self.raw_edit_mode = false;
+            }
         }
     }
+
     fn value_for_editing(val: &TiValue) -> String {
         if matches!(val, TiValue::Array(_) | TiValue::Object(_))
             && val.is_relational_ref().is_none()
@@ -804,13 +1156,7 @@ impl TiseApp {
         match src {
             TiValue::Number(crate::value::TiNumber::I64(v)) => *v,
             TiValue::Number(crate::value::TiNumber::U64(v)) => i64::try_from(*v).unwrap_or(0),
-            TiValue::Number(crate::value::TiNumber::F64(v)) => {
-                if v.is_finite() {
-                    *v as i64
-                } else {
-                    0
-                }
-            }
+            TiValue::Number(crate::value::TiNumber::F64(v)) if v.is_finite() => *v as i64,
             TiValue::Bool(b) => i64::from(*b),
             TiValue::String(s) => s.trim().parse::<i64>().unwrap_or(0),
             _ => 0,
@@ -821,12 +1167,8 @@ impl TiseApp {
         match src {
             TiValue::Number(crate::value::TiNumber::U64(v)) => *v,
             TiValue::Number(crate::value::TiNumber::I64(v)) => u64::try_from(*v).unwrap_or(0),
-            TiValue::Number(crate::value::TiNumber::F64(v)) => {
-                if v.is_finite() && *v >= 0.0 {
-                    *v as u64
-                } else {
-                    0
-                }
+            TiValue::Number(crate::value::TiNumber::F64(v)) if v.is_finite() && *v >= 0.0 => {
+                *v as u64
             }
             TiValue::Bool(b) => u64::from(*b),
             TiValue::String(s) => s.trim().parse::<u64>().unwrap_or(0),
@@ -837,13 +1179,7 @@ impl TiseApp {
     fn coerce_to_f64(src: &TiValue) -> f64 {
         match src {
             TiValue::Number(n) => Self::as_f64_lossy(n),
-            TiValue::Bool(b) => {
-                if *b {
-                    1.0
-                } else {
-                    0.0
-                }
-            }
+            TiValue::Bool(b) => f64::from(u8::from(*b)),
             TiValue::String(s) => {
                 if let Some(n) = Self::parse_number_like(s) {
                     Self::as_f64_lossy(&n)
@@ -873,6 +1209,31 @@ impl TiseApp {
         TiValue::Array(Vec::new())
     }
 
+    /// Build a relational reference `{ $type?, value: id }`.
+    fn make_reference(type_hint: Option<String>, id: i64) -> TiValue {
+        let mut map = indexmap::IndexMap::new();
+        if let Some(t) = type_hint {
+            map.insert(statics::TI_REF_FIELD_TYPE.to_string(), TiValue::String(t));
+        }
+        map.insert(
+            statics::TI_REF_FIELD_VALUE.to_string(),
+            TiValue::Number(crate::value::TiNumber::I64(id)),
+        );
+        TiValue::Object(map)
+    }
+
+    /// Remove the first paste event whose text is a bare integer and return that integer.
+    fn take_pasted_integer(input: &mut egui::InputState) -> Option<i64> {
+        let pos = input
+            .events
+            .iter()
+            .position(|e| matches!(e, egui::Event::Paste(t) if t.trim().parse::<i64>().is_ok()))?;
+        match input.events.remove(pos) {
+            egui::Event::Paste(t) => t.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
     fn coerce_to_reference(src: &TiValue) -> TiValue {
         // Our ref detector treats { value: <int> } as a ref; $type is optional.
         let target_id = match src {
@@ -889,12 +1250,7 @@ impl TiseApp {
             _ => 0,
         };
 
-        let mut map = indexmap::IndexMap::new();
-        map.insert(
-            statics::TI_REF_FIELD_VALUE.to_string(),
-            TiValue::Number(crate::value::TiNumber::I64(target_id)),
-        );
-        TiValue::Object(map)
+        Self::make_reference(None, target_id)
     }
 
     fn coerce_value_to_type(label: &str, src: &TiValue) -> TiValue {
@@ -928,6 +1284,91 @@ impl TiseApp {
             _ => src.clone(),
         }
     }
+
+    /// The Change Type label matching a scalar's current type.
+    fn scalar_type_label(val: &TiValue) -> Option<&'static str> {
+        Some(match val {
+            TiValue::Bool(_) => statics::EN_TYPE_BOOL,
+            TiValue::Number(crate::value::TiNumber::I64(_)) => statics::EN_TYPE_I64,
+            TiValue::Number(crate::value::TiNumber::U64(_)) => statics::EN_TYPE_U64,
+            TiValue::Number(crate::value::TiNumber::F64(..)) => statics::EN_TYPE_F64,
+            TiValue::String(_) => statics::EN_TYPE_STRING,
+            _ => return None,
+        })
+    }
+
+    /// Describe what converting `src` to `converted` (via type `label`) would throw away, if anything.
+    /// Containers lose data whenever their contents don't survive unchanged; scalars lose data when
+    /// converting back to the original type wouldn't restore the original value.
+    fn change_type_data_loss(label: &str, src: &TiValue, converted: &TiValue) -> Option<String> {
+        // Compare serialized forms so e.g. an i64 vs u64 representation of the same number matches.
+        let unchanged = || converted.to_json5_compact() == src.to_json5_compact();
+        match src {
+            TiValue::Array(values) if !values.is_empty() && !unchanged() => Some(format!(
+                "{} {}",
+                values.len(),
+                statics::EN_WARN_LOSS_ARRAY_ELEMENTS
+            )),
+            TiValue::Object(map) if !map.is_empty() && !unchanged() => Some(format!(
+                "{} {}",
+                map.len(),
+                statics::EN_WARN_LOSS_OBJECT_FIELDS
+            )),
+            TiValue::Null | TiValue::Array(_) | TiValue::Object(_) => None,
+            _ => {
+                // Scalars wrapped in an array or a reference keep their payload.
+                let payload = match converted {
+                    TiValue::Array(values) if values.len() == 1 => Some(&values[0]),
+                    TiValue::Array(_) => None,
+                    TiValue::Object(map) => map.get(statics::TI_REF_FIELD_VALUE),
+                    other => Some(other),
+                };
+                let restored = payload.zip(Self::scalar_type_label(src)).map(|(p, orig)| {
+                    value_preview(&Self::coerce_value_to_type(orig, p)) == value_preview(src)
+                });
+                (restored != Some(true)).then(|| {
+                    format!(
+                        "'{}' {} {label}; {} '{}'.",
+                        value_preview(src),
+                        statics::EN_WARN_LOSS_NOT_REPRESENTABLE,
+                        statics::EN_WARN_LOSS_BECOMES,
+                        value_preview(converted)
+                    )
+                })
+            }
+        }
+    }
+
+    /// The number subtypes before and after a Change Type that converts between them.
+    fn number_subtype_change(
+        src: &TiValue,
+        converted: &TiValue,
+    ) -> Option<(&'static str, &'static str)> {
+        let (TiValue::Number(_), TiValue::Number(_)) = (src, converted) else {
+            return None;
+        };
+        let before = Self::scalar_type_label(src)?;
+        let after = Self::scalar_type_label(converted)?;
+        (before != after).then_some((before, after))
+    }
+
+    /// Warn about converting a number to or from f64 when it is past 2^53, beyond which f64 can't
+    /// hold every integer: the value may survive here but be rounded wherever else it's read.
+    fn number_subtype_warning(src: &TiValue, converted: &TiValue) -> Option<String> {
+        let (before, after) = Self::number_subtype_change(src, converted)?;
+        let TiValue::Number(n) = src else {
+            return None;
+        };
+        let involves_float = before == statics::EN_TYPE_F64 || after == statics::EN_TYPE_F64;
+        (involves_float && Self::as_f64_lossy(n).abs() > statics::F64_EXACT_INT_LIMIT).then(|| {
+            format!(
+                "'{}' {}",
+                value_preview(src),
+                statics::EN_WARN_LARGE_NUMBER_SUBTYPE
+            )
+        })
+    }
+
     fn is_simple_object(map: &indexmap::IndexMap<String, TiValue>) -> bool {
         if map.is_empty() {
             return false;
@@ -940,9 +1381,109 @@ impl TiseApp {
         })
     }
 
+    /// Total of a map whose values are all numbers, for the simple object editor's readout.
+    fn numeric_sum(map: &indexmap::IndexMap<String, TiValue>) -> Option<f64> {
+        map.values()
+            .map(|v| match v {
+                TiValue::Number(n) => Some(Self::as_f64_lossy(n)),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Unit hint for a known numeric property (see `statics::TI_UNIT_HINTS`).
+    fn unit_hint(prop: &str) -> Option<&'static str> {
+        statics::TI_UNIT_HINTS
+            .iter()
+            .find(|(name, _)| *name == prop)
+            .map(|(_, hint)| *hint)
+    }
+
+    /// The game's default for a known field (see `statics::TI_FIELD_DEFAULTS`).
+    fn known_default(group: &str, prop: &str) -> Option<TiValue> {
+        statics::TI_FIELD_DEFAULTS
+            .iter()
+            .find(|(g, name, _)| *g == group && *name == prop)
+            .and_then(|(_, _, text)| TiValue::parse_json5(text).ok())
+    }
+
+    /// Slider range implied by a unit hint; unbounded for units without a natural range.
+    fn unit_range(hint: Option<&str>) -> std::ops::RangeInclusive<f64> {
+        match hint {
+            Some(statics::EN_UNIT_FRACTION) => 0.0..=1.0,
+            Some(statics::EN_UNIT_SCALE_10) => 0.0..=10.0,
+            _ => f64::NEG_INFINITY..=f64::INFINITY,
+        }
+    }
+
+    /// Whether a float is edited as a percentage until toggled: on for known 0-1 fractions.
+    fn percent_edit_default(prop: &str) -> bool {
+        Self::unit_hint(prop) == Some(statics::EN_UNIT_FRACTION)
+    }
+
+    /// Drag input for a float shown as a percentage (`percent` is the value times 100), limited
+    /// to its unit's range scaled the same way.
+    fn percent_drag_value<'a>(
+        percent: &'a mut f64,
+        prop: &str,
+        decimals: Option<usize>,
+    ) -> egui::DragValue<'a> {
+        let range = Self::unit_range(Self::unit_hint(prop));
+        let drag = egui::DragValue::new(percent)
+            .speed(1.0)
+            .suffix(statics::EN_SUFFIX_PERCENT)
+            .range(range.start() * 100.0..=range.end() * 100.0)
+            .clamp_existing_to_range(false);
+        Self::with_float_decimals(drag, decimals)
+    }
+
+    /// Float drag input for `prop`, limited to its unit's range. Values already outside the range
+    /// are left alone so merely showing them doesn't change the save.
+    fn unit_drag_value<'a>(
+        value: &'a mut f64,
+        prop: &str,
+        decimals: Option<usize>,
+    ) -> egui::DragValue<'a> {
+        let hint = Self::unit_hint(prop);
+        let speed = if hint == Some(statics::EN_UNIT_FRACTION) {
+            0.01
+        } else {
+            0.1
+        };
+        let drag = egui::DragValue::new(value)
+            .speed(speed)
+            .range(Self::unit_range(hint))
+            .clamp_existing_to_range(false);
+        Self::with_float_decimals(drag, decimals)
+    }
+
+    /// Apply the `float_decimals` setting. Only the display is rounded; an untouched value
+    /// keeps its full precision.
+    fn with_float_decimals(
+        drag: egui::DragValue<'_>,
+        decimals: Option<usize>,
+    ) -> egui::DragValue<'_> {
+        match decimals {
+            Some(d) => drag.fixed_decimals(d),
+            None => drag,
+        }
+    }
+
+    /// Whether `resp` is a single-line editor that Enter just took focus from.
+    fn enter_pressed(ui: &egui::Ui, resp: &egui::Response) -> bool {
+        resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
+    }
+
+    fn unit_hint_label(ui: &mut egui::Ui, prop: &str) {
+        if let Some(hint) = Self::unit_hint(prop) {
+            ui.weak(hint);
+        }
+    }
+
     fn render_simple_object_editor(
         ui: &mut egui::Ui,
         map: &mut indexmap::IndexMap<String, TiValue>,
+        float_decimals: Option<usize>,
     ) -> bool {
         let mut changed_any = false;
         let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
@@ -1008,11 +1549,12 @@ impl TiseApp {
                                         }
                                         crate::value::TiNumber::F64(x) => {
                                             let mut tmp = *x;
-                                            let resp = ui.add(
-                                                egui::DragValue::new(&mut tmp)
-                                                    .speed(0.1)
-                                                    .range(f64::NEG_INFINITY..=f64::INFINITY),
-                                            );
+                                            let resp = ui.add(Self::unit_drag_value(
+                                                &mut tmp,
+                                                k,
+                                                float_decimals,
+                                            ));
+                                            Self::unit_hint_label(ui, k);
                                             if resp.changed() {
                                                 *x = tmp;
                                                 true
@@ -1036,6 +1578,58 @@ impl TiseApp {
         changed_any
     }
 
+    /// Apply a row operation from one of the list editors. `Insert` uses `new_item`;
+    /// `Duplicate` deep-clones the element and places the copy right after it.
+    /// Returns whether the array changed.
+    fn apply_list_op(arr: &mut Vec<TiValue>, op: ListOp, new_item: TiValue) -> bool {
+        match op {
+            ListOp::Delete(idx) if idx < arr.len() => {
+                arr.remove(idx);
+            }
+            ListOp::Insert(idx) if idx <= arr.len() => arr.insert(idx, new_item),
+            ListOp::Duplicate(idx) if idx < arr.len() => {
+                let copy = arr[idx].clone();
+                arr.insert(idx + 1, copy);
+            }
+            ListOp::MoveUp(idx) if idx > 0 && idx < arr.len() => arr.swap(idx, idx - 1),
+            ListOp::MoveDown(idx) if idx + 1 < arr.len() => arr.swap(idx, idx + 1),
+            ListOp::Move { from, to } if from != to && from < arr.len() && to < arr.len() => {
+                let item = arr.remove(from);
+                arr.insert(to, item);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Drag handle for a list row; dragging it carries the row index as the payload.
+    fn list_drag_handle(ui: &mut egui::Ui, id: egui::Id, idx: usize) {
+        ui.dnd_drag_source(id, ListDragPayload(idx), |ui| {
+            ui.monospace(format!("{} {idx}", statics::EN_DRAG_HANDLE));
+        });
+    }
+
+    /// While a list row is dragged over `resp`, mark the insertion point; on release, return the move.
+    fn list_drop_target(resp: &egui::Response, idx: usize) -> Option<ListOp> {
+        if resp.dnd_hover_payload::<ListDragPayload>().is_some() {
+            let stroke = resp.ctx.style().visuals.selection.stroke;
+            resp.ctx.layer_painter(resp.layer_id).hline(
+                resp.rect.x_range(),
+                resp.rect.top(),
+                stroke,
+            );
+        }
+        let from = resp.dnd_release_payload::<ListDragPayload>()?.0;
+        Some(Self::list_move_before(from, idx))
+    }
+
+    /// Move row `from` to just above row `before`. `Move` takes the final index, which is one
+    /// less than `before` when moving down, as the row leaves a gap above it.
+    fn list_move_before(from: usize, before: usize) -> ListOp {
+        let to = if from < before { before - 1 } else { before };
+        ListOp::Move { from, to }
+    }
+
     fn is_simple_list(arr: &[TiValue]) -> bool {
         arr.iter().all(|v| {
             matches!(
@@ -1045,16 +1639,36 @@ impl TiseApp {
         })
     }
 
-    fn render_simple_list_editor(ui: &mut egui::Ui, arr: &mut Vec<TiValue>) -> bool {
+    /// Picks the type of new list items and properties, from `LIST_INSERT_TYPES`.
+    fn insert_type_combo(ui: &mut egui::Ui, id_salt: &str, insert_type: &mut &'static str) {
+        let selected = if insert_type.is_empty() {
+            statics::EN_TYPE_NULL
+        } else {
+            *insert_type
+        };
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for label in LIST_INSERT_TYPES {
+                    ui.selectable_value(insert_type, label, label);
+                }
+            });
+    }
+
+    fn render_simple_list_editor(
+        ui: &mut egui::Ui,
+        arr: &mut Vec<TiValue>,
+        insert_type: &mut &'static str,
+        float_decimals: Option<usize>,
+    ) -> bool {
         let mut changed_any = false;
         let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
 
-        enum ListOp {
-            Delete(usize),
-            Insert(usize),
-            MoveUp(usize),
-            MoveDown(usize),
-        }
+        ui.horizontal(|ui| {
+            ui.label(statics::EN_LABEL_INSERT_TYPE);
+            Self::insert_type_combo(ui, "list_insert_type", insert_type);
+        });
+        let new_item = Self::coerce_value_to_type(insert_type, &TiValue::Null);
 
         let mut op: Option<ListOp> = None;
 
@@ -1065,7 +1679,7 @@ impl TiseApp {
                 .column(Column::initial(60.0).resizable(false))
                 .column(Column::remainder().resizable(true))
                 .column(Column::initial(80.0).resizable(false))
-                .column(Column::initial(140.0).resizable(false))
+                .column(Column::initial(180.0).resizable(false))
                 .header(row_h, |mut header| {
                     header.col(|ui| {
                         ui.strong(statics::EN_COL_INDEX);
@@ -1084,7 +1698,11 @@ impl TiseApp {
                     for (idx, v) in arr.iter_mut().enumerate() {
                         body.row(row_h, |mut row| {
                             row.col(|ui| {
-                                ui.monospace(idx.to_string());
+                                Self::list_drag_handle(
+                                    ui,
+                                    egui::Id::new(("simple_list_drag", idx)),
+                                    idx,
+                                );
                             });
                             row.col(|ui| {
                                 let changed = match v {
@@ -1127,11 +1745,12 @@ impl TiseApp {
                                         }
                                         crate::value::TiNumber::F64(x) => {
                                             let mut tmp = *x;
-                                            let resp = ui.add(
+                                            let resp = ui.add(Self::with_float_decimals(
                                                 egui::DragValue::new(&mut tmp)
                                                     .speed(0.1)
                                                     .range(f64::NEG_INFINITY..=f64::INFINITY),
-                                            );
+                                                float_decimals,
+                                            ));
                                             if resp.changed() {
                                                 *x = tmp;
                                                 true
@@ -1155,6 +1774,9 @@ impl TiseApp {
                                     if ui.small_button(statics::EN_BTN_INSERT).clicked() {
                                         op = Some(ListOp::Insert(idx));
                                     }
+                                    if ui.small_button(statics::EN_BTN_DUP).clicked() {
+                                        op = Some(ListOp::Duplicate(idx));
+                                    }
                                     if ui.small_button(statics::EN_BTN_UP).clicked() {
                                         op = Some(ListOp::MoveUp(idx));
                                     }
@@ -1166,43 +1788,23 @@ impl TiseApp {
                                     }
                                 });
                             });
+                            if let Some(drop) = Self::list_drop_target(&row.response(), idx) {
+                                op = Some(drop);
+                            }
                         });
                     }
                 });
         });
 
-        if let Some(op) = op {
-            match op {
-                ListOp::Delete(idx) => {
-                    if idx < arr.len() {
-                        arr.remove(idx);
-                        changed_any = true;
-                    }
-                }
-                ListOp::Insert(idx) => {
-                    if idx <= arr.len() {
-                        arr.insert(idx, TiValue::Null);
-                        changed_any = true;
-                    }
-                }
-                ListOp::MoveUp(idx) => {
-                    if idx > 0 && idx < arr.len() {
-                        arr.swap(idx, idx - 1);
-                        changed_any = true;
-                    }
-                }
-                ListOp::MoveDown(idx) => {
-                    if idx + 1 < arr.len() {
-                        arr.swap(idx, idx + 1);
-                        changed_any = true;
-                    }
-                }
-            }
+        if let Some(op) = op
+            && Self::apply_list_op(arr, op, new_item.clone())
+        {
+            changed_any = true;
         }
 
         ui.horizontal(|ui| {
             if ui.button(statics::EN_BTN_ADD_ITEM).clicked() {
-                arr.push(TiValue::Null);
+                arr.push(new_item);
                 changed_any = true;
             }
         });
@@ -1210,18 +1812,105 @@ impl TiseApp {
         changed_any
     }
 
-    fn nested_buffer_key(prop: &str, key: &str) -> String {
-        format!("{prop}::{key}")
+    /// Long strings and strings with line breaks get a multiline editor.
+    fn string_wants_multiline(s: &str) -> bool {
+        s.contains('\n') || s.chars().count() > LONG_STRING_CHARS
     }
 
-    fn render_mixed_object_editor(
+    fn is_object_list(arr: &[TiValue]) -> bool {
+        !arr.is_empty() && arr.iter().all(|v| matches!(v, TiValue::Object(_)))
+    }
+
+    /// Short label for an element of an object list: its index, the first string field if any,
+    /// and its field count.
+    fn object_list_item_label(idx: usize, map: &indexmap::IndexMap<String, TiValue>) -> String {
+        match map.values().find_map(TiValue::as_str) {
+            Some(s) => format!("[{idx}] {s} {{{}}}", map.len()),
+            None => format!("[{idx}] {{{}}}", map.len()),
+        }
+    }
+
+    /// Per-element editor for arrays of objects. Each element gets a collapsible mixed-object
+    /// editor, with insert/move/delete at the array level.
+    fn render_object_list_editor(
         &mut self,
         ui: &mut egui::Ui,
         prop: &str,
-        map: &mut indexmap::IndexMap<String, TiValue>,
+        arr: &mut Vec<TiValue>,
+        id_to_display_name: &std::collections::HashMap<i64, String>,
+    ) -> bool {
+        let mut changed_any = false;
+
+        let mut op: Option<ListOp> = None;
+
+        for (idx, v) in arr.iter_mut().enumerate() {
+            let TiValue::Object(map) = v else {
+                continue;
+            };
+            let row = ui.horizontal(|ui| {
+                Self::list_drag_handle(ui, egui::Id::new(("object_list_drag", prop, idx)), idx);
+                if ui.small_button(statics::EN_BTN_INSERT).clicked() {
+                    op = Some(ListOp::Insert(idx));
+                }
+                if ui.small_button(statics::EN_BTN_DUP).clicked() {
+                    op = Some(ListOp::Duplicate(idx));
+                }
+                if ui.small_button(statics::EN_BTN_UP).clicked() {
+                    op = Some(ListOp::MoveUp(idx));
+                }
+                if ui.small_button(statics::EN_BTN_DOWN).clicked() {
+                    op = Some(ListOp::MoveDown(idx));
+                }
+                if ui.small_button(statics::EN_BTN_DELETE).clicked() {
+                    op = Some(ListOp::Delete(idx));
+                }
+            });
+            if let Some(drop) = Self::list_drop_target(&row.response, idx) {
+                op = Some(drop);
+            }
+            egui::CollapsingHeader::new(Self::object_list_item_label(idx, map))
+                .id_salt(("object_list_item", prop, idx))
+                .show(ui, |ui| {
+                    let item_prop = format!("{prop}[{idx}]");
+                    if self.render_mixed_object_editor(ui, &item_prop, map, id_to_display_name) {
+                        changed_any = true;
+                    }
+                });
+        }
+
+        if let Some(op) = op {
+            let applied = Self::apply_list_op(arr, op, Self::empty_object());
+            if applied {
+                // Nested buffers are keyed by element index, which just shifted.
+                let item_prefix = format!("{prop}[");
+                self.nested_edit_buffers
+                    .retain(|k, _| !k.starts_with(&item_prefix));
+                changed_any = true;
+            }
+        }
+
+        if ui.button(statics::EN_BTN_ADD_ITEM).clicked() {
+            arr.push(Self::empty_object());
+            changed_any = true;
+        }
+
+        changed_any
+    }
+
+    fn nested_buffer_key(prop: &str, key: &str) -> String {
+        format!("{prop}::{key}")
+    }
+
+    fn render_mixed_object_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        prop: &str,
+        map: &mut indexmap::IndexMap<String, TiValue>,
+        id_to_display_name: &std::collections::HashMap<i64, String>,
     ) -> bool {
         let mut changed_any = false;
         let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
+        let float_decimals = self.settings.float_decimals;
 
         // Primitive fields in a compact table.
         ui.push_id(("mixed_object_table", prop), |ui| {
@@ -1300,11 +1989,12 @@ impl TiseApp {
                                         }
                                         crate::value::TiNumber::F64(x) => {
                                             let mut tmp = *x;
-                                            let resp = ui.add(
-                                                egui::DragValue::new(&mut tmp)
-                                                    .speed(0.1)
-                                                    .range(f64::NEG_INFINITY..=f64::INFINITY),
-                                            );
+                                            let resp = ui.add(Self::unit_drag_value(
+                                                &mut tmp,
+                                                k,
+                                                float_decimals,
+                                            ));
+                                            Self::unit_hint_label(ui, k);
                                             if resp.changed() {
                                                 *x = tmp;
                                                 true
@@ -1330,56 +2020,428 @@ impl TiseApp {
         ui.separator();
 
         // Structured fields with per-key nested JSON5 editor.
+        let mut nested_error = None;
         for (k, v) in map.iter_mut() {
             if matches!(v, TiValue::Array(_) | TiValue::Object(_)) {
-                let header = format!("{} ({})", k, v.type_name());
-                ui.collapsing(header, |ui| {
-                    let buf_key = Self::nested_buffer_key(prop, k);
-                    let default_text = match v {
-                        TiValue::Array(_) | TiValue::Object(_) => v.to_ti_save_pretty(),
-                        _ => v.to_json5_compact(),
-                    };
-                    let buf = self
-                        .nested_edit_buffers
-                        .entry(buf_key.clone())
-                        .or_insert(default_text);
-
-                    ui.label(statics::EN_LABEL_JSON5);
-                    let editor_h = (ui.available_height() * 0.6).clamp(120.0, 420.0);
-                    ui.add_sized(
-                        [ui.available_width(), editor_h],
-                        egui::TextEdit::multiline(buf).font(egui::TextStyle::Monospace),
-                    );
+                // References read as "key -> id: name"; expanding still shows the full object.
+                let header = match v.is_relational_ref() {
+                    Some(id) if self.collapse_ref_types => {
+                        format!("{k} -> {}", ref_label(id, id_to_display_name))
+                    }
+                    _ => format!(
+                        "{k} ({} {})",
+                        v.type_name(),
+                        count_badge(v).unwrap_or_default()
+                    ),
+                };
+                // Salted by key so the open state survives the count in the header changing.
+                egui::CollapsingHeader::new(header)
+                    .id_salt(("mixed_object_nested", prop, k.as_str()))
+                    .show(ui, |ui| {
+                        let buf_key = Self::nested_buffer_key(prop, k);
+                        let default_text = match v {
+                            TiValue::Array(_) | TiValue::Object(_) => v.to_ti_save_pretty(),
+                            _ => v.to_json5_compact(),
+                        };
+                        let buf = self
+                            .nested_edit_buffers
+                            .entry(buf_key.clone())
+                            .or_insert(default_text);
+
+                        ui.label(statics::EN_LABEL_JSON5);
+                        let editor_h = (ui.available_height() * 0.6).clamp(120.0, 420.0);
+                        ui.add_sized(
+                            [ui.available_width(), editor_h],
+                            egui::TextEdit::multiline(buf).font(egui::TextStyle::Monospace),
+                        );
 
-                    ui.horizontal(|ui| {
-                        if ui.button(statics::EN_BTN_APPLY).clicked() {
-                            match TiValue::parse_json5(buf.trim()) {
-                                Ok(parsed) => {
-                                    *v = parsed;
-                                    changed_any = true;
-                                    self.last_error = None;
-                                }
-                                Err(e) => {
-                                    self.last_error = Some(format!(
-                                        "Invalid JSON5 for nested value '{k}': {e:#}"
-                                    ));
+                        ui.horizontal(|ui| {
+                            if ui.button(statics::EN_BTN_APPLY).clicked() {
+                                match TiValue::parse_json5(buf.trim()) {
+                                    Ok(parsed) => {
+                                        *v = parsed;
+                                        changed_any = true;
+                                        self.last_error = None;
+                                    }
+                                    Err(e) => {
+                                        nested_error = Some(format!(
+                                            "Invalid JSON5 for nested value '{k}': {e:#}"
+                                        ));
+                                    }
                                 }
                             }
-                        }
-                        if ui.button(statics::EN_BTN_RESET).clicked() {
-                            *buf = match v {
-                                TiValue::Array(_) | TiValue::Object(_) => v.to_ti_save_pretty(),
-                                _ => v.to_json5_compact(),
-                            };
-                        }
+                            if ui.button(statics::EN_BTN_RESET).clicked() {
+                                *buf = match v {
+                                    TiValue::Array(_) | TiValue::Object(_) => v.to_ti_save_pretty(),
+                                    _ => v.to_json5_compact(),
+                                };
+                            }
+                        });
                     });
-                });
             }
         }
+        if let Some(e) = nested_error {
+            self.set_error(e);
+        }
 
         changed_any
     }
 
+    /// Switch between dark and light visuals and remember the choice.
+    fn toggle_theme(&mut self, ctx: &egui::Context) {
+        self.settings.theme_dark = !self.settings.theme_dark;
+        ctx.set_visuals(theme_visuals(&self.settings));
+        self.save_settings();
+    }
+
+    /// Store the settings, reporting a failure without interrupting the user.
+    fn save_settings(&mut self) {
+        if let Err(e) = self.settings.save() {
+            self.set_error(format!("{e:#}"));
+        }
+    }
+
+    /// Load a second save to show beside the main one for comparison.
+    fn open_other_file(&mut self) {
+        let Some(path) = self.file_dialog().pick_file() else {
+            return;
+        };
+        match LoadedSave::load_path(&path) {
+            Ok(save) => {
+                let mut status = format!("{} {}", statics::EN_STATUS_OPENED_OTHER, path.display());
+                if self
+                    .save
+                    .as_ref()
+                    .is_some_and(|main| main.content_hash() == save.content_hash())
+                {
+                    status = format!("{status} {}", statics::EN_STATUS_SAME_CONTENT);
+                }
+                self.set_status(status);
+                self.other_save = Some(save);
+                self.last_error = None;
+            }
+            Err(e) => self.set_error(format!("Failed to open {}: {e:#}", path.display())),
+        }
+    }
+
+    /// Copy an object from the other save into the main one under a fresh ID, as one undoable
+    /// step. The raised `currentID` is kept on undo; a gap in used IDs is harmless.
+    fn copy_object_from_other(&mut self, save: &mut LoadedSave, group: &str, object_id: i64) {
+        let Some(other) = self.other_save.take() else {
+            return;
+        };
+        let result = save.copy_object_from(&other, group, object_id, self.copy_remap_refs);
+        self.other_save = Some(other);
+        let copied = match result {
+            Ok(copied) => copied,
+            Err(e) => {
+                self.set_error(format!("{e:#}"));
+                return;
+            }
+        };
+
+        let description = format!(
+            "{} {object_id} -> {}",
+            statics::EN_PREFIX_COPIED_OBJECT,
+            copied.id
+        );
+        self.set_status(format!(
+            "{description} ({} {}, {} {})",
+            copied.remapped_refs,
+            statics::EN_COPY_REFS_REMAPPED,
+            copied.unresolved_refs,
+            statics::EN_COPY_REFS_UNRESOLVED
+        ));
+        self.last_error = None;
+        self.record_action(EditAction {
+            group: group.to_string(),
+            object_id: copied.id,
+            prop: String::new(),
+            before: None,
+            after: save
+                .get_object_value(group, copied.id)
+                .cloned()
+                .map(TiValue::Object),
+            description,
+            batch: None,
+        });
+        self.select_object_programmatic(group, copied.id, true, true);
+    }
+
+    /// Raise `currentID` to the highest ID in use, as one undoable step.
+    fn bump_current_id(&mut self, save: &mut LoadedSave) {
+        let current_id = |save: &LoadedSave| {
+            save.get_object_value(statics::ROOT_GROUP, statics::ROOT_OBJECT_ID)
+                .and_then(|root| root.get(statics::TI_PROP_CURRENT_ID))
+                .cloned()
+        };
+        let before = current_id(save);
+        let previous = match save.bump_current_id() {
+            Ok(previous) => previous,
+            Err(e) => {
+                self.set_error(format!("{e:#}"));
+                return;
+            }
+        };
+        self.validate_pending = true;
+        let description = format!(
+            "{} {previous} -> {}",
+            statics::EN_STATUS_CURRENT_ID_BUMPED,
+            save.game_id().unwrap_or_default()
+        );
+        self.set_status(description.clone());
+        self.last_error = None;
+        self.record_action(EditAction {
+            group: statics::ROOT_GROUP.to_string(),
+            object_id: statics::ROOT_OBJECT_ID,
+            prop: statics::TI_PROP_CURRENT_ID.to_string(),
+            before,
+            after: current_id(save),
+            description,
+            batch: None,
+        });
+    }
+
+    /// Reorder the selected object's keys like `reference_id`'s, as one undoable step.
+    fn match_key_order(&mut self, save: &mut LoadedSave, reference_id: i64) {
+        let (Some(group), Some(object_id)) = (self.selected_group.clone(), self.selected_object_id)
+        else {
+            return;
+        };
+        match save.match_key_order(&group, object_id, reference_id) {
+            Ok(Some((before, after))) => {
+                let description = format!(
+                    "{} {object_id}: {} {reference_id}",
+                    statics::EN_DESC_OBJECT_ID,
+                    statics::EN_DESC_MATCH_KEY_ORDER
+                );
+                self.set_status(description.clone());
+                self.last_error = None;
+                self.record_action(EditAction {
+                    group,
+                    object_id,
+                    prop: String::new(),
+                    before: Some(before),
+                    after: Some(after),
+                    description,
+                    batch: None,
+                });
+                save.refresh_dirty();
+            }
+            Ok(None) => {
+                self.set_status(statics::EN_STATUS_KEY_ORDER_UNCHANGED.to_string());
+                self.last_error = None;
+            }
+            Err(e) => self.set_error(format!("{e:#}")),
+        }
+    }
+
+    /// Set NaN/Infinity numbers to 0 as one undo step, then refresh the open scan results.
+    fn zero_non_finite_numbers(&mut self, save: &mut LoadedSave, found: &[NonFiniteNumber]) {
+        let batch = (found.len() > 1).then(|| {
+            self.next_batch_id += 1;
+            self.next_batch_id - 1
+        });
+        let mut fixed = 0;
+        for f in found {
+            match save.zero_non_finite(f) {
+                Ok((before, after)) => {
+                    let description = format!(
+                        "{} {}: {}",
+                        statics::EN_DESC_OBJECT_ID,
+                        f.object_id,
+                        Self::describe_change(&f.prop, Some(&before), Some(&after))
+                    );
+                    self.undo_stack.push(EditAction {
+                        group: f.group.clone(),
+                        object_id: f.object_id,
+                        prop: f.prop.clone(),
+                        before: Some(before),
+                        after: Some(after),
+                        description,
+                        batch,
+                    });
+                    fixed += 1;
+                }
+                Err(e) => self.set_error(format!("{e:#}")),
+            }
+        }
+        if fixed > 0 {
+            self.redo_stack.clear();
+            self.validate_pending = true;
+            save.refresh_dirty();
+            self.refresh_selected_property_from_save(save);
+            self.set_status(format!("{} {fixed}", statics::EN_STATUS_NON_FINITE_ZEROED));
+        }
+        if self.non_finite.is_some() {
+            self.non_finite = Some(save.non_finite_numbers());
+        }
+    }
+
+    /// Keys whose values differ between two objects, including keys only one of them has.
+    fn differing_props(
+        a: &indexmap::IndexMap<String, TiValue>,
+        b: &indexmap::IndexMap<String, TiValue>,
+    ) -> std::collections::HashSet<String> {
+        a.iter()
+            .filter(|(k, v)| b.get(*k) != Some(*v))
+            .map(|(k, _)| k.clone())
+            .chain(b.keys().filter(|k| !a.contains_key(*k)).cloned())
+            .collect()
+    }
+
+    /// The other save's object with the same ID as the main selection (or the object picked here),
+    /// read-only, with values that differ from the main save highlighted. Sets `copy_request` when
+    /// the user asks to copy the shown object into the main save. Returns `false` when the user
+    /// closes the pane.
+    fn render_other_save_panel(
+        &mut self,
+        ui: &mut egui::Ui,
+        save: &LoadedSave,
+        other: &LoadedSave,
+        copy_request: &mut Option<(String, i64)>,
+    ) -> bool {
+        let mut keep_open = true;
+        ui.horizontal(|ui| {
+            ui.heading(statics::EN_HEADING_OTHER_SAVE);
+            if ui.small_button(statics::EN_BTN_CLOSE).clicked() {
+                keep_open = false;
+            }
+        });
+        if let Some(path) = &other.source_path {
+            ui.label(path.display().to_string());
+        }
+        ui.separator();
+
+        // An object picked here wins while it belongs to the selected group.
+        let group_objects = self
+            .selected_group
+            .as_ref()
+            .and_then(|g| other.index.objects_by_group.get(g));
+        let picked = self
+            .other_object_id
+            .filter(|id| group_objects.is_some_and(|objs| objs.iter().any(|o| o.id == *id)));
+        if let Some(objects) = group_objects {
+            let names = &other.index.id_to_display_name;
+            let selected_text = match picked {
+                Some(id) => ref_label(id, names),
+                None => statics::EN_OTHER_FOLLOW_SELECTION.to_string(),
+            };
+            egui::ComboBox::from_id_salt("other_save_object")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut self.other_object_id,
+                        None,
+                        statics::EN_OTHER_FOLLOW_SELECTION,
+                    );
+                    for o in objects {
+                        ui.selectable_value(
+                            &mut self.other_object_id,
+                            Some(o.id),
+                            ref_label(o.id, names),
+                        );
+                    }
+                });
+        }
+
+        let Some(object_id) = picked.or(self.selected_object_id) else {
+            ui.label(statics::EN_SELECT_OBJECT);
+            return keep_open;
+        };
+        let Some(other_group) = other.object_group(object_id).map(str::to_string) else {
+            ui.label(statics::EN_OTHER_ID_MISSING);
+            return keep_open;
+        };
+        let Some(other_obj) = other.object_properties(&other_group, object_id) else {
+            ui.colored_label(egui::Color32::RED, statics::EN_ERR_OBJECT_VALUE_MISSING);
+            return keep_open;
+        };
+
+        ui.label(format!(
+            "{}  ID {object_id}",
+            LoadedSave::group_display_name(&other_group)
+        ));
+        // Top-level keys are settings of the save itself, not an object that can be copied.
+        if other_group != statics::ROOT_GROUP {
+            ui.horizontal(|ui| {
+                if ui
+                    .button(statics::EN_BTN_COPY_TO_MAIN)
+                    .on_hover_text(statics::EN_HINT_COPY_TO_MAIN)
+                    .clicked()
+                {
+                    *copy_request = Some((other_group.clone(), object_id));
+                }
+                ui.checkbox(&mut self.copy_remap_refs, statics::EN_COPY_REMAP_REFS)
+                    .on_hover_text(statics::EN_HINT_COPY_REMAP_REFS);
+            });
+        }
+        let main_obj = self
+            .selected_group
+            .as_deref()
+            .and_then(|g| save.object_properties(g, object_id));
+        let differing = match &main_obj {
+            Some(main_obj) => Self::differing_props(main_obj, &other_obj),
+            None => other_obj.keys().cloned().collect(),
+        };
+        ui.label(format!(
+            "{} {}",
+            differing.len(),
+            statics::EN_OTHER_DIFFERING
+        ));
+        ui.separator();
+
+        let names = self
+            .collapse_ref_types
+            .then_some(&other.index.id_to_display_name);
+        let mut properties: Vec<_> = other_obj.iter().collect();
+        properties.sort_by_key(|(k, _)| k.to_lowercase());
+        let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
+        ui.push_id("other_save_table", |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::initial(160.0).resizable(true))
+                .column(Column::remainder())
+                .header(row_h, |mut header| {
+                    header.col(|ui| {
+                        ui.strong(statics::EN_COL_PROPERTY);
+                    });
+                    header.col(|ui| {
+                        ui.strong(statics::EN_COL_VALUE);
+                    });
+                })
+                .body(|body| {
+                    body.rows(row_h, properties.len(), |mut row| {
+                        let (key, val) = properties[row.index()];
+                        let selected = self.selected_property.as_deref() == Some(key.as_str());
+                        row.col(|ui| {
+                            // Selecting a row selects the same property in the main save.
+                            if ui.selectable_label(selected, key.as_str()).clicked()
+                                && main_obj.as_ref().is_some_and(|o| o.contains_key(key))
+                            {
+                                self.selected_property = Some(key.clone());
+                                self.pending_property = None;
+                                self.scroll_properties_to_selected = true;
+                                self.refresh_selected_property_from_save(save);
+                            }
+                        });
+                        row.col(|ui| {
+                            let text = float_full_expansion(val)
+                                .filter(|_| self.show_full_float_precision)
+                                .unwrap_or_else(|| value_preview_refs(val, names));
+                            if differing.contains(key) {
+                                ui.colored_label(egui::Color32::YELLOW, text);
+                            } else {
+                                ui.label(text);
+                            }
+                        });
+                    });
+                });
+        });
+        keep_open
+    }
+
     fn default_save_dir() -> Option<PathBuf> {
         let home = std::env::var_os("USERPROFILE")
             .or_else(|| std::env::var_os("HOME"))
@@ -1399,8 +2461,8 @@ impl TiseApp {
     }
 
     fn file_dialog(&self) -> rfd::FileDialog {
-        let mut dlg =
-            rfd::FileDialog::new().add_filter("Terra Invicta Save", &["json", "json5", "gz"]);
+        let mut dlg = rfd::FileDialog::new()
+            .add_filter("Terra Invicta Save", &statics::SAVE_DIALOG_EXTENSIONS);
 
         if let Some(dir) = self.dialog_dir.clone().or_else(Self::initial_dialog_dir) {
             dlg = dlg.set_directory(dir);
@@ -1415,9 +2477,9 @@ impl TiseApp {
         };
 
         match LoadedSave::load_path(&path) {
-            Ok(save) => {
+            Ok(mut save) => {
                 self.dialog_dir = path.parent().map(PathBuf::from);
-                self.status = format!("Loaded {}", path.display());
+                self.set_status(format!("Loaded {}", path.display()));
                 self.selected_group = save.index.groups.first().cloned();
                 self.selected_object_id = None;
                 self.selected_property = None;
@@ -1427,8 +2489,27 @@ impl TiseApp {
                 self.scroll_objects_to_selected = false;
                 self.scroll_properties_to_selected = false;
                 self.scroll_align_center = false;
+                // Without the usual `gamestates` key the lists would otherwise just be empty.
+                let warnings: Vec<String> = save
+                    .gamestates_report()
+                    .into_iter()
+                    .chain((save.single_quoted_strings > 0).then(|| {
+                        format!(
+                            "{} {}",
+                            save.single_quoted_strings,
+                            statics::EN_WARN_SINGLE_QUOTED
+                        )
+                    }))
+                    .collect();
+                if warnings.is_empty() {
+                    self.last_error = None;
+                } else {
+                    self.set_error(warnings.join("; "));
+                }
+                save.minimize_diff = self.settings.minimize_diff;
+                save.empty_object_quirk = !self.settings.plain_empty_objects;
+                self.load_summary = Some(Self::load_summary(&save, &path));
                 self.save = Some(save);
-                self.last_error = None;
 
                 self.history_back.clear();
                 self.history_forward.clear();
@@ -1438,13 +2519,42 @@ impl TiseApp {
                 self.undo_stack.clear();
                 self.redo_stack.clear();
                 self.changes_open = false;
+                self.changes_filter.clear();
+                self.malformed_entries_group = None;
+                self.non_finite = None;
+                self.search_ref_missing_cache = None;
+
+                self.flush_notes();
+                self.notes = ObjectNotes::load_for(&path).unwrap_or_else(|e| {
+                    self.set_error(format!("Failed to load notes: {e:#}"));
+                    ObjectNotes::default()
+                });
             }
             Err(e) => {
-                self.last_error = Some(format!("Failed to load: {e:#}"));
+                self.set_error(format!("Failed to load: {e:#}"));
             }
         }
     }
 
+    /// One line saying what a just-opened file is: its format, game version and date.
+    fn load_summary(save: &LoadedSave, path: &Path) -> String {
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        let date = save.game_date().map(|d| d.format_day());
+        format!(
+            "{} {name} {} {} | {} {} | {} {}",
+            statics::EN_LOADED_OPENED,
+            statics::EN_LOADED_AS,
+            format_label(save.format),
+            statics::EN_LOADED_VERSION,
+            save.game_version().unwrap_or(statics::EN_LOADED_UNKNOWN),
+            statics::EN_LOADED_DATE,
+            date.as_deref().unwrap_or(statics::EN_LOADED_UNKNOWN),
+        )
+    }
+
     fn save_file(&mut self) {
         // UX: don't overwrite the loaded file by default.
         self.save_file_as();
@@ -1456,6 +2566,7 @@ impl TiseApp {
             && let Some(source_path) = save.source_path.as_ref()
             && let Some(file_name) = source_path.file_name()
         {
+            // The whole name, so `Save.json.gz` keeps both extensions.
             dlg = dlg.set_file_name(file_name.to_string_lossy());
         }
 
@@ -1463,44 +2574,724 @@ impl TiseApp {
             return;
         };
 
+        if self.save.is_some() {
+            self.save_as_confirm_path = Some(path);
+        }
+    }
+
+    /// Continue a Save As once the path (and so the format) is settled.
+    fn save_to_chosen_path(&mut self, path: PathBuf) {
+        if self.settings.preview_before_save {
+            self.open_save_preview(path);
+        } else {
+            self.write_save_to(&path);
+        }
+    }
+
+    fn open_save_preview(&mut self, path: PathBuf) {
+        let Some(save) = self.save.as_ref() else {
+            return;
+        };
+
+        let hunks = match save.pending_line_changes(SAVE_PREVIEW_CONTEXT) {
+            Ok(hunks) => hunks,
+            Err(e) => {
+                self.set_error(format!("Failed to preview changes: {e:#}"));
+                return;
+            }
+        };
+
+        let mut rows = Vec::new();
+        let (mut removed, mut added) = (0, 0);
+        for hunk in hunks {
+            rows.push(SavePreviewRow::Header(format!(
+                "@@ -{} +{} @@",
+                hunk.old_start, hunk.new_start
+            )));
+            for line in hunk.lines {
+                match line {
+                    DiffLine::Removed(_) => removed += 1,
+                    DiffLine::Added(_) => added += 1,
+                    DiffLine::Context(_) => {}
+                }
+                rows.push(SavePreviewRow::Line(line));
+            }
+        }
+
+        self.save_preview = Some(SavePreview {
+            path,
+            rows,
+            removed,
+            added,
+        });
+    }
+
+    fn write_save_to(&mut self, path: &std::path::Path) {
+        self.flush_notes();
         let Some(save) = self.save.as_mut() else {
             return;
         };
 
-        if let Err(e) = save.save_to_path(&path) {
-            self.last_error = Some(format!("Failed to save: {e:#}"));
+        if let Err(e) = save.save_to_path(path) {
+            self.set_error(format!("Failed to save: {e:#}"));
+        } else if !self.notes.is_empty()
+            && let Err(e) = self.notes.save_for(path)
+        {
+            // Notes follow the save to its new name.
+            self.set_error(format!("Saved, but failed to copy notes: {e:#}"));
         } else {
             self.dialog_dir = path.parent().map(PathBuf::from);
-            self.status = format!("Saved {}", path.display());
+            self.set_status(format!("Saved {}", path.display()));
             self.last_error = None;
         }
     }
 
-    fn select_object_user(&mut self, group: &str, id: i64) {
-        self.select_object_internal(group, id, true, false, false);
+    /// Collapsible note for the selected object. Edits are kept in memory and written to the
+    /// sidecar file when the editor loses focus (or the selection changes, or the app exits).
+    fn render_notes_editor(&mut self, ui: &mut egui::Ui, save: &LoadedSave, object_id: i64) {
+        let mut note = self.notes.get(object_id).unwrap_or_default().to_string();
+        let header = if note.is_empty() {
+            statics::EN_HEADING_NOTES.to_string()
+        } else {
+            format!("{} {}", statics::EN_GLYPH_NOTE, statics::EN_HEADING_NOTES)
+        };
+        egui::CollapsingHeader::new(header)
+            .id_salt("object_notes")
+            .show(ui, |ui| {
+                let resp = ui
+                    .add(
+                        egui::TextEdit::multiline(&mut note)
+                            .desired_rows(3)
+                            .desired_width(ui.available_width()),
+                    )
+                    .on_hover_text(statics::EN_HINT_NOTES);
+                if resp.changed() {
+                    self.notes.set(object_id, note);
+                    self.notes_unsaved = save.source_path.clone();
+                }
+                if resp.lost_focus() {
+                    self.flush_notes();
+                }
+            });
     }
 
-    fn select_object_programmatic(
+    /// Name field for a new property, suggesting the names other objects of the group use that
+    /// this one lacks (filtered by what was typed), so a typo doesn't add a key the game ignores.
+    fn render_add_property(
         &mut self,
+        ui: &mut egui::Ui,
+        save: &mut LoadedSave,
         group: &str,
-        id: i64,
-        record_history: bool,
-        center_on_target: bool,
+        object_id: i64,
+        value_obj: &indexmap::IndexMap<String, TiValue>,
     ) {
-        self.select_object_internal(group, id, record_history, true, center_on_target);
+        let mut add = false;
+        ui.horizontal(|ui| {
+            ui.label(statics::EN_LABEL_ADD_PROPERTY);
+            let resp = ui.add(
+                egui::TextEdit::singleline(&mut self.add_property_name)
+                    .hint_text(statics::EN_HINT_ADD_PROPERTY_NAME),
+            );
+            add |= Self::enter_pressed(ui, &resp);
+            let mut picked = None;
+            egui::ComboBox::from_id_salt("add_property_known")
+                .selected_text(statics::EN_LABEL_KNOWN_PROPERTIES)
+                .show_ui(ui, |ui| {
+                    let typed = self.add_property_name.trim().to_lowercase();
+                    // Only worked out while the list is open.
+                    for (key, count) in save.group_property_keys(group) {
+                        if value_obj.contains_key(&key) || !key.to_lowercase().contains(&typed) {
+                            continue;
+                        }
+                        if ui
+                            .selectable_label(false, format!("{key} ({count})"))
+                            .clicked()
+                        {
+                            picked = Some(key);
+                        }
+                    }
+                });
+            if let Some(key) = picked {
+                self.add_property_name = key;
+            }
+            ui.label(statics::EN_LABEL_INSERT_TYPE);
+            Self::insert_type_combo(ui, "add_property_type", &mut self.list_insert_type);
+            add |= ui.button(statics::EN_BTN_ADD_PROPERTY).clicked();
+        });
+        if add {
+            let name = self.add_property_name.trim().to_string();
+            let value = Self::coerce_value_to_type(self.list_insert_type, &TiValue::Null);
+            self.add_property(save, group, object_id, &name, value);
+        }
     }
 
-    fn select_object_internal(
+    /// Add `name` to the object and select it for editing, as one undoable edit.
+    fn add_property(
         &mut self,
+        save: &mut LoadedSave,
         group: &str,
-        id: i64,
-        record_history: bool,
-        request_scroll: bool,
-        center_on_target: bool,
+        object_id: i64,
+        name: &str,
+        value: TiValue,
     ) {
-        if record_history
-            && let Some(cur) = self.selected_object_id
-            && cur != id
+        if name.is_empty() {
+            return;
+        }
+        let Some(value_obj) = save.get_object_value_mut(group, object_id) else {
+            self.set_error(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
+            return;
+        };
+        if value_obj.contains_key(name) {
+            self.set_error(format!("{} {name:?}", statics::EN_ERR_PROPERTY_EXISTS));
+            return;
+        }
+        value_obj.insert(name.to_string(), value.clone());
+        save.rebuild_index();
+        save.refresh_dirty();
+
+        let desc = format!(
+            "{} {}: {}",
+            statics::EN_DESC_OBJECT_ID,
+            object_id,
+            Self::describe_change(name, None, Some(&value))
+        );
+        self.record_action(EditAction {
+            group: group.to_string(),
+            object_id,
+            prop: name.to_string(),
+            before: None,
+            after: Some(value),
+            description: desc.clone(),
+            batch: None,
+        });
+        self.set_status(desc);
+        self.last_error = None;
+        self.add_property_name.clear();
+        self.selected_property = Some(name.to_string());
+        self.pending_property = None;
+        self.scroll_properties_to_selected = true;
+        self.refresh_selected_property_from_save(save);
+    }
+
+    /// Write pending note edits to their save's sidecar file.
+    fn flush_notes(&mut self) {
+        if let Some(path) = self.notes_unsaved.take()
+            && let Err(e) = self.notes.save_for(&path)
+        {
+            self.set_error(format!("Failed to save notes: {e:#}"));
+        }
+    }
+
+    /// Drop groups with no objects from the Groups list. Groups with skipped (malformed) entries
+    /// stay, as does `keep` (the selected group) so the selection never disappears.
+    fn retain_nonempty_groups(groups: &mut Vec<String>, save: &LoadedSave, keep: Option<&str>) {
+        groups.retain(|g| {
+            g == statics::ROOT_GROUP
+                || keep == Some(g.as_str())
+                || save.index.malformed_by_group.contains_key(g)
+                || save
+                    .index
+                    .objects_by_group
+                    .get(g)
+                    .is_some_and(|objects| !objects.is_empty())
+        });
+    }
+
+    /// Order objects as the Objects panel lists them.
+    fn sort_objects(objects: &mut [&ObjectSummary], by_id: bool) {
+        if by_id {
+            objects.sort_by_key(|o| o.id);
+        } else {
+            objects.sort_by_key(|o| o.display_name.to_lowercase());
+        }
+    }
+
+    /// IDs of a group's objects in the Objects panel's order.
+    fn sorted_object_ids(&self, save: &LoadedSave, group: &str) -> Vec<i64> {
+        if group == statics::ROOT_GROUP {
+            return vec![statics::ROOT_OBJECT_ID];
+        }
+        let mut objects: Vec<_> = save
+            .index
+            .objects_by_group
+            .get(group)
+            .map(|v| v.iter().collect())
+            .unwrap_or_default();
+        Self::sort_objects(&mut objects, self.sort_objects_by_id);
+        objects.iter().map(|o| o.id).collect()
+    }
+
+    /// Move to the previous/next object of the selected group, keeping the selected property
+    /// when the new object has it too, so one field can be edited across a run of objects.
+    fn step_selected_object(&mut self, save: &LoadedSave, nav: ObjectNav) {
+        let Some(group) = self.selected_group.clone() else {
+            return;
+        };
+        let ids = self.sorted_object_ids(save, &group);
+        let Some(id) = Self::object_nav_target(&ids, self.selected_object_id, nav, 1) else {
+            return;
+        };
+        if self.selected_object_id == Some(id) {
+            return;
+        }
+        let prop = self.selected_property.clone();
+        self.select_object_internal(&group, id, true, true, false);
+        self.pending_property = prop;
+        self.apply_pending_property(save);
+    }
+
+    /// Select the property carried over from the previous object, if the selected object has it.
+    fn apply_pending_property(&mut self, save: &LoadedSave) {
+        let Some(prop) = self.pending_property.take() else {
+            return;
+        };
+        let (Some(group), Some(id)) = (self.selected_group.as_deref(), self.selected_object_id)
+        else {
+            return;
+        };
+        if save
+            .object_properties(group, id)
+            .is_some_and(|o| o.contains_key(&prop))
+        {
+            self.selected_property = Some(prop);
+            self.scroll_properties_to_selected = true;
+            self.refresh_selected_property_from_save(save);
+        }
+    }
+
+    /// Resolve a navigation request against the visible (sorted) object IDs.
+    /// Paging starts from the current selection, or from the top if nothing in the list is selected.
+    fn object_nav_target(
+        ids: &[i64],
+        current: Option<i64>,
+        nav: ObjectNav,
+        page: usize,
+    ) -> Option<i64> {
+        let last = ids.len().checked_sub(1)?;
+        let pos = current.and_then(|id| ids.iter().position(|x| *x == id));
+        let idx = match nav {
+            ObjectNav::First => 0,
+            ObjectNav::Last => last,
+            ObjectNav::PageUp => pos.unwrap_or(0).saturating_sub(page),
+            ObjectNav::PageDown => pos.map_or(0, |p| (p + page).min(last)),
+            ObjectNav::Prev => pos.unwrap_or(0).saturating_sub(1),
+            ObjectNav::Next => pos.map_or(0, |p| (p + 1).min(last)),
+        };
+        ids.get(idx).copied()
+    }
+
+    /// Parse an optional ID bound from a text field; blank means "no bound".
+    fn parse_id_bound(text: &str) -> Result<Option<i64>, std::num::ParseIntError> {
+        let text = text.trim();
+        if text.is_empty() {
+            Ok(None)
+        } else {
+            text.parse().map(Some)
+        }
+    }
+
+    /// Ref browser mode listing reference targets that no object has, with a jump to a referrer.
+    fn render_missing_ref_targets(&mut self, ui: &mut egui::Ui, save: &LoadedSave) {
+        let missing = self
+            .search_ref_missing_cache
+            .get_or_insert_with(|| save.missing_ref_targets())
+            .clone();
+        ui.horizontal(|ui| {
+            let refs: usize = missing.iter().map(|m| m.count).sum();
+            ui.label(format!(
+                "{} {}, {refs} {}",
+                missing.len(),
+                statics::EN_LABEL_MISSING_TARGETS,
+                statics::EN_LABEL_REFERENCES
+            ));
+            if ui.button(statics::EN_BTN_RESCAN).clicked() {
+                self.search_ref_missing_cache = None;
+            }
+        });
+        if missing.is_empty() {
+            return;
+        }
+
+        let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
+        ui.push_id("missing_ref_targets_table", |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::initial(60.0).resizable(false))
+                .column(Column::initial(90.0).resizable(true))
+                .column(Column::initial(60.0).resizable(true))
+                .column(Column::remainder().resizable(true))
+                .header(row_h, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("");
+                    });
+                    header.col(|ui| {
+                        ui.strong(statics::EN_COL_ID);
+                    });
+                    header.col(|ui| {
+                        ui.strong(statics::EN_COL_REF_COUNT);
+                    });
+                    header.col(|ui| {
+                        ui.strong(statics::EN_COL_REFERRER);
+                    });
+                })
+                .body(|body| {
+                    body.rows(row_h, missing.len(), |mut row| {
+                        let m = &missing[row.index()];
+                        let (group, object_id, prop) = &m.referrer;
+                        row.col(|ui| {
+                            if ui
+                                .small_button(statics::EN_BTN_GO)
+                                .on_hover_text(statics::EN_HINT_GO_TO_REFERRER)
+                                .clicked()
+                            {
+                                self.select_object_programmatic(group, *object_id, true, true);
+                                self.pending_property = Some(prop.clone());
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.monospace(m.id.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(m.count.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(format!(
+                                "{} {object_id}.{prop}",
+                                LoadedSave::group_display_name(group)
+                            ));
+                        });
+                    });
+                });
+        });
+    }
+
+    /// Whether a Changes entry matches `query`: an object ID substring, or a case-insensitive
+    /// substring of the property name or description.
+    fn action_matches_filter(action: &EditAction, query: &str) -> bool {
+        let query = query.trim();
+        if query.is_empty() {
+            return true;
+        }
+        let query_lower = query.to_lowercase();
+        action.object_id.to_string().contains(query)
+            || action.prop.to_lowercase().contains(&query_lower)
+            || action.description.to_lowercase().contains(&query_lower)
+    }
+
+    /// IDs (sorted) whose ID or display name matches `query` and that fall within `min..=max`.
+    fn filter_ref_ids(
+        id_to_display_name: &std::collections::HashMap<i64, String>,
+        query: &str,
+        min: Option<i64>,
+        max: Option<i64>,
+    ) -> Vec<i64> {
+        let query = query.trim();
+        let query_lower = query.to_lowercase();
+
+        let mut ids: Vec<i64> = id_to_display_name
+            .keys()
+            .copied()
+            .filter(|id| min.is_none_or(|m| *id >= m) && max.is_none_or(|m| *id <= m))
+            .collect();
+        ids.sort_unstable();
+
+        if query.is_empty() {
+            return ids;
+        }
+        ids.into_iter()
+            .filter(|id| {
+                let name = id_to_display_name
+                    .get(id)
+                    .map(String::as_str)
+                    .unwrap_or(statics::EN_EMPTY);
+
+                id.to_string().contains(query) || name.to_lowercase().contains(&query_lower)
+            })
+            .collect()
+    }
+
+    fn export_flat_file(&mut self) {
+        let Some(save) = self.save.as_ref() else {
+            return;
+        };
+
+        let mut dlg = rfd::FileDialog::new().add_filter("Text", &["txt"]);
+        if let Some(dir) = self.dialog_dir.clone().or_else(Self::initial_dialog_dir) {
+            dlg = dlg.set_directory(dir);
+        }
+        if let Some(stem) = save.source_path.as_ref().and_then(|p| p.file_stem()) {
+            dlg = dlg.set_file_name(format!("{}_flat.txt", stem.to_string_lossy()));
+        }
+        let Some(path) = dlg.save_file() else {
+            return;
+        };
+
+        let text = crate::flatten::export_flat(&save.root);
+        if let Err(e) = std::fs::write(&path, text) {
+            self.set_error(format!("Failed to export: {e:#}"));
+        } else {
+            self.set_status(format!("Exported {}", path.display()));
+            self.last_error = None;
+        }
+    }
+
+    /// Render the undo history as an edit log: readable comment lines per action followed by
+    /// the exact record (see `crate::editlog`).
+    fn format_edit_log(actions: &[EditAction]) -> String {
+        let preview =
+            |v: Option<&TiValue>| v.map_or(statics::EN_LOG_ABSENT.to_string(), value_preview);
+        let comment = crate::editlog::LOG_COMMENT;
+        let mut out = format!("{comment} {}\n", statics::EN_LOG_HEADER);
+        for (i, action) in actions.iter().enumerate() {
+            out.push_str(&format!("\n{comment} {}. {}\n", i + 1, action.description));
+            // Whole-object actions (add, delete, copy, key reorder) have no property to replay.
+            if action.prop.is_empty() {
+                out.push_str(&format!(
+                    "{comment} {}: {}\n",
+                    action.object_id,
+                    statics::EN_LOG_NOT_REPLAYABLE
+                ));
+                continue;
+            }
+            let entry = crate::editlog::LogEntry {
+                group: action.group.clone(),
+                id: action.object_id,
+                prop: action.prop.clone(),
+                before: action.before.clone(),
+                after: action.after.clone(),
+            };
+            out.push_str(&format!(
+                "{comment} {}.{}: {} -> {}\n",
+                action.object_id,
+                action.prop,
+                preview(action.before.as_ref()),
+                preview(action.after.as_ref())
+            ));
+            out.push_str(&entry.to_record());
+            out.push('\n');
+        }
+        out
+    }
+
+    fn export_changes_file(&mut self) {
+        let Some(save) = self.save.as_ref() else {
+            return;
+        };
+
+        let mut dlg = rfd::FileDialog::new().add_filter("Text", &["txt"]);
+        if let Some(dir) = self.dialog_dir.clone().or_else(Self::initial_dialog_dir) {
+            dlg = dlg.set_directory(dir);
+        }
+        if let Some(stem) = save.source_path.as_ref().and_then(|p| p.file_stem()) {
+            dlg = dlg.set_file_name(format!("{}_changes.txt", stem.to_string_lossy()));
+        }
+        let Some(path) = dlg.save_file() else {
+            return;
+        };
+
+        let text = Self::format_edit_log(&self.undo_stack);
+        if let Err(e) = std::fs::write(&path, text) {
+            self.set_error(format!("Failed to export: {e:#}"));
+        } else {
+            self.set_status(format!("Exported {}", path.display()));
+            self.last_error = None;
+        }
+    }
+
+    /// Replay an exported edit log against the loaded save as one undoable batch.
+    fn replay_log_file(&mut self) {
+        if self.save.is_none() {
+            return;
+        }
+
+        let mut dlg = rfd::FileDialog::new().add_filter("Text", &["txt"]);
+        if let Some(dir) = self.dialog_dir.clone().or_else(Self::initial_dialog_dir) {
+            dlg = dlg.set_directory(dir);
+        }
+        let Some(path) = dlg.pick_file() else {
+            return;
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                self.set_error(format!("Failed to read {}: {e:#}", path.display()));
+                return;
+            }
+        };
+
+        let Some(mut save) = self.save.take() else {
+            return;
+        };
+        let (entries, mut problems) = crate::editlog::parse_log(&text);
+        let (applied, replay_problems) = crate::editlog::replay(&mut save, &entries);
+        problems.extend(replay_problems);
+
+        let batch = self.next_batch_id;
+        self.next_batch_id += 1;
+        let changed = applied.len();
+        for entry in applied {
+            let description = format!(
+                "{} {}: {}",
+                statics::EN_DESC_OBJECT_ID,
+                entry.id,
+                Self::describe_change(&entry.prop, entry.before.as_ref(), entry.after.as_ref())
+            );
+            self.undo_stack.push(EditAction {
+                group: entry.group,
+                object_id: entry.id,
+                prop: entry.prop,
+                before: entry.before,
+                after: entry.after,
+                description,
+                batch: Some(batch),
+            });
+        }
+
+        if changed > 0 {
+            self.redo_stack.clear();
+            self.validate_pending = true;
+            save.refresh_dirty();
+            self.refresh_selected_property_from_save(&save);
+        }
+        self.save = Some(save);
+
+        self.set_status(format!(
+            "Replayed {} change(s) from {}",
+            changed,
+            path.display()
+        ));
+        self.last_error = None;
+        self.flat_import_report =
+            (!problems.is_empty()).then_some((statics::EN_WINDOW_REPLAY_REPORT, problems));
+    }
+
+    fn import_flat_file(&mut self) {
+        if self.save.is_none() {
+            return;
+        }
+
+        let mut dlg = rfd::FileDialog::new().add_filter("Text", &["txt"]);
+        if let Some(dir) = self.dialog_dir.clone().or_else(Self::initial_dialog_dir) {
+            dlg = dlg.set_directory(dir);
+        }
+        let Some(path) = dlg.pick_file() else {
+            return;
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                self.set_error(format!("Failed to read {}: {e:#}", path.display()));
+                return;
+            }
+        };
+
+        let Some(mut save) = self.save.take() else {
+            return;
+        };
+        let (edits, mut problems) = crate::flatten::parse_flat(&text);
+
+        // Only edits inside object properties can be recorded as undoable actions.
+        let mut accepted = Vec::new();
+        let mut targets: Vec<(String, i64, String)> = Vec::new();
+        for edit in edits {
+            match save.object_prop_for_pointer(&edit.path) {
+                Some(target) => {
+                    if !targets.contains(&target) {
+                        targets.push(target);
+                    }
+                    accepted.push(edit);
+                }
+                None => problems.push(format!(
+                    "line {}: not inside an object property: {}",
+                    edit.line, edit.path
+                )),
+            }
+        }
+
+        let befores: Vec<Option<TiValue>> = targets
+            .iter()
+            .map(|(g, id, p)| {
+                save.get_object_value(g, *id)
+                    .and_then(|o| o.get(p))
+                    .cloned()
+            })
+            .collect();
+        problems.extend(crate::flatten::apply_flat(&mut save.root, &accepted));
+
+        let batch = self.next_batch_id;
+        self.next_batch_id += 1;
+        let mut changed = 0usize;
+        for ((group, object_id, prop), before) in targets.into_iter().zip(befores) {
+            let after = save
+                .get_object_value(&group, object_id)
+                .and_then(|o| o.get(&prop))
+                .cloned();
+            if after == before {
+                continue;
+            }
+            let description = format!(
+                "{} {}: {}",
+                statics::EN_DESC_OBJECT_ID,
+                object_id,
+                Self::describe_change(&prop, before.as_ref(), after.as_ref())
+            );
+            self.undo_stack.push(EditAction {
+                group,
+                object_id,
+                prop,
+                before,
+                after,
+                description,
+                batch: Some(batch),
+            });
+            changed += 1;
+        }
+
+        if changed > 0 {
+            self.redo_stack.clear();
+            self.validate_pending = true;
+            save.rebuild_index();
+            save.refresh_dirty();
+            self.refresh_selected_property_from_save(&save);
+        }
+        self.save = Some(save);
+
+        self.set_status(format!(
+            "Imported {} change(s) from {}",
+            changed,
+            path.display()
+        ));
+        self.last_error = None;
+        self.flat_import_report =
+            (!problems.is_empty()).then_some((statics::EN_WINDOW_IMPORT_REPORT, problems));
+    }
+
+    fn select_object_user(&mut self, group: &str, id: i64) {
+        self.select_object_internal(group, id, true, false, false);
+    }
+
+    fn select_object_programmatic(
+        &mut self,
+        group: &str,
+        id: i64,
+        record_history: bool,
+        center_on_target: bool,
+    ) {
+        self.select_object_internal(group, id, record_history, true, center_on_target);
+    }
+
+    fn select_object_internal(
+        &mut self,
+        group: &str,
+        id: i64,
+        record_history: bool,
+        request_scroll: bool,
+        center_on_target: bool,
+    ) {
+        if record_history
+            && let Some(cur) = self.selected_object_id
+            && cur != id
         {
             if self.history_back.last().copied() != Some(cur) {
                 self.history_back.push(cur);
@@ -1508,9 +3299,15 @@ impl TiseApp {
             self.history_forward.clear();
         }
 
+        if self.selected_object_id != Some(id) {
+            self.flush_notes();
+        }
         self.selected_group = Some(group.to_string());
         self.selected_object_id = Some(id);
-        self.selected_property = None;
+        self.pending_property = self
+            .selected_property
+            .take()
+            .filter(|_| self.settings.sticky_property);
         self.edit_buffer.clear();
         self.raw_edit_mode = false;
 
@@ -1532,12 +3329,12 @@ impl TiseApp {
         let group = self
             .save
             .as_ref()
-            .and_then(|s| s.index.id_lookup.get(&target))
-            .map(|(g, _)| g.clone());
+            .and_then(|s| s.object_group(target))
+            .map(str::to_string);
         if let Some(group) = group {
             self.select_object_programmatic(&group, target, false, false);
         } else {
-            self.last_error = Some(format!("History target ID {target} not found"));
+            self.set_error(format!("History target ID {target} not found"));
         }
     }
 
@@ -1551,12 +3348,12 @@ impl TiseApp {
         let group = self
             .save
             .as_ref()
-            .and_then(|s| s.index.id_lookup.get(&target))
-            .map(|(g, _)| g.clone());
+            .and_then(|s| s.object_group(target))
+            .map(str::to_string);
         if let Some(group) = group {
             self.select_object_programmatic(&group, target, false, false);
         } else {
-            self.last_error = Some(format!("History target ID {target} not found"));
+            self.set_error(format!("History target ID {target} not found"));
         }
     }
 
@@ -1571,14 +3368,18 @@ impl TiseApp {
             return;
         };
 
-        let parsed = match TiValue::parse_json5(self.edit_buffer.trim()) {
+        // Untrimmed, so a syntax error's line and column match the editor.
+        let parsed = match TiValue::parse_json5(&self.edit_buffer) {
             Ok(v) => v,
             Err(e) => {
-                self.last_error = Some(format!("Invalid JSON5 for property: {e:#}"));
+                self.set_error(format!("Invalid JSON5 for property: {e:#}"));
                 return;
             }
         };
 
+        let Some(group) = self.relocate_selected_object(save, &group, object_id) else {
+            return;
+        };
         let before = save
             .get_object_value(&group, object_id)
             .and_then(|o| o.get(&prop))
@@ -1586,7 +3387,7 @@ impl TiseApp {
 
         {
             let Some(value_obj) = save.get_object_value_mut(&group, object_id) else {
-                self.last_error = Some(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
+                self.set_error(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
                 return;
             };
             value_obj.insert(prop.clone(), parsed.clone());
@@ -1605,7 +3406,7 @@ impl TiseApp {
 
         let desc = format!(
             "{} {}: {}",
-            statics::EN_SORT_ID,
+            statics::EN_DESC_OBJECT_ID,
             object_id,
             Self::describe_change(&prop, before.as_ref(), Some(&parsed))
         );
@@ -1616,8 +3417,9 @@ impl TiseApp {
             before,
             after: Some(parsed),
             description: desc.clone(),
+            batch: None,
         });
-        self.status = desc;
+        self.set_status(desc);
         self.last_error = None;
 
         // Keep the edit buffer in a pleasant display format after applying.
@@ -1632,28 +3434,122 @@ impl TiseApp {
         }
     }
 
-    fn set_property_null(&mut self, save: &mut LoadedSave) {
-        let Some(group) = self.selected_group.clone() else {
-            return;
-        };
-        let Some(object_id) = self.selected_object_id else {
+    /// Put the JSON Pointer of the selected property on the clipboard.
+    fn copy_property_path(&mut self, ctx: &egui::Context, save: &LoadedSave, prop: &str) {
+        let (Some(group), Some(object_id)) =
+            (self.selected_group.as_deref(), self.selected_object_id)
+        else {
             return;
         };
-        let Some(prop) = self.selected_property.clone() else {
-            return;
-        };
-
-        let before = save
-            .get_object_value(&group, object_id)
-            .and_then(|o| o.get(&prop))
-            .cloned();
+        match save.pointer_for_property(group, object_id, prop) {
+            Some(path) => {
+                if self.copy_to_clipboard(ctx, path.clone()) {
+                    self.set_status(format!("{} {path}", statics::EN_PREFIX_COPIED));
+                }
+            }
+            None => self.set_error(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string()),
+        }
+    }
+
+    /// Put an object's whole `{Key, Value}` entry on the clipboard, formatted like the save.
+    fn copy_object_entry(
+        &mut self,
+        ctx: &egui::Context,
+        save: &LoadedSave,
+        group: &str,
+        object_id: i64,
+    ) {
+        match save.object_entry(group, object_id) {
+            Some(entry) => {
+                if self.copy_to_clipboard(ctx, entry.to_ti_save_pretty()) {
+                    self.set_status(format!("{} {object_id}", statics::EN_STATUS_COPIED_ENTRY));
+                }
+            }
+            None => self.set_error(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string()),
+        }
+    }
+
+    /// Show `text` in the status bar and add it to the message log.
+    fn set_status(&mut self, text: String) {
+        self.push_log(false, text.clone());
+        self.status = text;
+    }
+
+    /// Show `text` in the error bar and add it to the message log.
+    fn set_error(&mut self, text: String) {
+        self.push_log(true, text.clone());
+        self.last_error = Some(text);
+    }
+
+    fn push_log(&mut self, error: bool, text: String) {
+        if self.message_log.len() == MESSAGE_LOG_LIMIT {
+            self.message_log.pop_front();
+        }
+        self.message_log.push_back(LoggedMessage { error, text });
+    }
+
+    /// Every copy goes through here. egui hands the text to the platform without reporting
+    /// failures, so the system clipboard is opened first: if that fails (e.g. on some Wayland
+    /// setups) the copy is still attempted, but the error says why it likely didn't work.
+    /// Returns whether the clipboard was reachable.
+    fn copy_to_clipboard(&mut self, ctx: &egui::Context, text: String) -> bool {
+        let reachable = arboard::Clipboard::new();
+        ctx.copy_text(text);
+        if let Err(e) = reachable {
+            self.set_error(format!("{}: {e}", statics::EN_ERR_CLIPBOARD));
+            return false;
+        }
+        true
+    }
+
+    /// The selected object's group, following the object if it has moved to another group
+    /// since it was selected. Reports an error if it can't be found at all.
+    fn relocate_selected_object(
+        &mut self,
+        save: &mut LoadedSave,
+        group: &str,
+        object_id: i64,
+    ) -> Option<String> {
+        let Some(found) = save.relocate_object(group, object_id) else {
+            self.set_error(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
+            return None;
+        };
+        if found != group {
+            self.selected_group = Some(found.clone());
+        }
+        Some(found)
+    }
+
+    fn set_property_null(&mut self, save: &mut LoadedSave) {
+        self.set_property_to(save, TiValue::Null);
+    }
+
+    /// Replace the selected property with `value` as one undoable edit.
+    fn set_property_to(&mut self, save: &mut LoadedSave, value: TiValue) {
+        let Some(group) = self.selected_group.clone() else {
+            return;
+        };
+        let Some(object_id) = self.selected_object_id else {
+            return;
+        };
+        let Some(prop) = self.selected_property.clone() else {
+            return;
+        };
+
+        let Some(group) = self.relocate_selected_object(save, &group, object_id) else {
+            return;
+        };
+        let before = save
+            .get_object_value(&group, object_id)
+            .and_then(|o| o.get(&prop))
+            .cloned();
 
         {
             let Some(value_obj) = save.get_object_value_mut(&group, object_id) else {
-                self.last_error = Some(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
+                self.set_error(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
                 return;
             };
-            value_obj.insert(prop.clone(), TiValue::Null);
+            value_obj.insert(prop.clone(), value.clone());
         }
 
         save.rebuild_index();
@@ -1661,21 +3557,22 @@ impl TiseApp {
 
         let desc = format!(
             "{} {}: {}",
-            statics::EN_SORT_ID,
+            statics::EN_DESC_OBJECT_ID,
             object_id,
-            Self::describe_change(&prop, before.as_ref(), Some(&TiValue::Null))
+            Self::describe_change(&prop, before.as_ref(), Some(&value))
         );
+        self.edit_buffer = Self::value_for_editing(&value);
         self.record_action(EditAction {
             group: group.clone(),
             object_id,
             prop: prop.clone(),
             before,
-            after: Some(TiValue::Null),
+            after: Some(value),
             description: desc.clone(),
+            batch: None,
         });
-        self.status = desc;
+        self.set_status(desc);
         self.last_error = None;
-        self.edit_buffer = statics::EN_LITERAL_NULL.to_string();
 
         if prop == statics::TI_PROP_PUBLIC_OPINION
             && let Some(obj) = save.get_object_value(&group, object_id)
@@ -1725,14 +3622,315 @@ impl TiseApp {
         self.public_opinion_remainder = Some(1.0 - sum);
     }
 
+    fn councilor_form_source(obj: &indexmap::IndexMap<String, TiValue>) -> Vec<Option<TiValue>> {
+        COUNCILOR_FORM_PROPS
+            .iter()
+            .map(|p| obj.get(*p).cloned())
+            .collect()
+    }
+
+    fn councilor_form(object_id: i64, obj: &indexmap::IndexMap<String, TiValue>) -> CouncilorForm {
+        let text = |prop: &str| {
+            obj.get(prop)
+                .and_then(TiValue::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let attributes = obj
+            .get(statics::TI_PROP_ATTRIBUTES)
+            .and_then(TiValue::as_object)
+            .map(|attrs| {
+                attrs
+                    .iter()
+                    .filter_map(|(k, v)| match v {
+                        TiValue::Number(n) => n.as_i64().map(|n| (k.clone(), n)),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let traits = obj
+            .get(statics::TI_PROP_TRAIT_TEMPLATE_NAMES)
+            .and_then(TiValue::as_array)
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(TiValue::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        CouncilorForm {
+            object_id,
+            source: Self::councilor_form_source(obj),
+            personal_name: text(statics::TI_PROP_PERSONAL_NAME),
+            family_name: text(statics::TI_PROP_FAMILY_NAME),
+            display_name: text(statics::TI_PROP_DISPLAY_NAME),
+            attributes,
+            traits,
+            new_trait: String::new(),
+        }
+    }
+
+    /// New values for the properties the form changes. Attributes keep their key order and any
+    /// entries the form doesn't show; a missing property is only created if the form has content.
+    fn councilor_form_changes(
+        form: &CouncilorForm,
+        obj: &indexmap::IndexMap<String, TiValue>,
+    ) -> Vec<(&'static str, TiValue)> {
+        let mut changes = Vec::new();
+        let mut consider = |prop: &'static str, value: TiValue, empty: bool| match obj.get(prop) {
+            Some(current) if *current == value => {}
+            None if empty => {}
+            _ => changes.push((prop, value)),
+        };
+
+        for (prop, text) in [
+            (statics::TI_PROP_PERSONAL_NAME, &form.personal_name),
+            (statics::TI_PROP_FAMILY_NAME, &form.family_name),
+            (statics::TI_PROP_DISPLAY_NAME, &form.display_name),
+        ] {
+            consider(prop, TiValue::String(text.clone()), text.is_empty());
+        }
+
+        if let Some(attrs) = obj
+            .get(statics::TI_PROP_ATTRIBUTES)
+            .and_then(TiValue::as_object)
+        {
+            let mut updated = attrs.clone();
+            for (k, v) in &form.attributes {
+                let unchanged =
+                    matches!(updated.get(k), Some(TiValue::Number(n)) if n.as_i64() == Some(*v));
+                if !unchanged {
+                    updated.insert(k.clone(), TiValue::Number(crate::value::TiNumber::I64(*v)));
+                }
+            }
+            consider(statics::TI_PROP_ATTRIBUTES, TiValue::Object(updated), false);
+        }
+
+        let traits = form
+            .traits
+            .iter()
+            .map(|t| TiValue::String(t.clone()))
+            .collect();
+        consider(
+            statics::TI_PROP_TRAIT_TEMPLATE_NAMES,
+            TiValue::Array(traits),
+            form.traits.is_empty(),
+        );
+        changes
+    }
+
+    /// Write the councilor form back to the save as one undoable step.
+    fn apply_councilor_form(&mut self, save: &mut LoadedSave, form: &CouncilorForm) {
+        let Some(group) = self.selected_group.clone() else {
+            return;
+        };
+        let object_id = form.object_id;
+        let Some(obj) = save.get_object_value(&group, object_id) else {
+            self.set_error(statics::EN_ERR_LOCATE_SELECTED_OBJECT.to_string());
+            return;
+        };
+        let changes = Self::councilor_form_changes(form, obj);
+        if changes.is_empty() {
+            return;
+        }
+
+        let batch = self.next_batch_id;
+        self.next_batch_id += 1;
+        let count = changes.len();
+        for (prop, after) in changes {
+            let Ok(before) = save.set_property(&group, object_id, prop, after.clone()) else {
+                continue;
+            };
+            let description = format!(
+                "{} {}: {}",
+                statics::EN_DESC_OBJECT_ID,
+                object_id,
+                Self::describe_change(prop, before.as_ref(), Some(&after))
+            );
+            self.record_action(EditAction {
+                group: group.clone(),
+                object_id,
+                prop: prop.to_string(),
+                before,
+                after: Some(after),
+                description,
+                batch: Some(batch),
+            });
+        }
+
+        save.rebuild_index();
+        save.refresh_dirty();
+        self.refresh_selected_property_from_save(save);
+        self.set_status(format!(
+            "{} {}: {} ({count} changes)",
+            statics::EN_DESC_OBJECT_ID,
+            object_id,
+            statics::EN_COUNCILOR_EDITOR
+        ));
+        self.last_error = None;
+    }
+
+    fn render_councilor_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        object_id: i64,
+        value_obj: &indexmap::IndexMap<String, TiValue>,
+        save: &mut LoadedSave,
+    ) {
+        let source = Self::councilor_form_source(value_obj);
+        let mut form = match self.councilor_form.take() {
+            Some(form) if form.object_id == object_id && form.source == source => form,
+            _ => Self::councilor_form(object_id, value_obj),
+        };
+        let mut apply = false;
+        let mut reset = false;
+        let mut go_to_location = None;
+        let mut edit_location = false;
+
+        egui::CollapsingHeader::new(statics::EN_COUNCILOR_EDITOR)
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("councilor_names")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(statics::EN_LABEL_PERSONAL_NAME);
+                        ui.text_edit_singleline(&mut form.personal_name);
+                        ui.end_row();
+
+                        ui.label(statics::EN_LABEL_FAMILY_NAME);
+                        ui.text_edit_singleline(&mut form.family_name);
+                        ui.end_row();
+
+                        ui.label(statics::EN_LABEL_DISPLAY_NAME);
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut form.display_name);
+                            if ui
+                                .small_button(statics::EN_BTN_COMPOSE_DISPLAY_NAME)
+                                .clicked()
+                            {
+                                form.display_name =
+                                    format!("{} {}", form.personal_name, form.family_name)
+                                        .trim()
+                                        .to_string();
+                            }
+                        });
+                        ui.end_row();
+
+                        if let Some(target_id) = value_obj
+                            .get(statics::TI_PROP_LOCATION)
+                            .and_then(TiValue::is_relational_ref)
+                        {
+                            ui.label(statics::EN_LABEL_LOCATION);
+                            ui.horizontal(|ui| {
+                                if ui.small_button(statics::EN_BTN_GO).clicked() {
+                                    go_to_location = Some(target_id);
+                                }
+                                if ui.small_button(statics::EN_BTN_EDIT).clicked() {
+                                    edit_location = true;
+                                }
+                                match save.index.id_to_display_name.get(&target_id) {
+                                    Some(name) => ui.label(format!("{target_id}: {name}")),
+                                    None => ui.label(target_id.to_string()),
+                                };
+                            });
+                            ui.end_row();
+                        }
+                    });
+
+                if !form.attributes.is_empty() {
+                    ui.label(statics::EN_LABEL_ATTRIBUTES);
+                    ui.horizontal_wrapped(|ui| {
+                        for (name, value) in form.attributes.iter_mut() {
+                            ui.label(name.as_str());
+                            ui.add(egui::DragValue::new(value));
+                            ui.add_space(8.0);
+                        }
+                    });
+                }
+
+                ui.label(statics::EN_LABEL_TRAITS);
+                let mut remove_trait = None;
+                ui.horizontal_wrapped(|ui| {
+                    for (i, name) in form.traits.iter().enumerate() {
+                        ui.label(name.as_str());
+                        if ui.small_button(statics::EN_BTN_REMOVE).clicked() {
+                            remove_trait = Some(i);
+                        }
+                        ui.add_space(8.0);
+                    }
+                });
+                if let Some(i) = remove_trait {
+                    form.traits.remove(i);
+                }
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut form.new_trait)
+                            .hint_text(statics::EN_HINT_NEW_TRAIT),
+                    );
+                    let name = form.new_trait.trim().to_string();
+                    if ui
+                        .add_enabled(
+                            !name.is_empty(),
+                            egui::Button::new(statics::EN_BTN_ADD_ITEM),
+                        )
+                        .clicked()
+                    {
+                        form.traits.push(name);
+                        form.new_trait.clear();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    let changed = !Self::councilor_form_changes(&form, value_obj).is_empty();
+                    if ui
+                        .add_enabled(changed, egui::Button::new(statics::EN_BTN_APPLY_COUNCILOR))
+                        .clicked()
+                    {
+                        apply = true;
+                    }
+                    if ui
+                        .add_enabled(changed, egui::Button::new(statics::EN_BTN_RESET))
+                        .clicked()
+                    {
+                        reset = true;
+                    }
+                });
+            });
+
+        if apply {
+            self.apply_councilor_form(save, &form);
+        }
+        if !reset && !apply {
+            self.councilor_form = Some(form);
+        }
+
+        if edit_location {
+            self.selected_property = Some(statics::TI_PROP_LOCATION.to_string());
+            self.pending_property = None;
+            self.scroll_properties_to_selected = true;
+            self.refresh_selected_property_from_save(save);
+        }
+        if let Some(target_id) = go_to_location {
+            if let Some((ref_group, _)) = save.index.id_lookup.get(&target_id).cloned() {
+                self.select_object_programmatic(&ref_group, target_id, true, true);
+            } else {
+                self.set_error(format!("Reference ID {target_id} not found"));
+            }
+        }
+    }
+
     fn render_properties_panel(
         &mut self,
         ui: &mut egui::Ui,
         properties: &[(&String, &TiValue)],
         value_obj: &indexmap::IndexMap<String, TiValue>,
-        id_lookup: &std::collections::HashMap<i64, (String, usize)>,
-        id_to_display_name: &std::collections::HashMap<i64, String>,
+        save: &LoadedSave,
     ) {
+        let id_lookup = &save.index.id_lookup;
+        let id_to_display_name = &save.index.id_to_display_name;
         ui.heading(statics::EN_HEADING_PROPERTIES);
         ui.separator();
 
@@ -1745,13 +3943,18 @@ impl TiseApp {
                 .max_height(scroll_h)
                 .show(ui, |ui| {
                     let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
+                    let show_round_trip = self.show_round_trip_column;
 
-                    TableBuilder::new(ui)
+                    let mut table = TableBuilder::new(ui)
                         .striped(true)
                         .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
                         .column(Column::initial(240.0).resizable(true))
                         .column(Column::remainder().resizable(true))
-                        .column(Column::initial(80.0).resizable(false))
+                        .column(Column::initial(80.0).resizable(false));
+                    if show_round_trip {
+                        table = table.column(Column::initial(90.0).resizable(false));
+                    }
+                    table
                         .header(row_h, |mut header| {
                             header.col(|ui| {
                                 ui.strong(statics::EN_COL_PROPERTY);
@@ -1762,6 +3965,11 @@ impl TiseApp {
                             header.col(|ui| {
                                 ui.strong(statics::EN_COL_TYPE);
                             });
+                            if show_round_trip {
+                                header.col(|ui| {
+                                    ui.strong(statics::EN_COL_ROUND_TRIP);
+                                });
+                            }
                         })
                         .body(|mut body| {
                             for (key, val) in properties.iter() {
@@ -1783,6 +3991,7 @@ impl TiseApp {
                                         }
                                         if resp.clicked() {
                                             self.selected_property = Some((*key).to_string());
+                                            self.pending_property = None;
                                             self.last_error = None;
 
                                             self.raw_edit_mode =
@@ -1819,19 +4028,28 @@ impl TiseApp {
                                                             ref_group, target_id, true, true,
                                                         );
                                                     } else {
-                                                        self.last_error = Some(format!(
+                                                        self.set_error(format!(
                                                             "Reference ID {target_id} not found"
                                                         ));
                                                     }
                                                 }
-                                                if name.is_empty() {
-                                                    ui.label(format!("{target_id}"));
+                                                let label = if name.is_empty() {
+                                                    ui.label(format!("{target_id}"))
                                                 } else {
-                                                    ui.label(format!("{target_id}: {name}"));
-                                                }
+                                                    ui.label(format!("{target_id}: {name}"))
+                                                };
+                                                label.on_hover_ui(|ui| {
+                                                    ref_peek_ui(ui, save, target_id);
+                                                });
                                             });
                                         } else if let Some(ids) = array_of_relational_refs(val) {
                                             ui.label(format!("{} refs", ids.len()));
+                                        } else if let Some(date) = TiDate::from_value(val) {
+                                            ui.label(date.format());
+                                        } else if self.show_full_float_precision
+                                            && let Some(full) = float_full_expansion(val)
+                                        {
+                                            ui.label(&full).on_hover_text(&full);
                                         } else {
                                             ui.label(value_preview(val));
                                         }
@@ -1840,6 +4058,28 @@ impl TiseApp {
                                     row.col(|ui| {
                                         ui.monospace(val.type_name());
                                     });
+
+                                    if show_round_trip {
+                                        row.col(|ui| {
+                                            if !val.round_trips() {
+                                                ui.colored_label(
+                                                    egui::Color32::YELLOW,
+                                                    statics::EN_ROUND_TRIP_UNSTABLE,
+                                                )
+                                                .on_hover_text(
+                                                    statics::EN_HINT_ROUND_TRIP_UNSTABLE,
+                                                );
+                                            } else if val.has_respelled_float(&save.float_literals)
+                                            {
+                                                ui.label(statics::EN_ROUND_TRIP_RESPELLED)
+                                                    .on_hover_text(
+                                                        statics::EN_HINT_ROUND_TRIP_RESPELLED,
+                                                    );
+                                            } else {
+                                                ui.label(statics::EN_ROUND_TRIP_OK);
+                                            }
+                                        });
+                                    }
                                 });
                             }
                         });
@@ -1892,11 +4132,35 @@ impl TiseApp {
                                     self.set_property_null(save);
                                 }
 
+                                if let Some(default) = self
+                                    .selected_group
+                                    .as_deref()
+                                    .and_then(|group| Self::known_default(group, prop))
+                                    && ui
+                                        .add_enabled(
+                                            current_val.map(TiValue::to_json5_compact)
+                                                != Some(default.to_json5_compact()),
+                                            egui::Button::new(statics::EN_BTN_RESET_DEFAULT),
+                                        )
+                                        .on_hover_text(format!(
+                                            "{} {}",
+                                            statics::EN_HINT_RESET_DEFAULT,
+                                            default.to_json5_compact()
+                                        ))
+                                        .clicked()
+                                {
+                                    self.set_property_to(save, default);
+                                }
+
                                 if ui.button(statics::EN_BTN_CHANGE_TYPE).clicked() {
                                     self.change_type_open = true;
                                     self.change_type_preview = None;
                                 }
 
+                                if ui.button(statics::EN_BTN_COPY_PATH).clicked() {
+                                    self.copy_property_path(ui.ctx(), save, prop);
+                                }
+
                                 if let Some(val) = current_val
                                     && let Some(target_id) = val.is_relational_ref()
                                     && ui.button(statics::EN_BTN_GO_TO_REF).clicked()
@@ -1908,8 +4172,9 @@ impl TiseApp {
                                             ref_group, target_id, true, true,
                                         );
                                     } else {
-                                        self.last_error =
-                                            Some(format!("Reference ID {target_id} not found"));
+                                        self.set_error(format!(
+                                            "Reference ID {target_id} not found"
+                                        ));
                                     }
                                 }
                             });
@@ -1952,11 +4217,27 @@ impl TiseApp {
                                         .show(ui, |ui| {
                                             for (i, label) in type_labels.iter().enumerate() {
                                                 if ui.button(*label).clicked() {
-                                                    self.change_type_preview =
-                                                        Some(Self::coerce_value_to_type(
+                                                    let converted = Self::coerce_value_to_type(
+                                                        label,
+                                                        &source_value,
+                                                    );
+                                                    self.change_type_loss =
+                                                        Self::change_type_data_loss(
                                                             label,
                                                             &source_value,
-                                                        ));
+                                                            &converted,
+                                                        );
+                                                    self.change_type_warning =
+                                                        if self.change_type_loss.is_none() {
+                                                            Self::number_subtype_warning(
+                                                                &source_value,
+                                                                &converted,
+                                                            )
+                                                        } else {
+                                                            None
+                                                        };
+                                                    self.change_type_loss_confirmed = false;
+                                                    self.change_type_preview = Some(converted);
                                                     self.last_error = None;
                                                 }
 
@@ -1983,9 +4264,40 @@ impl TiseApp {
                                             .desired_rows(6),
                                     );
 
+                                    if let Some(preview) = &self.change_type_preview
+                                        && let Some((before, after)) =
+                                            Self::number_subtype_change(&source_value, preview)
+                                    {
+                                        ui.label(format!(
+                                            "{} {before} -> {after} ({} -> {})",
+                                            statics::EN_LABEL_NUMBER_SUBTYPE,
+                                            source_value.to_ti_save_pretty(),
+                                            preview.to_ti_save_pretty()
+                                        ));
+                                    }
+
+                                    if let Some(loss) = &self.change_type_loss {
+                                        ui.separator();
+                                        ui.colored_label(egui::Color32::ORANGE, loss);
+                                        ui.checkbox(
+                                            &mut self.change_type_loss_confirmed,
+                                            statics::EN_CHECK_CONFIRM_DATA_LOSS,
+                                        );
+                                    } else if let Some(warning) = &self.change_type_warning {
+                                        ui.separator();
+                                        ui.colored_label(egui::Color32::ORANGE, warning);
+                                        ui.checkbox(
+                                            &mut self.change_type_loss_confirmed,
+                                            statics::EN_CHECK_CONFIRM_SUBTYPE,
+                                        );
+                                    }
+
                                     ui.separator();
                                     ui.horizontal(|ui| {
-                                        let can_apply = self.change_type_preview.is_some();
+                                        let can_apply = self.change_type_preview.is_some()
+                                            && (self.change_type_loss.is_none()
+                                                && self.change_type_warning.is_none()
+                                                || self.change_type_loss_confirmed);
                                         if ui
                                             .add_enabled(
                                                 can_apply,
@@ -2015,6 +4327,9 @@ impl TiseApp {
                             self.change_type_open = open;
                             if !self.change_type_open {
                                 self.change_type_preview = None;
+                                self.change_type_loss = None;
+                                self.change_type_warning = None;
+                                self.change_type_loss_confirmed = false;
                             }
                         }
 
@@ -2022,6 +4337,10 @@ impl TiseApp {
                             self.raw_edit_mode = true;
                         }
 
+                        // Enter in a single-line scalar editor applies, once the editors below
+                        // have written their value into `edit_buffer`.
+                        let mut enter_applies = false;
+
                         if is_rel_ref {
                             let fallback_id =
                                 current_val.and_then(|v| v.is_relational_ref()).unwrap_or(0);
@@ -2045,24 +4364,30 @@ impl TiseApp {
                                 }
                             }
 
+                            // Smart paste: a bare integer pasted while editing a reference becomes
+                            // `{ value: N }` (keeping `$type`) instead of replacing the ref.
+                            let raw_edit_id = egui::Id::new("ref_raw_edit");
+                            let focus = ui.ctx().memory(|m| m.focused());
+                            if (focus.is_none() || focus == Some(raw_edit_id))
+                                && let Some(id) = ui.input_mut(Self::take_pasted_integer)
+                            {
+                                target_id = id;
+                                self.edit_buffer =
+                                    Self::make_reference(type_hint.clone(), id).to_json5_compact();
+                                self.set_status(format!("Pasted ID {id} as a reference"));
+                                self.last_error = None;
+                            }
+
                             ui.group(|ui| {
                                 ui.horizontal(|ui| {
                                     ui.label(statics::EN_LABEL_REFERENCE_ID);
                                     let resp =
                                         ui.add(egui::DragValue::new(&mut target_id).speed(1.0));
+                                    enter_applies |= Self::enter_pressed(ui, &resp);
                                     if resp.changed() {
-                                        let mut map = indexmap::IndexMap::new();
-                                        if let Some(t) = type_hint.clone() {
-                                            map.insert(
-                                                statics::TI_REF_FIELD_TYPE.to_string(),
-                                                TiValue::String(t),
-                                            );
-                                        }
-                                        map.insert(
-                                            statics::TI_REF_FIELD_VALUE.to_string(),
-                                            TiValue::Number(crate::value::TiNumber::I64(target_id)),
-                                        );
-                                        self.edit_buffer = TiValue::Object(map).to_json5_compact();
+                                        self.edit_buffer =
+                                            Self::make_reference(type_hint.clone(), target_id)
+                                                .to_json5_compact();
                                         self.last_error = None;
                                     }
 
@@ -2074,8 +4399,9 @@ impl TiseApp {
                                                 ref_group, target_id, true, true,
                                             );
                                         } else {
-                                            self.last_error =
-                                                Some(format!("Reference ID {target_id} not found"));
+                                            self.set_error(format!(
+                                                "Reference ID {target_id} not found"
+                                            ));
                                         }
                                     }
                                 });
@@ -2085,11 +4411,13 @@ impl TiseApp {
                                     statics::EN_CHECKBOX_RAW_JSON5,
                                 );
                                 if self.raw_edit_mode {
-                                    ui.add(
+                                    let resp = ui.add(
                                         egui::TextEdit::singleline(&mut self.edit_buffer)
+                                            .id(raw_edit_id)
                                             .font(egui::TextStyle::Monospace)
                                             .desired_width(ui.available_width()),
                                     );
+                                    enter_applies |= Self::enter_pressed(ui, &resp);
                                 } else {
                                     let mut preview = self.edit_buffer.clone();
                                     ui.add_enabled(
@@ -2133,6 +4461,40 @@ impl TiseApp {
                                         _ => None,
                                     };
 
+                                    // Text that isn't a number of this kind is kept for the user to
+                                    // fix; the drag value is locked so it can't overwrite it.
+                                    let buffer_ok = matches!(
+                                        (n, &staged),
+                                        (TiNumber::F64(..), Some(_))
+                                            | (
+                                                TiNumber::I64(_) | TiNumber::U64(_),
+                                                Some(TiNumber::I64(_) | TiNumber::U64(_))
+                                            )
+                                    );
+                                    if !buffer_ok {
+                                        let expected = match n {
+                                            TiNumber::F64(..) => statics::EN_KIND_NUMBER,
+                                            _ => statics::EN_KIND_INTEGER,
+                                        };
+                                        ui.colored_label(
+                                            egui::Color32::YELLOW,
+                                            format!(
+                                                "{} {expected}",
+                                                statics::EN_WARN_NUMBER_BUFFER
+                                            ),
+                                        );
+                                        ui.horizontal(|ui| {
+                                            let resp = ui.add(
+                                                egui::TextEdit::singleline(&mut self.edit_buffer)
+                                                    .font(egui::TextStyle::Monospace),
+                                            );
+                                            enter_applies |= Self::enter_pressed(ui, &resp);
+                                            if ui.small_button(statics::EN_BTN_REVERT).clicked() {
+                                                self.edit_buffer = current_val.to_json5_compact();
+                                            }
+                                        });
+                                    }
+
                                     match n {
                                         TiNumber::I64(orig) => {
                                             let mut v = match &staged {
@@ -2140,11 +4502,13 @@ impl TiseApp {
                                                 _ => *orig,
                                             };
                                             let resp = ui.add_enabled(
-                                                !self.raw_edit_mode,
+                                                !self.raw_edit_mode && buffer_ok,
                                                 egui::DragValue::new(&mut v)
                                                     .speed(1)
                                                     .prefix(statics::EN_PREFIX_VALUE),
                                             );
+                                            enter_applies |= Self::enter_pressed(ui, &resp);
+                                            Self::unit_hint_label(ui, prop);
                                             if resp.changed() {
                                                 self.edit_buffer =
                                                     TiValue::Number(TiNumber::I64(v))
@@ -2163,11 +4527,13 @@ impl TiseApp {
                                                 _ => *orig,
                                             };
                                             let resp = ui.add_enabled(
-                                                !self.raw_edit_mode,
+                                                !self.raw_edit_mode && buffer_ok,
                                                 egui::DragValue::new(&mut v)
                                                     .speed(1)
                                                     .prefix(statics::EN_PREFIX_VALUE),
                                             );
+                                            enter_applies |= Self::enter_pressed(ui, &resp);
+                                            Self::unit_hint_label(ui, prop);
                                             if resp.changed() {
                                                 self.edit_buffer =
                                                     TiValue::Number(TiNumber::U64(v))
@@ -2183,15 +4549,56 @@ impl TiseApp {
                                         TiNumber::F64(orig) => {
                                             let mut v = match &staged {
                                                 Some(TiNumber::F64(x)) => *x,
+                                                Some(TiNumber::I64(x)) => *x as f64,
+                                                Some(TiNumber::U64(x)) => *x as f64,
                                                 _ => *orig,
                                             };
-                                            let resp = ui.add_enabled(
-                                                !self.raw_edit_mode,
-                                                egui::DragValue::new(&mut v)
-                                                    .speed(0.1)
-                                                    .range(f64::NEG_INFINITY..=f64::INFINITY)
+                                            let mut as_percent = self
+                                                .percent_edit
+                                                .get(prop)
+                                                .copied()
+                                                .unwrap_or_else(|| {
+                                                    Self::percent_edit_default(prop)
+                                                });
+                                            if ui
+                                                .checkbox(
+                                                    &mut as_percent,
+                                                    statics::EN_LABEL_EDIT_AS_PERCENT,
+                                                )
+                                                .on_hover_text(statics::EN_HINT_EDIT_AS_PERCENT)
+                                                .changed()
+                                            {
+                                                self.percent_edit
+                                                    .insert(prop.to_string(), as_percent);
+                                            }
+                                            let resp = if as_percent {
+                                                let mut percent = v * 100.0;
+                                                let resp = ui.add_enabled(
+                                                    !self.raw_edit_mode && buffer_ok,
+                                                    Self::percent_drag_value(
+                                                        &mut percent,
+                                                        prop,
+                                                        self.settings.float_decimals,
+                                                    )
                                                     .prefix(statics::EN_PREFIX_VALUE),
-                                            );
+                                                );
+                                                if resp.changed() {
+                                                    v = percent / 100.0;
+                                                }
+                                                resp
+                                            } else {
+                                                ui.add_enabled(
+                                                    !self.raw_edit_mode && buffer_ok,
+                                                    Self::unit_drag_value(
+                                                        &mut v,
+                                                        prop,
+                                                        self.settings.float_decimals,
+                                                    )
+                                                    .prefix(statics::EN_PREFIX_VALUE),
+                                                )
+                                            };
+                                            enter_applies |= Self::enter_pressed(ui, &resp);
+                                            Self::unit_hint_label(ui, prop);
                                             if resp.changed() {
                                                 self.edit_buffer =
                                                     TiValue::Number(TiNumber::F64(v))
@@ -2215,24 +4622,52 @@ impl TiseApp {
                                         _ => s.clone(),
                                     };
                                     let mut v = staged;
+                                    // Edited unescaped; the buffer always holds the escaped literal.
+                                    let auto_multiline = Self::string_wants_multiline(&v);
+                                    ui.add_enabled(
+                                        !auto_multiline,
+                                        egui::Checkbox::new(
+                                            &mut self.string_editor_multiline,
+                                            statics::EN_LABEL_MULTILINE,
+                                        ),
+                                    );
+                                    let multiline = auto_multiline || self.string_editor_multiline;
+                                    let editor = if multiline {
+                                        // Lock focus so Tab types a tab character.
+                                        egui::TextEdit::multiline(&mut v)
+                                            .desired_rows(6)
+                                            .desired_width(ui.available_width())
+                                            .lock_focus(true)
+                                    } else {
+                                        egui::TextEdit::singleline(&mut v)
+                                    };
                                     let resp = ui.add_enabled(
                                         !self.raw_edit_mode,
-                                        egui::TextEdit::singleline(&mut v)
-                                            .hint_text(statics::EN_HINT_VALUE),
+                                        editor.hint_text(statics::EN_HINT_VALUE),
                                     );
                                     if resp.changed() {
-                                        self.edit_buffer = format!(
-                                            "\"{}\"",
-                                            v.replace('\\', "\\\\").replace('"', "\\\"")
-                                        );
+                                        self.edit_buffer = TiValue::escape_string(&v);
                                     }
-                                    let mut preview = v;
-                                    ui.add_enabled(false, egui::TextEdit::singleline(&mut preview));
+                                    // Enter is a newline in the multiline editor.
+                                    enter_applies |= !multiline && Self::enter_pressed(ui, &resp);
+                                    ui.horizontal(|ui| {
+                                        ui.label(statics::EN_LABEL_STORED_AS);
+                                        let mut preview = TiValue::escape_string(&v);
+                                        ui.add_enabled(
+                                            false,
+                                            egui::TextEdit::singleline(&mut preview)
+                                                .font(egui::TextStyle::Monospace)
+                                                .desired_width(ui.available_width()),
+                                        );
+                                    });
                                     ui.separator();
                                 }
                                 _ => {}
                             }
                         }
+                        if enter_applies {
+                            self.apply_property_edit(save);
+                        }
 
                         if prop == statics::TI_PROP_PUBLIC_OPINION
                             && !self.public_opinion_inputs.is_empty()
@@ -2419,29 +4854,27 @@ impl TiseApp {
                             ui.separator();
                         }
 
+                        if let Ok(staged) = TiValue::parse_json5(self.edit_buffer.trim())
+                            && let Some(date) = TiDate::from_value(&staged)
+                        {
+                            self.render_date_editor(ui, prop, date);
+                            ui.separator();
+                        }
+
                         if is_structured && prop != statics::TI_PROP_PUBLIC_OPINION {
                             // Attempt to show structured values in a more readable way.
                             if let Ok(mut staged) = TiValue::parse_json5(self.edit_buffer.trim()) {
+                                let is_ref_list = array_of_relational_refs(&staged).is_some();
                                 if let Some(ids) = array_of_relational_refs(&staged) {
                                     ui.group(|ui| {
                                         ui.label(format!("References ({})", ids.len()));
-                                        self.render_ref_list_table(
-                                            ui,
-                                            &ids,
-                                            &save.index.id_lookup,
-                                            &save.index.id_to_display_name,
-                                        );
+                                        self.render_ref_list_table(ui, &ids, save);
                                     });
                                     ui.separator();
                                 } else if let Some(rows) = array_of_key_value_refs(&staged) {
                                     ui.group(|ui| {
                                         ui.label(format!("Entries ({})", rows.len()));
-                                        self.render_key_value_ref_table(
-                                            ui,
-                                            &rows,
-                                            &save.index.id_lookup,
-                                            &save.index.id_to_display_name,
-                                        );
+                                        self.render_key_value_ref_table(ui, &rows, save);
                                     });
                                     ui.separator();
                                 }
@@ -2451,8 +4884,17 @@ impl TiseApp {
                                 {
                                     let mut changed = false;
                                     ui.group(|ui| {
-                                        ui.label(statics::EN_SIMPLE_LIST_EDITOR);
-                                        changed = Self::render_simple_list_editor(ui, arr);
+                                        ui.label(format!(
+                                            "{} [{}]",
+                                            statics::EN_SIMPLE_LIST_EDITOR,
+                                            arr.len()
+                                        ));
+                                        changed = Self::render_simple_list_editor(
+                                            ui,
+                                            arr,
+                                            &mut self.list_insert_type,
+                                            self.settings.float_decimals,
+                                        );
                                     });
                                     if changed {
                                         self.edit_buffer = staged.to_ti_save_pretty();
@@ -2461,13 +4903,23 @@ impl TiseApp {
                                     ui.separator();
                                 }
 
-                                if let TiValue::Object(map) = &mut staged
-                                    && Self::is_simple_object(map)
+                                if let TiValue::Array(arr) = &mut staged
+                                    && Self::is_object_list(arr)
+                                    && !is_ref_list
                                 {
                                     let mut changed = false;
                                     ui.group(|ui| {
-                                        ui.label(statics::EN_SIMPLE_OBJECT_EDITOR);
-                                        changed = Self::render_simple_object_editor(ui, map);
+                                        ui.label(format!(
+                                            "{} [{}]",
+                                            statics::EN_OBJECT_LIST_EDITOR,
+                                            arr.len()
+                                        ));
+                                        changed = self.render_object_list_editor(
+                                            ui,
+                                            prop,
+                                            arr,
+                                            &save.index.id_to_display_name,
+                                        );
                                     });
                                     if changed {
                                         self.edit_buffer = staged.to_ti_save_pretty();
@@ -2477,12 +4929,51 @@ impl TiseApp {
                                 }
 
                                 if let TiValue::Object(map) = &mut staged
-                                    && !Self::is_simple_object(map)
+                                    && Self::is_simple_object(map)
                                 {
                                     let mut changed = false;
                                     ui.group(|ui| {
-                                        ui.label(statics::EN_MIXED_OBJECT_EDITOR);
-                                        changed = self.render_mixed_object_editor(ui, prop, map);
+                                        ui.label(format!(
+                                            "{} {{{}}}",
+                                            statics::EN_SIMPLE_OBJECT_EDITOR,
+                                            map.len()
+                                        ));
+                                        changed = Self::render_simple_object_editor(
+                                            ui,
+                                            map,
+                                            self.settings.float_decimals,
+                                        );
+                                        if let Some(sum) = Self::numeric_sum(map) {
+                                            ui.label(format!(
+                                                "{} {}",
+                                                statics::EN_LABEL_SUM,
+                                                Self::format_public_opinion_value(sum)
+                                            ));
+                                        }
+                                    });
+                                    if changed {
+                                        self.edit_buffer = staged.to_ti_save_pretty();
+                                        self.last_error = None;
+                                    }
+                                    ui.separator();
+                                }
+
+                                if let TiValue::Object(map) = &mut staged
+                                    && !Self::is_simple_object(map)
+                                {
+                                    let mut changed = false;
+                                    ui.group(|ui| {
+                                        ui.label(format!(
+                                            "{} {{{}}}",
+                                            statics::EN_MIXED_OBJECT_EDITOR,
+                                            map.len()
+                                        ));
+                                        changed = self.render_mixed_object_editor(
+                                            ui,
+                                            prop,
+                                            map,
+                                            &save.index.id_to_display_name,
+                                        );
                                     });
                                     if changed {
                                         self.edit_buffer = staged.to_ti_save_pretty();
@@ -2493,15 +4984,43 @@ impl TiseApp {
                             }
 
                             // Always display arrays/objects in a formatted multiline text box.
+                            let editor_id = ui.id().with("raw_value_editor");
+                            ui.horizontal(|ui| {
+                                if ui.button(statics::EN_BTN_SELECT_ALL).clicked() {
+                                    Self::select_all_text(ui.ctx(), editor_id, &self.edit_buffer);
+                                }
+                                ui.checkbox(&mut self.raw_editor_wrap, statics::EN_LABEL_WORD_WRAP);
+                            });
+
                             // Leave a small safety margin so we don't spill outside the viewport on
                             // some platforms/window configurations.
                             let editor_h = (ui.available_height() - 8.0).max(120.0);
-                            let editor = egui::TextEdit::multiline(&mut self.edit_buffer)
+                            let wrap = self.raw_editor_wrap;
+                            let mut no_wrap_layouter =
+                                |ui: &egui::Ui, buf: &dyn egui::TextBuffer, _wrap_width: f32| {
+                                    let job = egui::text::LayoutJob::simple(
+                                        buf.as_str().to_owned(),
+                                        egui::TextStyle::Monospace.resolve(ui.style()),
+                                        ui.visuals().text_color(),
+                                        f32::INFINITY,
+                                    );
+                                    ui.fonts_mut(|f| f.layout_job(job))
+                                };
+                            let mut editor = egui::TextEdit::multiline(&mut self.edit_buffer)
+                                .id(editor_id)
                                 .font(egui::TextStyle::Monospace)
                                 .desired_rows(10)
                                 .lock_focus(true)
                                 .interactive(true);
-                            let resp = ui.add_sized([ui.available_width(), editor_h], editor);
+                            if !wrap {
+                                editor = editor.layouter(&mut no_wrap_layouter);
+                            }
+                            let resp = egui::ScrollArea::horizontal()
+                                .id_salt("raw_value_editor_scroll")
+                                .show(ui, |ui| {
+                                    ui.add_sized([ui.available_width(), editor_h], editor)
+                                })
+                                .inner;
                             if resp.lost_focus()
                                 && let Ok(v) = TiValue::parse_json5(self.edit_buffer.trim())
                             {
@@ -2516,13 +5035,76 @@ impl TiseApp {
         });
     }
 
-    fn render_ref_list_table(
-        &mut self,
-        ui: &mut egui::Ui,
-        ids: &[i64],
-        id_lookup: &std::collections::HashMap<i64, (String, usize)>,
-        id_to_display_name: &std::collections::HashMap<i64, String>,
-    ) {
+    /// Focus a text edit and select its whole text, e.g. so a huge value can be copied at once.
+    fn select_all_text(ctx: &egui::Context, id: egui::Id, text: &str) {
+        let mut state = egui::TextEdit::load_state(ctx, id).unwrap_or_default();
+        state
+            .cursor
+            .set_char_range(Some(egui::text::CCursorRange::two(
+                egui::text::CCursor::new(0),
+                egui::text::CCursor::new(text.chars().count()),
+            )));
+        state.store(ctx, id);
+        ctx.memory_mut(|m| m.request_focus(id));
+    }
+
+    /// Structured inputs for a date object; edits go to the edit buffer like the other editors.
+    fn render_date_editor(&mut self, ui: &mut egui::Ui, prop: &str, mut date: TiDate) {
+        ui.label(statics::EN_DATE_EDITOR);
+
+        let max_day = days_in_month(date.year, date.month);
+        let mut changed = false;
+        ui.horizontal_wrapped(|ui| {
+            let fields = [
+                (statics::EN_DATE_YEAR, &mut date.year, 1, 9999),
+                (statics::EN_DATE_MONTH, &mut date.month, 1, 12),
+                (statics::EN_DATE_DAY, &mut date.day, 1, max_day),
+                (statics::EN_DATE_HOUR, &mut date.hour, 0, 23),
+                (statics::EN_DATE_MINUTE, &mut date.minute, 0, 59),
+                (statics::EN_DATE_SECOND, &mut date.second, 0, 59),
+                (statics::EN_DATE_MILLISECOND, &mut date.millisecond, 0, 999),
+            ];
+            for (label, value, min, max) in fields {
+                ui.label(label);
+                changed |= ui
+                    .add(egui::DragValue::new(value).range(min..=max))
+                    .changed();
+            }
+        });
+
+        // Typed input, e.g. "2031-04-16 12:00"; applied to the buffer when focus leaves.
+        let key = format!("{prop}::date");
+        let mut text = self
+            .nested_edit_buffers
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| date.format());
+        let resp = ui.add(egui::TextEdit::singleline(&mut text).hint_text(statics::EN_HINT_DATE));
+        if resp.changed() {
+            self.nested_edit_buffers.insert(key.clone(), text.clone());
+        }
+        if resp.lost_focus() && self.nested_edit_buffers.contains_key(&key) {
+            match TiDate::parse(&text) {
+                Ok(parsed) => {
+                    date = parsed;
+                    changed = true;
+                    self.last_error = None;
+                }
+                Err(e) => self.set_error(format!("{e:#}")),
+            }
+        }
+
+        if changed {
+            // Changing the month or year can leave the day past the end of the month.
+            date.day = date.day.min(days_in_month(date.year, date.month));
+            self.edit_buffer = date.to_value().to_ti_save_pretty();
+            self.nested_edit_buffers.remove(&key);
+        }
+    }
+
+    fn render_ref_list_table(&mut self, ui: &mut egui::Ui, ids: &[i64], save: &LoadedSave) {
+        let id_lookup = &save.index.id_lookup;
+        let id_to_display_name = &save.index.id_to_display_name;
         let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
         ui.push_id("ref_list_table", |ui| {
             TableBuilder::new(ui)
@@ -2546,8 +5128,7 @@ impl TiseApp {
                                     if let Some((ref_group, _)) = id_lookup.get(id) {
                                         self.select_object_programmatic(ref_group, *id, true, true);
                                     } else {
-                                        self.last_error =
-                                            Some(format!("Reference ID {id} not found"));
+                                        self.set_error(format!("Reference ID {id} not found"));
                                     }
                                 }
                             });
@@ -2556,11 +5137,12 @@ impl TiseApp {
                                     .get(id)
                                     .map(String::as_str)
                                     .unwrap_or(statics::EN_EMPTY);
-                                if name.is_empty() {
-                                    ui.label(format!("{id}"));
+                                let label = if name.is_empty() {
+                                    ui.label(format!("{id}"))
                                 } else {
-                                    ui.label(format!("{id}: {name}"));
-                                }
+                                    ui.label(format!("{id}: {name}"))
+                                };
+                                label.on_hover_ui(|ui| ref_peek_ui(ui, save, *id));
                             });
                         });
                     }
@@ -2572,9 +5154,10 @@ impl TiseApp {
         &mut self,
         ui: &mut egui::Ui,
         rows: &[(i64, String)],
-        id_lookup: &std::collections::HashMap<i64, (String, usize)>,
-        id_to_display_name: &std::collections::HashMap<i64, String>,
+        save: &LoadedSave,
     ) {
+        let id_lookup = &save.index.id_lookup;
+        let id_to_display_name = &save.index.id_to_display_name;
         let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
         ui.push_id("key_value_ref_table", |ui| {
             TableBuilder::new(ui)
@@ -2602,8 +5185,7 @@ impl TiseApp {
                                     if let Some((ref_group, _)) = id_lookup.get(id) {
                                         self.select_object_programmatic(ref_group, *id, true, true);
                                     } else {
-                                        self.last_error =
-                                            Some(format!("Reference ID {id} not found"));
+                                        self.set_error(format!("Reference ID {id} not found"));
                                     }
                                 }
                             });
@@ -2612,11 +5194,12 @@ impl TiseApp {
                                     .get(id)
                                     .map(String::as_str)
                                     .unwrap_or(statics::EN_EMPTY);
-                                if name.is_empty() {
-                                    ui.label(format!("{id}"));
+                                let label = if name.is_empty() {
+                                    ui.label(format!("{id}"))
                                 } else {
-                                    ui.label(format!("{id}: {name}"));
-                                }
+                                    ui.label(format!("{id}: {name}"))
+                                };
+                                label.on_hover_ui(|ui| ref_peek_ui(ui, save, *id));
                             });
                             row.col(|ui| {
                                 ui.monospace(v);
@@ -2628,6 +5211,74 @@ impl TiseApp {
     }
 }
 
+/// Hover popup for a reference: the target's name, group and `$type`, then its first few
+/// properties, so a reference can be checked without navigating away.
+fn ref_peek_ui(ui: &mut egui::Ui, save: &LoadedSave, id: i64) {
+    let Some(group) = save.object_group(id) else {
+        ui.label(format!("Reference ID {id} not found"));
+        return;
+    };
+    let names = &save.index.id_to_display_name;
+    ui.strong(ref_label(id, names));
+    ui.label(LoadedSave::group_display_name(group));
+    let Some(props) = save.object_properties(group, id) else {
+        return;
+    };
+    if let Some(ty) = props
+        .get(statics::TI_REF_FIELD_TYPE)
+        .and_then(TiValue::as_str)
+    {
+        ui.monospace(ty);
+    }
+    ui.separator();
+    let shown: Vec<_> = props
+        .iter()
+        .filter(|(k, _)| k.as_str() != statics::TI_REF_FIELD_TYPE)
+        .take(REF_PEEK_PROPS)
+        .collect();
+    egui::Grid::new(("ref_peek", id))
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (k, v) in &shown {
+                ui.monospace(k.as_str());
+                ui.label(value_preview_refs(v, Some(names)));
+                ui.end_row();
+            }
+        });
+    let listed = props
+        .keys()
+        .filter(|k| k.as_str() != statics::TI_REF_FIELD_TYPE)
+        .count();
+    let hidden = listed - shown.len();
+    if hidden > 0 {
+        ui.weak(format!("+{hidden} {}", statics::EN_REF_PEEK_MORE));
+    }
+}
+
+fn format_label(format: SaveFormat) -> &'static str {
+    match format {
+        SaveFormat::Json5 => statics::EN_FORMAT_JSON5,
+        SaveFormat::GzipJson5 => statics::EN_FORMAT_GZIP_JSON5,
+    }
+}
+
+/// Dark or light visuals, with the accent colour (if any) on selections and active widgets.
+fn theme_visuals(settings: &Settings) -> egui::Visuals {
+    let mut visuals = if settings.theme_dark {
+        egui::Visuals::dark()
+    } else {
+        egui::Visuals::light()
+    };
+    if let Some([r, g, b]) = settings.accent_color {
+        let accent = egui::Color32::from_rgb(r, g, b);
+        visuals.selection.bg_fill = accent;
+        visuals.widgets.active.bg_fill = accent;
+        visuals.widgets.active.weak_bg_fill = accent;
+        visuals.widgets.hovered.bg_stroke.color = accent;
+    }
+    visuals
+}
+
 fn value_preview(val: &TiValue) -> String {
     match val {
         TiValue::Null => "null".to_string(),
@@ -2663,8 +5314,105 @@ fn value_preview(val: &TiValue) -> String {
             }
             s
         }
-        TiValue::Array(values) => format!("[{}]", values.len()),
-        TiValue::Object(map) => format!("{{{}}}", map.len()),
+        TiValue::Array(_) | TiValue::Object(_) => count_badge(val).unwrap_or_default(),
+    }
+}
+
+/// The exact decimal value of a finite float, e.g. `0.1` is really
+/// `0.1000000000000000055511151231257827021181583404541015625`. `None` for anything else.
+fn float_full_expansion(val: &TiValue) -> Option<String> {
+    let TiValue::Number(crate::value::TiNumber::F64(v)) = val else {
+        return None;
+    };
+    if !v.is_finite() {
+        return None;
+    }
+    // Every finite f64 is a multiple of 2^-1074, so 1074 fractional digits are always exact.
+    let s = format!("{v:.1074}");
+    let s = s.trim_end_matches('0');
+    Some(
+        s.strip_suffix('.')
+            .map_or(s.to_string(), |s| format!("{s}.0")),
+    )
+}
+
+/// Element count of an array (`[N]`) or object (`{N}`), as shown wherever structures are listed.
+fn count_badge(val: &TiValue) -> Option<String> {
+    match val {
+        TiValue::Array(values) => Some(format!("[{}]", values.len())),
+        TiValue::Object(map) => Some(format!("{{{}}}", map.len())),
+        _ => None,
+    }
+}
+
+/// `id: name` for a reference, or just the id when the target has no name.
+fn ref_label(id: i64, id_to_display_name: &std::collections::HashMap<i64, String>) -> String {
+    match id_to_display_name.get(&id) {
+        Some(name) if !name.is_empty() => format!("{id}: {name}"),
+        _ => id.to_string(),
+    }
+}
+
+/// Like `value_preview`, but with `names` given, references show as `id: name` instead of
+/// their `{value, $type}` object.
+/// One Changes window row: its number, a Go button and a before -> after summary.
+/// Returns whether Go was clicked.
+fn change_row_ui(
+    ui: &mut egui::Ui,
+    i: usize,
+    action: &EditAction,
+    names: Option<&std::collections::HashMap<i64, String>>,
+) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(format!("{}.", i + 1));
+        let go = ui.small_button(statics::EN_BTN_GO).clicked();
+
+        let mut text = if let (Some(b), Some(a)) = (&action.before, &action.after) {
+            let s_b = value_preview_refs(b, names);
+            let s_a = value_preview_refs(a, names);
+            format!("{}.{}: {} -> {}", action.object_id, action.prop, s_b, s_a)
+        } else {
+            // Fallback if values missing (should be rare/legacy).
+            format!(
+                "{}.{}: {}",
+                action.object_id, action.prop, action.description
+            )
+        };
+
+        // Limit line length as requested.
+        if text.len() > 100 {
+            text.truncate(97);
+            text.push_str("...");
+        }
+        ui.label(text);
+        go
+    })
+    .inner
+}
+
+type ObjectChanges<'a> = ((String, i64), Vec<(usize, &'a EditAction)>);
+
+/// Changes grouped by (group, object ID) in order of each object's first change; actions keep
+/// their undo stack index and chronological order within a group.
+fn group_changes_by_object(actions: Vec<(usize, &EditAction)>) -> Vec<ObjectChanges<'_>> {
+    let mut groups: Vec<ObjectChanges> = Vec::new();
+    for (i, action) in actions {
+        let key = (action.group.clone(), action.object_id);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, list)) => list.push((i, action)),
+            None => groups.push((key, vec![(i, action)])),
+        }
+    }
+    groups
+}
+
+fn value_preview_refs(
+    val: &TiValue,
+    names: Option<&std::collections::HashMap<i64, String>>,
+) -> String {
+    match (names, val.is_relational_ref()) {
+        (Some(names), Some(id)) => ref_label(id, names),
+        _ => value_preview(val),
     }
 }
 
@@ -2710,11 +5458,20 @@ fn array_of_key_value_refs(val: &TiValue) -> Option<Vec<(i64, String)>> {
 
 impl eframe::App for TiseApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // The UI scale multiplies the OS scaling; re-applied each frame so it survives moving the
+        // window to a display with different scaling.
+        let pixels_per_point =
+            ctx.native_pixels_per_point().unwrap_or(1.0) * self.settings.clamped_ui_scale();
+        if (ctx.pixels_per_point() - pixels_per_point).abs() > f32::EPSILON {
+            ctx.set_pixels_per_point(pixels_per_point);
+        }
+
         // Undo/Redo shortcuts.
         // Note: we explicitly consume these keys so egui text editors don't also apply their own
         // internal undo/redo to our edit buffers.
         let mut do_undo = false;
         let mut do_redo = false;
+        let mut do_toggle_theme = false;
         ctx.input_mut(|i| {
             let ctrl_shift = egui::Modifiers {
                 shift: true,
@@ -2729,7 +5486,13 @@ impl eframe::App for TiseApp {
             if i.consume_key(egui::Modifiers::CTRL, egui::Key::Z) {
                 do_undo = true;
             }
+            if i.consume_key(egui::Modifiers::CTRL, egui::Key::T) {
+                do_toggle_theme = true;
+            }
         });
+        if do_toggle_theme {
+            self.toggle_theme(ctx);
+        }
         if do_undo {
             self.undo();
             ctx.request_repaint();
@@ -2762,6 +5525,13 @@ impl eframe::App for TiseApp {
                 }
 
                 let has_save = self.save.is_some();
+                if ui
+                    .add_enabled(has_save, egui::Button::new(statics::EN_BTN_OPEN_OTHER))
+                    .clicked()
+                {
+                    self.open_other_file();
+                }
+
                 if ui
                     .add_enabled(has_save, egui::Button::new(statics::EN_BTN_SAVE_AS))
                     .clicked()
@@ -2769,19 +5539,182 @@ impl eframe::App for TiseApp {
                     self.save_file();
                 }
 
+                if ui
+                    .add_enabled(has_save, egui::Button::new(statics::EN_BTN_FLATTEN_EXPORT))
+                    .clicked()
+                {
+                    self.export_flat_file();
+                }
+
+                if ui
+                    .add_enabled(has_save, egui::Button::new(statics::EN_BTN_FLATTEN_IMPORT))
+                    .clicked()
+                {
+                    self.import_flat_file();
+                }
+
                 if ui.button(statics::EN_BTN_ABOUT).clicked() {
                     self.about_open = true;
                 }
 
+                if ui.button(statics::EN_BTN_LOG).clicked() {
+                    self.message_log_open = true;
+                }
+
                 if ui.button(statics::EN_BTN_TOGGLE_THEME).clicked() {
-                    self.theme_dark = !self.theme_dark;
-                    if self.theme_dark {
-                        ctx.set_visuals(egui::Visuals::dark());
-                    } else {
-                        ctx.set_visuals(egui::Visuals::light());
-                    }
+                    self.toggle_theme(ctx);
                 }
 
+                ui.menu_button(statics::EN_MENU_SETTINGS, |ui| {
+                    if ui
+                        .checkbox(
+                            &mut self.settings.preview_before_save,
+                            statics::EN_SETTING_PREVIEW_BEFORE_SAVE,
+                        )
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+                    ui.checkbox(
+                        &mut self.show_round_trip_column,
+                        statics::EN_SETTING_ROUND_TRIP_COLUMN,
+                    );
+                    ui.checkbox(
+                        &mut self.show_full_float_precision,
+                        statics::EN_SETTING_FULL_FLOAT_PRECISION,
+                    )
+                    .on_hover_text(statics::EN_HINT_FULL_FLOAT_PRECISION);
+                    ui.separator();
+                    if ui
+                        .checkbox(
+                            &mut self.settings.validate_after_edit,
+                            statics::EN_SETTING_VALIDATE_AFTER_EDIT,
+                        )
+                        .on_hover_text(statics::EN_HINT_VALIDATE_AFTER_EDIT)
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+                    if ui
+                        .checkbox(
+                            &mut self.settings.minimize_diff,
+                            statics::EN_SETTING_MINIMIZE_DIFF,
+                        )
+                        .on_hover_text(statics::EN_HINT_MINIMIZE_DIFF)
+                        .changed()
+                    {
+                        if let Some(save) = &mut self.save {
+                            save.minimize_diff = self.settings.minimize_diff;
+                            save.refresh_dirty();
+                        }
+                        self.save_settings();
+                    }
+                    if ui
+                        .checkbox(
+                            &mut self.settings.plain_empty_objects,
+                            statics::EN_SETTING_PLAIN_EMPTY_OBJECTS,
+                        )
+                        .on_hover_text(statics::EN_HINT_PLAIN_EMPTY_OBJECTS)
+                        .changed()
+                    {
+                        if let Some(save) = &mut self.save {
+                            save.empty_object_quirk = !self.settings.plain_empty_objects;
+                            save.refresh_dirty();
+                        }
+                        self.save_settings();
+                    }
+                    if ui
+                        .checkbox(
+                            &mut self.settings.sticky_property,
+                            statics::EN_SETTING_STICKY_PROPERTY,
+                        )
+                        .on_hover_text(statics::EN_HINT_STICKY_PROPERTY)
+                        .changed()
+                    {
+                        self.save_settings();
+                    }
+                    let groups = ui.checkbox(
+                        &mut self.settings.show_groups_panel,
+                        statics::EN_SETTING_SHOW_GROUPS_PANEL,
+                    );
+                    let objects = ui.checkbox(
+                        &mut self.settings.show_objects_panel,
+                        statics::EN_SETTING_SHOW_OBJECTS_PANEL,
+                    );
+                    if groups.changed() || objects.changed() {
+                        self.save_settings();
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(statics::EN_SETTING_UI_SCALE);
+                        let slider = ui.add(
+                            egui::Slider::new(&mut self.ui_scale_input, UI_SCALE_RANGE)
+                                .step_by(0.05),
+                        );
+                        // Rescaling mid-drag would move the slider under the pointer.
+                        let mut apply =
+                            slider.drag_stopped() || (slider.changed() && !slider.dragged());
+                        if ui.small_button(statics::EN_BTN_RESET).clicked() {
+                            self.ui_scale_input = 1.0;
+                            apply = true;
+                        }
+                        if apply {
+                            self.settings.ui_scale = self.ui_scale_input;
+                            self.save_settings();
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        let mut custom = self.settings.accent_color.is_some();
+                        let mut changed = ui
+                            .checkbox(&mut custom, statics::EN_SETTING_ACCENT_COLOR)
+                            .changed();
+                        let mut rgb = self.settings.accent_color.unwrap_or_else(|| {
+                            let c = ctx.style().visuals.selection.bg_fill;
+                            [c.r(), c.g(), c.b()]
+                        });
+                        if custom {
+                            changed |= ui.color_edit_button_srgb(&mut rgb).changed();
+                        }
+                        if changed {
+                            self.settings.accent_color = custom.then_some(rgb);
+                            ctx.set_visuals(theme_visuals(&self.settings));
+                            self.save_settings();
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(statics::EN_SETTING_FLOAT_DECIMALS);
+                        let label = |d: Option<usize>| {
+                            d.map_or(statics::EN_FLOAT_DECIMALS_AUTO.to_string(), |d| {
+                                d.to_string()
+                            })
+                        };
+                        let before = self.settings.float_decimals;
+                        egui::ComboBox::from_id_salt("float_decimals")
+                            .selected_text(label(before))
+                            .show_ui(ui, |ui| {
+                                for d in std::iter::once(None).chain((0..=6).map(Some)) {
+                                    ui.selectable_value(
+                                        &mut self.settings.float_decimals,
+                                        d,
+                                        label(d),
+                                    );
+                                }
+                            });
+                        if self.settings.float_decimals != before {
+                            self.save_settings();
+                        }
+                    });
+                    if ui
+                        .checkbox(
+                            &mut self.collapse_ref_types,
+                            statics::EN_SETTING_COLLAPSE_REF_TYPES,
+                        )
+                        .changed()
+                    {
+                        // Cached hits hold previews rendered with the old setting.
+                        self.search_items_cache = None;
+                    }
+                });
+
                 ui.separator();
                 let can_undo = self.save.is_some() && !self.undo_stack.is_empty();
                 let can_redo = self.save.is_some() && !self.redo_stack.is_empty();
@@ -2847,6 +5780,24 @@ impl eframe::App for TiseApp {
                     self.search_items_open = true;
                     self.search_items_request_focus = true;
                 }
+                if ui
+                    .add_enabled(has_save, egui::Button::new(statics::EN_BTN_FIND_PROPERTY))
+                    .on_hover_text(statics::EN_HINT_FIND_PROPERTY)
+                    .clicked()
+                {
+                    self.property_query = Some(PropertyQuery {
+                        group: self.selected_group.clone(),
+                        request_focus: true,
+                        ..PropertyQuery::default()
+                    });
+                }
+                if ui
+                    .add_enabled(has_save, egui::Button::new(statics::EN_BTN_NON_FINITE))
+                    .on_hover_text(statics::EN_HINT_NON_FINITE)
+                    .clicked()
+                {
+                    self.non_finite = self.save.as_ref().map(LoadedSave::non_finite_numbers);
+                }
 
                 if !self.status.is_empty() {
                     ui.separator();
@@ -2858,47 +5809,130 @@ impl eframe::App for TiseApp {
         if self.changes_open {
             let mut open = self.changes_open;
             let mut go_to_action_idx = None;
+            let names = self
+                .save
+                .as_ref()
+                .filter(|_| self.collapse_ref_types)
+                .map(|save| &save.index.id_to_display_name);
+            let all_names = self
+                .save
+                .as_ref()
+                .map(|save| &save.index.id_to_display_name);
+            let mut step_all = None;
+            let mut export_changes = false;
+            let mut replay_log = false;
 
             egui::Window::new(statics::EN_WINDOW_CHANGES)
                 .collapsible(false)
                 .open(&mut open)
                 .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                !self.undo_stack.is_empty(),
+                                egui::Button::new(statics::EN_BTN_UNDO_ALL),
+                            )
+                            .clicked()
+                        {
+                            step_all = Some(false);
+                        }
+                        if ui
+                            .add_enabled(
+                                !self.redo_stack.is_empty(),
+                                egui::Button::new(statics::EN_BTN_REDO_ALL),
+                            )
+                            .clicked()
+                        {
+                            step_all = Some(true);
+                        }
+                        if ui
+                            .add_enabled(
+                                !self.undo_stack.is_empty(),
+                                egui::Button::new(statics::EN_BTN_EXPORT_CHANGES),
+                            )
+                            .clicked()
+                        {
+                            export_changes = true;
+                        }
+                        if ui.button(statics::EN_BTN_REPLAY_LOG).clicked() {
+                            replay_log = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(statics::EN_LABEL_FILTER);
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.changes_filter)
+                                .hint_text(statics::EN_HINT_CHANGES_FILTER),
+                        );
+                        if ui.small_button(statics::EN_BTN_CLEAR).clicked() {
+                            self.changes_filter.clear();
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(
+                            &mut self.changes_by_object,
+                            false,
+                            statics::EN_CHANGES_MODE_CHRONOLOGICAL,
+                        );
+                        ui.selectable_value(
+                            &mut self.changes_by_object,
+                            true,
+                            statics::EN_CHANGES_MODE_BY_OBJECT,
+                        );
+                    });
+                    ui.separator();
+                    let shown: Vec<(usize, &EditAction)> = self
+                        .undo_stack
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, a)| Self::action_matches_filter(a, &self.changes_filter))
+                        .collect();
                     if self.undo_stack.is_empty() {
                         ui.label(statics::EN_CHANGES_NONE);
+                    } else if shown.is_empty() {
+                        ui.label(statics::EN_CHANGES_NO_MATCH);
                     } else {
                         ui.push_id("changes_scroll", |ui| {
                             egui::ScrollArea::vertical().show(ui, |ui| {
-                                for (i, action) in self.undo_stack.iter().enumerate() {
-                                    ui.horizontal(|ui| {
-                                        ui.label(format!("{}.", i + 1));
-                                        if ui.small_button(statics::EN_BTN_GO).clicked() {
+                                if !self.changes_by_object {
+                                    for (i, action) in shown {
+                                        if change_row_ui(ui, i, action, names) {
                                             go_to_action_idx = Some(i);
                                         }
-
-                                        let mut text = if let (Some(b), Some(a)) =
-                                            (&action.before, &action.after)
-                                        {
-                                            let s_b = value_preview(b);
-                                            let s_a = value_preview(a);
-                                            format!(
-                                                "{}.{}: {} -> {}",
-                                                action.object_id, action.prop, s_b, s_a
-                                            )
+                                    }
+                                    return;
+                                }
+                                for ((group, object_id), actions) in group_changes_by_object(shown)
+                                {
+                                    let name = all_names
+                                        .and_then(|n| n.get(&object_id))
+                                        .map(String::as_str)
+                                        .unwrap_or(statics::EN_EMPTY);
+                                    let mut props: Vec<&str> = Vec::new();
+                                    for (_, a) in &actions {
+                                        let prop = if a.prop.is_empty() {
+                                            statics::EN_CHANGES_WHOLE_OBJECT
                                         } else {
-                                            // Fallback if values missing (should be rare/legacy).
-                                            format!(
-                                                "{}.{}: {}",
-                                                action.object_id, action.prop, action.description
-                                            )
+                                            a.prop.as_str()
                                         };
-
-                                        // Limit line length as requested.
-                                        if text.len() > 100 {
-                                            text.truncate(97);
-                                            text.push_str("...");
+                                        if !props.contains(&prop) {
+                                            props.push(prop);
                                         }
-                                        ui.label(text);
-                                    });
+                                    }
+                                    let header = format!(
+                                        "{group} {object_id} {name} ({}): {}",
+                                        actions.len(),
+                                        props.join(", ")
+                                    );
+                                    egui::CollapsingHeader::new(header)
+                                        .id_salt(("changes_object", group.as_str(), object_id))
+                                        .show(ui, |ui| {
+                                            for (i, action) in actions {
+                                                if change_row_ui(ui, i, action, names) {
+                                                    go_to_action_idx = Some(i);
+                                                }
+                                            }
+                                        });
                                 }
                             });
                         });
@@ -2908,7 +5942,16 @@ impl eframe::App for TiseApp {
                 });
             self.changes_open = open;
 
-            if let Some(idx) = go_to_action_idx
+            if export_changes {
+                self.export_changes_file();
+            }
+            if replay_log {
+                self.replay_log_file();
+            }
+
+            if let Some(forward) = step_all {
+                self.step_history(forward, true);
+            } else if let Some(idx) = go_to_action_idx
                 && let Some(save) = self.save.take()
             {
                 let action = self.undo_stack[idx].clone();
@@ -2933,6 +5976,10 @@ impl eframe::App for TiseApp {
                     ui.label(statics::EN_ABOUT_SHORTCUTS);
                     ui.label(statics::EN_ABOUT_SHORTCUT_ALT);
                     ui.label(statics::EN_ABOUT_SHORTCUT_MOUSE);
+                    ui.label(statics::EN_ABOUT_SHORTCUT_OBJECT_NAV);
+                    ui.label(statics::EN_ABOUT_SHORTCUT_STEP_OBJECT);
+                    ui.label(statics::EN_ABOUT_SHORTCUT_THEME);
+                    ui.label(statics::EN_ABOUT_SHORTCUT_APPLY);
                     ui.separator();
                     ui.hyperlink_to(
                         format!("{} @ {}", statics::EN_PROJECT_REPO, statics::GITHUB_URL),
@@ -2942,44 +5989,652 @@ impl eframe::App for TiseApp {
             self.about_open = open;
         }
 
-        if let Some(err) = self.last_error.clone() {
-            egui::TopBottomPanel::top("error_bar").show(ctx, |ui| {
-                ui.horizontal(|ui| {
-                    ui.colored_label(egui::Color32::RED, err);
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.small_button(statics::EN_BTN_CLEAR).clicked() {
-                            self.last_error = None;
+        if self.message_log_open {
+            let mut open = self.message_log_open;
+            let mut copy = None;
+            egui::Window::new(statics::EN_WINDOW_LOG)
+                .open(&mut open)
+                .default_width(520.0)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button(statics::EN_BTN_COPY_ALL).clicked() {
+                            copy = Some(
+                                self.message_log
+                                    .iter()
+                                    .map(|m| m.text.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join("\n"),
+                            );
+                        }
+                        if ui.button(statics::EN_BTN_CLEAR).clicked() {
+                            self.message_log.clear();
                         }
                     });
+                    ui.separator();
+                    if self.message_log.is_empty() {
+                        ui.weak(statics::EN_LOG_EMPTY);
+                    }
+                    egui::ScrollArea::vertical()
+                        .max_height(320.0)
+                        .stick_to_bottom(true)
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| {
+                            for message in &self.message_log {
+                                if message.error {
+                                    ui.colored_label(egui::Color32::RED, &message.text);
+                                } else {
+                                    ui.label(&message.text);
+                                }
+                            }
+                        });
                 });
-            });
-        }
-
-        if self.save.is_none() {
-            egui::CentralPanel::default().show(ctx, |ui| {
-                ui.heading(statics::EN_HOME_HEADING);
-                ui.label(statics::EN_HOME_INSTRUCTIONS);
-            });
-            return;
+            if let Some(text) = copy {
+                self.copy_to_clipboard(ctx, text);
+            }
+            self.message_log_open = open;
         }
 
-        let mut save = self.save.take().expect("checked above");
-
-        // We clone groups (List of strings) to allow sorting in UI (cheap).
-        // Larger maps are referenced directly from `save.index`.
-        let mut groups = save.index.groups.clone();
-
-        // Use references for the massive maps.
-        let objects_by_group = &save.index.objects_by_group;
+        if let Some((title, problems)) = self.flat_import_report.as_ref() {
+            let mut open = true;
+            let mut dismissed = false;
+            egui::Window::new(*title)
+                .collapsible(false)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    ui.label(format!("{} line(s) were not applied:", problems.len()));
+                    ui.separator();
+                    ui.push_id("flat_import_report_scroll", |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(300.0)
+                            .show(ui, |ui| {
+                                for p in problems {
+                                    ui.monospace(p);
+                                }
+                            });
+                    });
+                    ui.separator();
+                    if ui.button(statics::EN_BTN_CLOSE).clicked() {
+                        dismissed = true;
+                    }
+                });
+            if dismissed || !open {
+                self.flat_import_report = None;
+            }
+        }
+
+        if let Some(path) = self.save_as_confirm_path.clone()
+            && let Some(original) = self.save.as_ref().map(|s| s.format)
+        {
+            let mut open = true;
+            let mut choice = None;
+            let mut cancelled = false;
+            let inferred = SaveFormat::for_path(&path);
+            let kept_path = original.adjust_path(&path);
+            let title = if inferred == original {
+                statics::EN_WINDOW_CONFIRM_SAVE_AS
+            } else {
+                statics::EN_WINDOW_FORMAT_CHANGE
+            };
+            egui::Window::new(title)
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    if inferred == original {
+                        ui.label(format!(
+                            "{} {} {} {}",
+                            statics::EN_CONFIRM_SAVE_AS_WRITING,
+                            format_label(inferred),
+                            statics::EN_CONFIRM_SAVE_AS_TO,
+                            path.display()
+                        ));
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            if ui.button(statics::EN_BTN_OK).clicked() {
+                                choice = Some(path.clone());
+                            }
+                            if ui.button(statics::EN_BTN_CANCEL).clicked() {
+                                cancelled = true;
+                            }
+                        });
+                        return;
+                    }
+                    ui.label(format!(
+                        "{} {}",
+                        statics::EN_SAVE_PREVIEW_TARGET,
+                        path.display()
+                    ));
+                    ui.label(format!(
+                        "{} {}, {} {}.",
+                        statics::EN_FORMAT_CHANGE_LOADED,
+                        format_label(original),
+                        statics::EN_FORMAT_CHANGE_WOULD_WRITE,
+                        format_label(inferred)
+                    ));
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(format!(
+                                "{} {}",
+                                statics::EN_BTN_KEEP_FORMAT,
+                                format_label(original)
+                            ))
+                            .on_hover_text(kept_path.display().to_string())
+                            .clicked()
+                        {
+                            choice = Some(kept_path.clone());
+                        }
+                        if ui
+                            .button(format!(
+                                "{} {}",
+                                statics::EN_BTN_SAVE_AS_FORMAT,
+                                format_label(inferred)
+                            ))
+                            .clicked()
+                        {
+                            choice = Some(path.clone());
+                        }
+                        if ui.button(statics::EN_BTN_CANCEL).clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            if let Some(chosen) = choice {
+                self.save_as_confirm_path = None;
+                self.save_to_chosen_path(chosen);
+            } else if cancelled || !open {
+                self.save_as_confirm_path = None;
+            }
+        }
+
+        if let Some(preview) = self.save_preview.as_ref() {
+            let mut open = true;
+            let mut confirmed = false;
+            let mut cancelled = false;
+
+            egui::Window::new(statics::EN_WINDOW_SAVE_PREVIEW)
+                .collapsible(false)
+                .default_size([800.0, 500.0])
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "{} {}",
+                        statics::EN_SAVE_PREVIEW_TARGET,
+                        preview.path.display()
+                    ));
+                    if preview.rows.is_empty() {
+                        ui.label(statics::EN_SAVE_PREVIEW_NO_CHANGES);
+                    } else {
+                        ui.label(format!(
+                            "{} line(s) removed, {} line(s) added",
+                            preview.removed, preview.added
+                        ));
+                    }
+                    ui.separator();
+
+                    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                    ui.push_id("save_preview_scroll", |ui| {
+                        egui::ScrollArea::both()
+                            .max_height(ui.available_height() - 40.0)
+                            .auto_shrink([false, true])
+                            .show_rows(ui, row_height, preview.rows.len(), |ui, range| {
+                                for row in &preview.rows[range] {
+                                    let (text, color) = match row {
+                                        SavePreviewRow::Header(h) => {
+                                            (h.clone(), ui.visuals().weak_text_color())
+                                        }
+                                        SavePreviewRow::Line(DiffLine::Context(l)) => {
+                                            (format!("  {l}"), ui.visuals().text_color())
+                                        }
+                                        SavePreviewRow::Line(DiffLine::Removed(l)) => {
+                                            (format!("- {l}"), egui::Color32::from_rgb(220, 80, 80))
+                                        }
+                                        SavePreviewRow::Line(DiffLine::Added(l)) => {
+                                            (format!("+ {l}"), egui::Color32::from_rgb(80, 180, 80))
+                                        }
+                                    };
+                                    ui.label(egui::RichText::new(text).monospace().color(color));
+                                }
+                            });
+                    });
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button(statics::EN_BTN_SAVE).clicked() {
+                            confirmed = true;
+                        }
+                        if ui.button(statics::EN_BTN_CANCEL).clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+
+            if confirmed {
+                let path = preview.path.clone();
+                self.save_preview = None;
+                self.write_save_to(&path);
+            } else if cancelled || !open {
+                self.save_preview = None;
+            }
+        }
+
+        if let Some(summary) = &self.load_summary
+            && self.save.is_some()
+        {
+            let mut dismissed = false;
+            egui::TopBottomPanel::top("load_summary_bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong(summary)
+                        .on_hover_text(statics::EN_HINT_LOADED_SUMMARY);
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        dismissed = ui.small_button(statics::EN_BTN_CLOSE).clicked();
+                    });
+                });
+            });
+            if dismissed {
+                self.load_summary = None;
+            }
+        }
+
+        if let Some(err) = self.last_error.clone() {
+            // Lines after the first (e.g. a syntax error's source line and caret) are monospace.
+            let (first, detail) = err.split_once('\n').unwrap_or((err.as_str(), ""));
+            egui::TopBottomPanel::top("error_bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::RED, first);
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button(statics::EN_BTN_CLEAR).clicked() {
+                            self.last_error = None;
+                        }
+                        if ui.small_button(statics::EN_BTN_LOG).clicked() {
+                            self.message_log_open = true;
+                        }
+                    });
+                });
+                if !detail.is_empty() {
+                    ui.label(
+                        egui::RichText::new(detail)
+                            .monospace()
+                            .color(egui::Color32::RED),
+                    );
+                }
+            });
+        }
+
+        if self.save.is_none() {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.heading(statics::EN_HOME_HEADING);
+                ui.label(statics::EN_HOME_INSTRUCTIONS);
+            });
+            return;
+        }
+
+        let mut save = self.save.take().expect("checked above");
+
+        // We clone groups (List of strings) to allow sorting in UI (cheap).
+        // Larger maps are referenced directly from `save.index`.
+        let mut groups = save.index.groups.clone();
+
+        // Use references for the massive maps.
+        let objects_by_group = &save.index.objects_by_group;
         let id_lookup = &save.index.id_lookup;
         let id_to_display_name = &save.index.id_to_display_name;
+        let malformed_by_group = &save.index.malformed_by_group;
 
         let save_format = save.format;
         let dirty = save.dirty;
         let game_id = save.game_id();
+        let game_version = save.game_version().map(str::to_string);
+        let current_id_too_low = save.current_id_too_low();
+        let game_date = save.game_date();
+        let days_in_campaign = save.days_in_campaign();
 
         // Match Python UX: groups sorted by display name (namespace stripped).
         groups.sort_by_key(|g| LoadedSave::group_display_name(g).to_lowercase());
+        if self.settings.hide_empty_groups {
+            Self::retain_nonempty_groups(&mut groups, &save, self.selected_group.as_deref());
+        }
+        if save.has_root_properties() {
+            groups.insert(0, statics::ROOT_GROUP.to_string());
+        }
+        let root_summary = ObjectSummary {
+            id: statics::ROOT_OBJECT_ID,
+            display_name: statics::EN_ROOT_OBJECT_NAME.to_string(),
+            index_in_group: 0,
+        };
+
+        // Ctrl+PageUp/PageDown step through the selected group from anywhere; taken before the
+        // Objects panel handles plain PageUp/PageDown.
+        let mut step_object = None;
+        ctx.input_mut(|i| {
+            if i.consume_key(egui::Modifiers::CTRL, egui::Key::PageUp) {
+                step_object = Some(ObjectNav::Prev);
+            }
+            if i.consume_key(egui::Modifiers::CTRL, egui::Key::PageDown) {
+                step_object = Some(ObjectNav::Next);
+            }
+        });
+
+        let mut zero_non_finite = Vec::new();
+        if let Some(found) = self.non_finite.take() {
+            let mut open = true;
+            let mut rescan = false;
+            let mut go_to = None;
+            egui::Window::new(statics::EN_WINDOW_NON_FINITE)
+                .collapsible(false)
+                .open(&mut open)
+                .default_size([560.0, 360.0])
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{} {}",
+                            found.len(),
+                            statics::EN_LABEL_NON_FINITE_FOUND
+                        ));
+                        if ui.button(statics::EN_BTN_RESCAN).clicked() {
+                            rescan = true;
+                        }
+                        if ui
+                            .add_enabled(
+                                !found.is_empty(),
+                                egui::Button::new(statics::EN_BTN_ZERO_ALL),
+                            )
+                            .clicked()
+                        {
+                            zero_non_finite = found.clone();
+                        }
+                    });
+                    ui.separator();
+                    egui::ScrollArea::vertical()
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            for f in &found {
+                                ui.horizontal(|ui| {
+                                    if ui.small_button(statics::EN_BTN_GO).clicked() {
+                                        go_to = Some(f.clone());
+                                    }
+                                    if ui.small_button(statics::EN_BTN_ZERO).clicked() {
+                                        zero_non_finite = vec![f.clone()];
+                                    }
+                                    let value =
+                                        TiValue::Number(crate::value::TiNumber::F64(f.value));
+                                    ui.monospace(format!(
+                                        "{} {}: {}{} = {}",
+                                        LoadedSave::group_display_name(&f.group),
+                                        f.object_id,
+                                        f.prop,
+                                        f.path,
+                                        value.to_json5_compact()
+                                    ));
+                                });
+                            }
+                        });
+                });
+            if let Some(f) = go_to {
+                self.select_object_programmatic(&f.group, f.object_id, true, true);
+                self.pending_property = Some(f.prop);
+            }
+            if open {
+                self.non_finite = Some(if rescan {
+                    save.non_finite_numbers()
+                } else {
+                    found
+                });
+            }
+        }
+
+        if let Some(mut query) = self.property_query.take() {
+            let mut open = true;
+            let mut go_to = None;
+            egui::Window::new(statics::EN_WINDOW_FIND_PROPERTY)
+                .collapsible(false)
+                .open(&mut open)
+                .default_size([560.0, 360.0])
+                .show(ctx, |ui| {
+                    let mut find = false;
+                    ui.horizontal(|ui| {
+                        ui.label(statics::EN_COL_PROPERTY);
+                        let resp = ui.add(
+                            egui::TextEdit::singleline(&mut query.prop)
+                                .hint_text(statics::EN_HINT_FIND_PROPERTY_NAME),
+                        );
+                        if std::mem::take(&mut query.request_focus) {
+                            resp.request_focus();
+                        }
+                        find |= resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        let mut picked = None;
+                        egui::ComboBox::from_id_salt("find_property_known")
+                            .selected_text(statics::EN_LABEL_KNOWN_PROPERTIES)
+                            .show_ui(ui, |ui| {
+                                // Only worked out while the list is open.
+                                let keys = match &query.group {
+                                    Some(group) => save.group_property_keys(group),
+                                    None => save.all_property_keys(),
+                                };
+                                for (key, count) in keys {
+                                    if ui
+                                        .selectable_label(false, format!("{key} ({count})"))
+                                        .clicked()
+                                    {
+                                        picked = Some(key);
+                                    }
+                                }
+                            });
+                        if let Some(key) = picked {
+                            query.prop = key;
+                            find = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(statics::EN_COL_GROUP);
+                        let before = query.group.clone();
+                        egui::ComboBox::from_id_salt("find_property_group")
+                            .selected_text(
+                                query
+                                    .group
+                                    .as_deref()
+                                    .map_or(statics::EN_ALL_GROUPS, LoadedSave::group_display_name),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut query.group, None, statics::EN_ALL_GROUPS);
+                                for group in &save.index.groups {
+                                    ui.selectable_value(
+                                        &mut query.group,
+                                        Some(group.clone()),
+                                        LoadedSave::group_display_name(group),
+                                    );
+                                }
+                            });
+                        if query.group != before && query.results.is_some() {
+                            find = true;
+                        }
+                        if ui
+                            .add_enabled(
+                                !query.prop.trim().is_empty(),
+                                egui::Button::new(statics::EN_BTN_FIND),
+                            )
+                            .clicked()
+                        {
+                            find = true;
+                        }
+                    });
+                    if find && !query.prop.trim().is_empty() {
+                        query.results = Some(
+                            save.objects_with_property(query.group.as_deref(), query.prop.trim()),
+                        );
+                    }
+                    ui.separator();
+
+                    let Some(results) = &query.results else {
+                        ui.label(statics::EN_FIND_PROPERTY_ENTER);
+                        return;
+                    };
+                    if results.is_empty() {
+                        ui.label(statics::EN_SEARCH_NO_MATCHES);
+                        return;
+                    }
+                    ui.label(format!(
+                        "{} {}",
+                        results.len(),
+                        statics::EN_LABEL_FIND_PROPERTY_FOUND
+                    ));
+                    let prop = query.prop.trim();
+                    let names = &save.index.id_to_display_name;
+                    let row_h = ui.text_style_height(&egui::TextStyle::Body) + 6.0;
+                    ui.push_id("find_property_table", |ui| {
+                        TableBuilder::new(ui)
+                            .striped(true)
+                            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                            .column(Column::initial(40.0).resizable(false))
+                            .column(Column::initial(160.0).resizable(true))
+                            .column(Column::initial(80.0).resizable(true))
+                            .column(Column::initial(160.0).resizable(true))
+                            .column(Column::remainder().resizable(true))
+                            .header(row_h, |mut header| {
+                                for title in [
+                                    "",
+                                    statics::EN_COL_GROUP,
+                                    statics::EN_COL_ID,
+                                    statics::EN_COL_NAME,
+                                    statics::EN_COL_VALUE,
+                                ] {
+                                    header.col(|ui| {
+                                        ui.strong(title);
+                                    });
+                                }
+                            })
+                            .body(|body| {
+                                body.rows(row_h, results.len(), |mut row| {
+                                    let (group, id) = &results[row.index()];
+                                    row.col(|ui| {
+                                        if ui.small_button(statics::EN_BTN_GO).clicked() {
+                                            go_to = Some((group.clone(), *id));
+                                        }
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(LoadedSave::group_display_name(group));
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(id.to_string());
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(names.get(id).map(String::as_str).unwrap_or(""));
+                                    });
+                                    row.col(|ui| {
+                                        // The object may have changed since the Find.
+                                        let value = save
+                                            .get_object_value(group, *id)
+                                            .and_then(|o| o.get(prop));
+                                        if let Some(value) = value {
+                                            ui.label(value_preview_refs(
+                                                value,
+                                                self.collapse_ref_types.then_some(names),
+                                            ));
+                                        }
+                                    });
+                                });
+                            });
+                    });
+                });
+            if let Some((group, id)) = go_to {
+                self.select_object_programmatic(&group, id, true, true);
+                self.pending_property = Some(query.prop.trim().to_string());
+            }
+            if open {
+                self.property_query = Some(query);
+            }
+        }
+
+        let mut match_key_order = None;
+        if let Some(mut input) = self.key_order_ref_input.take()
+            && let (Some(group), Some(object_id)) =
+                (self.selected_group.clone(), self.selected_object_id)
+        {
+            let mut open = true;
+            let mut cancelled = false;
+            egui::Window::new(statics::EN_WINDOW_MATCH_KEY_ORDER)
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "{} {object_id} {}",
+                        statics::EN_LABEL_MATCH_KEY_ORDER,
+                        statics::EN_LABEL_MATCH_KEY_ORDER_REF
+                    ));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut input)
+                            .hint_text(statics::EN_HINT_REFERENCE_ID),
+                    );
+                    let reference = input
+                        .trim()
+                        .parse::<i64>()
+                        .ok()
+                        .filter(|id| *id != object_id)
+                        .filter(|id| save.get_object_value(&group, *id).is_some());
+                    match reference {
+                        Some(id) => ui.label(format!(
+                            "{id}: {}",
+                            id_to_display_name
+                                .get(&id)
+                                .map(String::as_str)
+                                .unwrap_or(statics::EN_EMPTY)
+                        )),
+                        None => ui.weak(statics::EN_LABEL_NO_REFERENCE_IN_GROUP),
+                    };
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                reference.is_some(),
+                                egui::Button::new(statics::EN_BTN_APPLY),
+                            )
+                            .clicked()
+                        {
+                            match_key_order = reference;
+                        }
+                        if ui.button(statics::EN_BTN_CANCEL).clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            if open && !cancelled && match_key_order.is_none() {
+                self.key_order_ref_input = Some(input);
+            }
+        }
+
+        if let Some(group) = self.malformed_entries_group.clone() {
+            let mut open = true;
+            egui::Window::new(statics::EN_WINDOW_MALFORMED_ENTRIES)
+                .collapsible(false)
+                .open(&mut open)
+                .default_size([520.0, 420.0])
+                .show(ctx, |ui| {
+                    ui.strong(LoadedSave::group_display_name(&group));
+                    ui.separator();
+                    egui::ScrollArea::vertical()
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            let indices = malformed_by_group.get(&group).map(Vec::as_slice);
+                            for &idx in indices.unwrap_or_default() {
+                                ui.label(format!("{} {idx}", statics::EN_LABEL_ENTRY_INDEX));
+                                let mut text = save
+                                    .group_entry(&group, idx)
+                                    .map(TiValue::to_ti_save_pretty)
+                                    .unwrap_or_default();
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut text)
+                                        .font(egui::TextStyle::Monospace)
+                                        .desired_width(f32::INFINITY)
+                                        .interactive(false),
+                                );
+                                ui.separator();
+                            }
+                        });
+                });
+            if !open {
+                self.malformed_entries_group = None;
+            }
+        }
 
         if self.search_ref_browser_open {
             let mut open = self.search_ref_browser_open;
@@ -2987,6 +6642,30 @@ impl eframe::App for TiseApp {
                 .collapsible(false)
                 .open(&mut open)
                 .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(
+                            &mut self.search_ref_missing_mode,
+                            false,
+                            statics::EN_REF_MODE_OBJECTS,
+                        );
+                        if ui
+                            .selectable_value(
+                                &mut self.search_ref_missing_mode,
+                                true,
+                                statics::EN_REF_MODE_MISSING,
+                            )
+                            .on_hover_text(statics::EN_HINT_REF_MODE_MISSING)
+                            .clicked()
+                        {
+                            self.search_ref_missing_cache = None;
+                        }
+                    });
+                    ui.separator();
+                    if self.search_ref_missing_mode {
+                        self.render_missing_ref_targets(ui, &save);
+                        return;
+                    }
+
                     ui.horizontal(|ui| {
                         ui.label(statics::EN_LABEL_SEARCH);
                         let resp = ui.add(
@@ -2999,36 +6678,49 @@ impl eframe::App for TiseApp {
                         }
                         if ui.small_button(statics::EN_BTN_CLEAR).clicked() {
                             self.search_ref_browser_query.clear();
+                            self.search_ref_min_id.clear();
+                            self.search_ref_max_id.clear();
                         }
                     });
+                    ui.horizontal(|ui| {
+                        ui.label(statics::EN_LABEL_ID_RANGE);
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.search_ref_min_id)
+                                .hint_text(statics::EN_HINT_MIN)
+                                .desired_width(90.0),
+                        );
+                        ui.label(statics::EN_LABEL_ID_RANGE_TO);
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.search_ref_max_id)
+                                .hint_text(statics::EN_HINT_MAX)
+                                .desired_width(90.0),
+                        );
+                    });
                     ui.separator();
 
+                    let range = (
+                        self.search_ref_min_id.clone(),
+                        self.search_ref_max_id.clone(),
+                    );
+                    let min_id = Self::parse_id_bound(&range.0);
+                    let max_id = Self::parse_id_bound(&range.1);
+                    if min_id.is_err() || max_id.is_err() {
+                        ui.colored_label(egui::Color32::RED, statics::EN_ERR_INVALID_ID_RANGE);
+                    }
+
                     if self.search_ref_cache.is_none()
                         || self.search_ref_cache_query != self.search_ref_browser_query
+                        || self.search_ref_cache_range != range
                     {
-                        let query = self.search_ref_browser_query.trim();
-                        let query_lower = query.to_lowercase();
-
-                        let mut ids: Vec<i64> = id_to_display_name.keys().copied().collect();
-                        ids.sort_unstable();
-
-                        let filtered_ids: Vec<i64> = if query.is_empty() {
-                            ids
-                        } else {
-                            ids.into_iter()
-                                .filter(|id| {
-                                    let name = id_to_display_name
-                                        .get(id)
-                                        .map(String::as_str)
-                                        .unwrap_or(statics::EN_EMPTY);
-
-                                    id.to_string().contains(query)
-                                        || name.to_lowercase().contains(&query_lower)
-                                })
-                                .collect()
-                        };
+                        let filtered_ids = Self::filter_ref_ids(
+                            id_to_display_name,
+                            &self.search_ref_browser_query,
+                            min_id.unwrap_or(None),
+                            max_id.unwrap_or(None),
+                        );
                         self.search_ref_cache = Some(filtered_ids);
                         self.search_ref_cache_query = self.search_ref_browser_query.clone();
+                        self.search_ref_cache_range = range;
                     }
 
                     // To avoid borrow checker conflict, we clone the ids out of the cache.
@@ -3090,7 +6782,7 @@ impl eframe::App for TiseApp {
                                                                     ref_group, id, true, true,
                                                                 );
                                                             } else {
-                                                                self.last_error = Some(format!(
+                                                                self.set_error(format!(
                                                                     "Reference ID {id} not found"
                                                                 ));
                                                             }
@@ -3142,7 +6834,12 @@ impl eframe::App for TiseApp {
                         if !query.is_empty() {
                             // Cap results to keep the UI responsive on very large saves.
                             // Pass our local `save` reference directly.
-                            let mut hits = Self::compute_item_search_hits(&save, query, 5_000);
+                            let mut hits = Self::compute_item_search_hits(
+                                &save,
+                                query,
+                                5_000,
+                                self.collapse_ref_types,
+                            );
                             Self::sort_item_search_hits(
                                 &mut hits,
                                 self.search_items_sort_key,
@@ -3370,6 +7067,7 @@ impl eframe::App for TiseApp {
                                                         );
                                                         self.selected_property =
                                                             Some(hit.prop.clone());
+                                                        self.pending_property = None;
                                                         self.scroll_properties_to_selected = true;
                                                         self.scroll_align_center = true;
                                                         self.refresh_selected_property_from_save(
@@ -3434,12 +7132,11 @@ impl eframe::App for TiseApp {
                                         close_requested = true;
                                         self.last_error = None;
                                     } else {
-                                        self.last_error = Some(format!("ID {id} not found"));
+                                        self.set_error(format!("ID {id} not found"));
                                     }
                                 }
                                 Err(_) => {
-                                    self.last_error =
-                                        Some(statics::EN_ERR_INVALID_ID_INTEGER.to_string());
+                                    self.set_error(statics::EN_ERR_INVALID_ID_INTEGER.to_string());
                                 }
                             }
                         }
@@ -3458,6 +7155,7 @@ impl eframe::App for TiseApp {
         // The bottom status bar must be shown before side/central panels so it reserves
         // space across the full window width (otherwise it only spans the remaining
         // central area after left side panels are laid out).
+        let mut bump_current_id = false;
         egui::TopBottomPanel::bottom("bottom_status").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 let file_label = save
@@ -3472,6 +7170,43 @@ impl eframe::App for TiseApp {
                     ui.separator();
                     ui.label(format!("game id: {gid}"));
                 }
+                if let Some(version) = &game_version {
+                    ui.separator();
+                    ui.label(format!("{} {version}", statics::EN_LABEL_VERSION));
+                }
+                if let Some((current, max)) = current_id_too_low {
+                    ui.separator();
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        format!("{} {current} < {max}", statics::EN_WARN_CURRENT_ID_LOW),
+                    )
+                    .on_hover_text(statics::EN_WARN_CURRENT_ID_LOW_HINT);
+                    if ui.small_button(statics::EN_BTN_FIX).clicked() {
+                        bump_current_id = true;
+                    }
+                }
+                if let Some(date) = game_date {
+                    ui.separator();
+                    match days_in_campaign {
+                        Some(days) => {
+                            ui.label(format!("game date: {} (day {days})", date.format_day()))
+                        }
+                        None => ui.label(format!("game date: {}", date.format_day())),
+                    };
+                }
+                let malformed: usize = malformed_by_group.values().map(Vec::len).sum();
+                if malformed > 0 {
+                    ui.separator();
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        format!(
+                            "{} {malformed} {}",
+                            statics::EN_WARN_MALFORMED,
+                            statics::EN_WARN_MALFORMED_ENTRIES
+                        ),
+                    )
+                    .on_hover_text(statics::EN_HINT_MALFORMED_ENTRIES);
+                }
                 ui.separator();
                 ui.label(format!("groups: {}", groups.len()));
                 ui.separator();
@@ -3497,11 +7232,21 @@ impl eframe::App for TiseApp {
             });
         });
 
+        // Hidden panels leave navigation to Search and Go to ID.
         egui::SidePanel::left("groups_panel")
             .resizable(true)
             .default_width(280.0)
-            .show(ctx, |ui| {
+            .show_animated(ctx, self.settings.show_groups_panel, |ui| {
                 ui.heading(statics::EN_HEADING_GROUPS);
+                if ui
+                    .checkbox(
+                        &mut self.settings.hide_empty_groups,
+                        statics::EN_SETTING_HIDE_EMPTY_GROUPS,
+                    )
+                    .changed()
+                {
+                    self.save_settings();
+                }
                 ui.separator();
                 let row_h = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
                 ui.push_id("groups_scroll", |ui| {
@@ -3509,10 +7254,18 @@ impl eframe::App for TiseApp {
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
                             for group in &groups {
-                                let label = LoadedSave::group_display_name(group);
+                                let name = LoadedSave::group_display_name(group);
+                                let label = match malformed_by_group.get(group) {
+                                    Some(skipped) => format!(
+                                        "{name}  {} {}",
+                                        statics::EN_WARN_MALFORMED,
+                                        skipped.len()
+                                    ),
+                                    None => name.to_string(),
+                                };
                                 let selected =
                                     self.selected_group.as_deref() == Some(group.as_str());
-                                let resp = Self::selectable_row_left(ui, selected, label, row_h);
+                                let resp = Self::selectable_row_left(ui, selected, &label, row_h);
                                 if selected && self.scroll_groups_to_selected {
                                     let align = if self.scroll_align_center {
                                         egui::Align::Center
@@ -3541,7 +7294,7 @@ impl eframe::App for TiseApp {
         egui::SidePanel::left("objects_panel")
             .resizable(true)
             .default_width(360.0)
-            .show(ctx, |ui| {
+            .show_animated(ctx, self.settings.show_objects_panel, |ui| {
                 ui.heading(statics::EN_HEADING_OBJECTS);
                 ui.separator();
 
@@ -3557,27 +7310,129 @@ impl eframe::App for TiseApp {
                     return;
                 };
 
-                let mut objects: Vec<_> = objects_by_group
-                    .get(&group)
-                    .map(|v| v.iter().collect())
-                    .unwrap_or_default();
+                if let Some(skipped) = malformed_by_group.get(&group) {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            format!(
+                                "{} {} {}",
+                                statics::EN_WARN_MALFORMED,
+                                skipped.len(),
+                                statics::EN_WARN_MALFORMED_ENTRIES
+                            ),
+                        )
+                        .on_hover_text(statics::EN_HINT_MALFORMED_ENTRIES);
+                        if ui.small_button(statics::EN_BTN_SHOW).clicked() {
+                            self.malformed_entries_group = Some(group.clone());
+                        }
+                    });
+                    ui.separator();
+                }
+
+                let mut objects: Vec<_> = if group == statics::ROOT_GROUP {
+                    vec![&root_summary]
+                } else {
+                    objects_by_group
+                        .get(&group)
+                        .map(|v| v.iter().collect())
+                        .unwrap_or_default()
+                };
+
+                Self::sort_objects(&mut objects, self.sort_objects_by_id);
 
-                if self.sort_objects_by_id {
-                    objects.sort_by_key(|o| o.id);
-                } else {
-                    objects.sort_by_key(|o| o.display_name.to_lowercase());
-                }
+                ui.horizontal(|ui| {
+                    ui.label(statics::EN_LABEL_LIST_COLUMN)
+                        .on_hover_text(statics::EN_HINT_LIST_COLUMN);
+                    let current = self.object_list_columns.get(&group).cloned();
+                    let mut chosen = current.clone();
+                    egui::ComboBox::from_id_salt(("object_list_column", group.as_str()))
+                        .selected_text(current.as_deref().unwrap_or(statics::EN_LIST_COLUMN_NONE))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut chosen, None, statics::EN_LIST_COLUMN_NONE);
+                            // Only worked out while the list is open.
+                            for (key, count) in save.group_property_keys(&group) {
+                                let label = format!("{key} ({count})");
+                                ui.selectable_value(&mut chosen, Some(key), label);
+                            }
+                        });
+                    if chosen != current {
+                        match chosen {
+                            Some(prop) => self.object_list_columns.insert(group.clone(), prop),
+                            None => self.object_list_columns.remove(&group),
+                        };
+                    }
+                });
+                let column = self.object_list_columns.get(&group).cloned();
 
                 let row_h = ui.text_style_height(&egui::TextStyle::Body) + 4.0;
+
+                // First/Last buttons plus Home/End/PageUp/PageDown when no text field has focus.
+                let mut nav = None;
+                ui.horizontal(|ui| {
+                    if ui.small_button(statics::EN_BTN_FIRST).clicked() {
+                        nav = Some(ObjectNav::First);
+                    }
+                    if ui.small_button(statics::EN_BTN_LAST).clicked() {
+                        nav = Some(ObjectNav::Last);
+                    }
+                });
+                if !ctx.wants_keyboard_input() {
+                    ctx.input_mut(|i| {
+                        for (key, target) in [
+                            (egui::Key::Home, ObjectNav::First),
+                            (egui::Key::End, ObjectNav::Last),
+                            (egui::Key::PageUp, ObjectNav::PageUp),
+                            (egui::Key::PageDown, ObjectNav::PageDown),
+                        ] {
+                            if i.consume_key(egui::Modifiers::NONE, key) {
+                                nav = Some(target);
+                            }
+                        }
+                    });
+                }
+                if let Some(nav) = nav {
+                    let ids: Vec<i64> = objects.iter().map(|o| o.id).collect();
+                    let page = ((ui.available_height() / row_h) as usize).max(1);
+                    if let Some(id) =
+                        Self::object_nav_target(&ids, self.selected_object_id, nav, page)
+                    {
+                        self.select_object_internal(&group, id, true, true, false);
+                    }
+                }
                 ui.push_id("objects_scroll", |ui| {
                     egui::ScrollArea::vertical()
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
                             for obj in objects {
                                 let selected = self.selected_object_id == Some(obj.id);
-                                let text = format!("{}: {}", obj.id, obj.display_name);
-                                let resp =
+                                let note = self.notes.get(obj.id);
+                                let mut text = match note {
+                                    Some(_) => format!(
+                                        "{} {}: {}",
+                                        statics::EN_GLYPH_NOTE,
+                                        obj.id,
+                                        obj.display_name
+                                    ),
+                                    None => format!("{}: {}", obj.id, obj.display_name),
+                                };
+                                if let Some(prop) = &column {
+                                    let value = save
+                                        .get_object_value(&group, obj.id)
+                                        .and_then(|o| o.get(prop));
+                                    let shown = match value {
+                                        Some(v) => match TiDate::from_value(v) {
+                                            Some(date) => date.format_day(),
+                                            None => value_preview_refs(v, Some(id_to_display_name)),
+                                        },
+                                        None => statics::EN_LITERAL_MISSING.to_string(),
+                                    };
+                                    text = format!("{text} | {shown}");
+                                }
+                                let mut resp =
                                     Self::selectable_row_left(ui, selected, text.as_str(), row_h);
+                                if let Some(note) = note {
+                                    resp = resp.on_hover_text(note);
+                                }
                                 if selected && self.scroll_objects_to_selected {
                                     let align = if self.scroll_align_center {
                                         egui::Align::Center
@@ -3596,6 +7451,23 @@ impl eframe::App for TiseApp {
                 });
             });
 
+        let mut copy_from_other = None;
+        if let Some(other) = self.other_save.take() {
+            let mut keep_open = true;
+            egui::SidePanel::right("other_save_panel")
+                .resizable(true)
+                .default_width(360.0)
+                .show(ctx, |ui| {
+                    keep_open =
+                        self.render_other_save_panel(ui, &save, &other, &mut copy_from_other);
+                });
+            if keep_open {
+                self.other_save = Some(other);
+            }
+        }
+
+        // Selections made in the side panels this frame; the central panel's own are picked up below.
+        self.apply_pending_property(&save);
         egui::CentralPanel::default().show(ctx, |ui| {
             let Some(group) = self.selected_group.clone() else {
                 ui.label(statics::EN_SELECT_GROUP_LEFT);
@@ -3606,7 +7478,9 @@ impl eframe::App for TiseApp {
                 return;
             };
 
-            let value_obj = save.get_object_value(&group, object_id).cloned();
+            let value_obj = save
+                .object_properties(&group, object_id)
+                .map(std::borrow::Cow::into_owned);
             let Some(value_obj) = value_obj else {
                 ui.colored_label(egui::Color32::RED, statics::EN_ERR_OBJECT_VALUE_MISSING);
                 return;
@@ -3615,14 +7489,53 @@ impl eframe::App for TiseApp {
             ui.horizontal(|ui| {
                 ui.heading(LoadedSave::group_display_name(&group));
                 ui.separator();
+                if ui
+                    .small_button(statics::EN_BTN_PREV_OBJECT)
+                    .on_hover_text(statics::EN_HINT_PREV_OBJECT)
+                    .clicked()
+                {
+                    step_object = Some(ObjectNav::Prev);
+                }
                 ui.label(format!("ID {object_id}"));
+                if ui
+                    .small_button(statics::EN_BTN_NEXT_OBJECT)
+                    .on_hover_text(statics::EN_HINT_NEXT_OBJECT)
+                    .clicked()
+                {
+                    step_object = Some(ObjectNav::Next);
+                }
+                if group != statics::ROOT_GROUP
+                    && ui
+                        .small_button(statics::EN_BTN_MATCH_KEY_ORDER)
+                        .on_hover_text(statics::EN_HINT_MATCH_KEY_ORDER)
+                        .clicked()
+                {
+                    self.key_order_ref_input = Some(String::new());
+                }
+                if group != statics::ROOT_GROUP
+                    && ui
+                        .small_button(statics::EN_BTN_COPY_ENTRY)
+                        .on_hover_text(statics::EN_HINT_COPY_ENTRY)
+                        .clicked()
+                {
+                    self.copy_object_entry(ui.ctx(), &save, &group, object_id);
+                }
                 if dirty {
                     ui.separator();
                     ui.colored_label(egui::Color32::YELLOW, statics::EN_BADGE_MODIFIED);
                 }
             });
+            self.render_notes_editor(ui, &save, object_id);
+            if group != statics::ROOT_GROUP {
+                self.render_add_property(ui, &mut save, &group, object_id, &value_obj);
+            }
             ui.separator();
 
+            if group == statics::TI_GROUP_COUNCILOR_STATE {
+                self.render_councilor_editor(ui, object_id, &value_obj, &mut save);
+                ui.separator();
+            }
+
             let mut properties: Vec<_> = value_obj.iter().collect();
             properties.sort_by_key(|(k, _)| (*k).to_lowercase());
 
@@ -3637,28 +7550,51 @@ impl eframe::App for TiseApp {
                 .max_height(max_h)
                 .resizable(true)
                 .show(ui, |ui| {
-                    self.render_properties_panel(
-                        ui,
-                        &properties,
-                        &value_obj,
-                        &save.index.id_lookup,
-                        &save.index.id_to_display_name,
-                    );
+                    self.render_properties_panel(ui, &properties, &value_obj, &save);
                 });
 
             ui.separator();
             self.render_editor_panel(ui, &value_obj, &mut save);
         });
 
+        if let Some(nav) = step_object {
+            self.step_selected_object(&save, nav);
+        }
+        self.apply_pending_property(&save);
+        if bump_current_id {
+            self.bump_current_id(&mut save);
+        }
+        if let Some((group, object_id)) = copy_from_other {
+            self.copy_object_from_other(&mut save, &group, object_id);
+        }
+        if !zero_non_finite.is_empty() {
+            self.zero_non_finite_numbers(&mut save, &zero_non_finite);
+        }
+        if let Some(reference_id) = match_key_order {
+            self.match_key_order(&mut save, reference_id);
+        }
+        if std::mem::take(&mut self.validate_pending)
+            && self.settings.validate_after_edit
+            && let Err(e) = save.check_round_trip()
+        {
+            self.set_error(format!("{}: {e:#}", statics::EN_ERR_VALIDATE_AFTER_EDIT));
+        }
+
         self.save = Some(save);
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.flush_notes();
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::TiseApp;
-    use super::{ItemSearchHit, ItemSortKey};
-    use crate::{TiValue, value::TiNumber};
+    use super::{
+        EditAction, ItemSearchHit, ItemSortKey, ListOp, ObjectNav, group_changes_by_object,
+    };
+    use crate::{TiValue, statics, value::TiNumber};
     use indexmap::IndexMap;
 
     #[test]
@@ -3675,6 +7611,864 @@ mod tests {
         ]));
     }
 
+    #[test]
+    fn apply_list_op_duplicate_inserts_deep_copy_after_element() {
+        let mut inner = IndexMap::new();
+        inner.insert("a".to_string(), TiValue::Number(TiNumber::I64(1)));
+        let mut arr = vec![TiValue::Null, TiValue::Object(inner)];
+
+        assert!(TiseApp::apply_list_op(
+            &mut arr,
+            ListOp::Duplicate(1),
+            TiValue::Null
+        ));
+        assert_eq!(arr.len(), 3);
+        assert_eq!(arr[1], arr[2]);
+
+        // Editing the copy leaves the original untouched.
+        if let Some(map) = arr[2].as_object_mut() {
+            map.insert("a".to_string(), TiValue::Number(TiNumber::I64(2)));
+        }
+        assert_ne!(arr[1], arr[2]);
+
+        assert!(!TiseApp::apply_list_op(
+            &mut arr,
+            ListOp::Duplicate(3),
+            TiValue::Null
+        ));
+        assert!(!TiseApp::apply_list_op(
+            &mut arr,
+            ListOp::MoveUp(0),
+            TiValue::Null
+        ));
+    }
+
+    #[test]
+    fn apply_list_op_move_places_element_at_target_index() {
+        let num = |n| TiValue::Number(TiNumber::I64(n));
+        let apply = |arr: &mut Vec<TiValue>, op| TiseApp::apply_list_op(arr, op, TiValue::Null);
+        let mut arr = vec![num(0), num(1), num(2), num(3)];
+
+        assert!(apply(&mut arr, ListOp::Move { from: 0, to: 2 }));
+        assert_eq!(arr, vec![num(1), num(2), num(0), num(3)]);
+
+        assert!(apply(&mut arr, ListOp::Move { from: 3, to: 0 }));
+        assert_eq!(arr, vec![num(3), num(1), num(2), num(0)]);
+
+        assert!(!apply(&mut arr, ListOp::Move { from: 1, to: 1 }));
+        assert!(!apply(&mut arr, ListOp::Move { from: 1, to: 4 }));
+    }
+
+    #[test]
+    fn dropping_a_row_lands_it_above_the_target_row() {
+        let num = |n| TiValue::Number(TiNumber::I64(n));
+        let drop = |from, before| {
+            let mut arr = vec![num(0), num(1), num(2), num(3)];
+            TiseApp::apply_list_op(
+                &mut arr,
+                TiseApp::list_move_before(from, before),
+                TiValue::Null,
+            );
+            arr
+        };
+
+        // Downward: row 0 dropped above row 2 ends up between 1 and 2.
+        assert_eq!(drop(0, 2), vec![num(1), num(0), num(2), num(3)]);
+        assert_eq!(drop(1, 3), vec![num(0), num(2), num(1), num(3)]);
+        // Upward.
+        assert_eq!(drop(3, 1), vec![num(0), num(3), num(1), num(2)]);
+        // Above itself or the row below it: no move.
+        assert_eq!(drop(1, 2), vec![num(0), num(1), num(2), num(3)]);
+    }
+
+    #[test]
+    fn object_nav_target_clamps_and_pages_from_selection() {
+        let ids = [10, 20, 30, 40, 50];
+        let nav = |cur, nav| TiseApp::object_nav_target(&ids, cur, nav, 2);
+
+        assert_eq!(nav(Some(30), ObjectNav::First), Some(10));
+        assert_eq!(nav(Some(30), ObjectNav::Last), Some(50));
+        assert_eq!(nav(Some(20), ObjectNav::PageDown), Some(40));
+        assert_eq!(nav(Some(40), ObjectNav::PageDown), Some(50));
+        assert_eq!(nav(Some(20), ObjectNav::PageUp), Some(10));
+        assert_eq!(nav(None, ObjectNav::PageDown), Some(10));
+        assert_eq!(nav(Some(999), ObjectNav::PageUp), Some(10));
+        assert_eq!(nav(Some(30), ObjectNav::Next), Some(40));
+        assert_eq!(nav(Some(50), ObjectNav::Next), Some(50));
+        assert_eq!(nav(Some(30), ObjectNav::Prev), Some(20));
+        assert_eq!(nav(Some(10), ObjectNav::Prev), Some(10));
+        assert_eq!(
+            TiseApp::object_nav_target(&[], None, ObjectNav::First, 2),
+            None
+        );
+    }
+
+    #[test]
+    fn filter_ref_ids_combines_numeric_range_and_query() {
+        let names: std::collections::HashMap<i64, String> = [
+            (4, "Alpha"),
+            (40, "Beta"),
+            (104, "Alpha Two"),
+            (200, "Gamma"),
+        ]
+        .into_iter()
+        .map(|(id, n)| (id, n.to_string()))
+        .collect();
+
+        assert_eq!(
+            TiseApp::filter_ref_ids(&names, "", Some(10), Some(150)),
+            vec![40, 104]
+        );
+        assert_eq!(
+            TiseApp::filter_ref_ids(&names, "alpha", Some(10), None),
+            vec![104]
+        );
+        assert_eq!(
+            TiseApp::filter_ref_ids(&names, "4", None, None),
+            vec![4, 40, 104]
+        );
+        assert_eq!(TiseApp::parse_id_bound("  "), Ok(None));
+        assert_eq!(TiseApp::parse_id_bound(" 12 "), Ok(Some(12)));
+        assert!(TiseApp::parse_id_bound("x").is_err());
+    }
+
+    #[test]
+    fn action_matches_filter_checks_id_prop_and_description() {
+        let action = EditAction {
+            group: "G".to_string(),
+            object_id: 1234,
+            prop: "loyalty".to_string(),
+            before: None,
+            after: None,
+            description: "Set Councilor Attribute".to_string(),
+            batch: None,
+        };
+        assert!(TiseApp::action_matches_filter(&action, "  "));
+        assert!(TiseApp::action_matches_filter(&action, "23"));
+        assert!(TiseApp::action_matches_filter(&action, "LOYAL"));
+        assert!(TiseApp::action_matches_filter(&action, "councilor"));
+        assert!(!TiseApp::action_matches_filter(&action, "persuasion"));
+    }
+
+    #[test]
+    fn group_changes_by_object_keeps_first_change_order() {
+        let action = |group: &str, object_id, prop: &str| EditAction {
+            group: group.to_string(),
+            object_id,
+            prop: prop.to_string(),
+            before: None,
+            after: None,
+            description: String::new(),
+            batch: None,
+        };
+        let stack = [
+            action("G", 7, "a"),
+            action("G", 3, "b"),
+            action("G", 7, "c"),
+            action("H", 7, "d"),
+        ];
+        let grouped = group_changes_by_object(stack.iter().enumerate().collect());
+        let summary: Vec<_> = grouped
+            .iter()
+            .map(|((g, id), list)| (g.as_str(), *id, list.iter().map(|(i, _)| *i).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("G", 7, vec![0, 2]), ("G", 3, vec![1]), ("H", 7, vec![3])]
+        );
+    }
+
+    #[test]
+    fn float_full_expansion_shows_exact_stored_value() {
+        let f = |v: f64| super::float_full_expansion(&TiValue::Number(TiNumber::F64(v)));
+        assert_eq!(
+            f(0.1 + 0.2).as_deref(),
+            Some("0.3000000000000000444089209850062616169452667236328125")
+        );
+        assert_eq!(f(2.5).as_deref(), Some("2.5"));
+        assert_eq!(f(-3.0).as_deref(), Some("-3.0"));
+        assert_eq!(f(f64::NAN), None);
+        assert_eq!(
+            super::float_full_expansion(&TiValue::Number(TiNumber::I64(1))),
+            None
+        );
+    }
+
+    #[test]
+    fn redo_all_edits_an_object_added_earlier_in_the_sequence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        std::fs::write(
+            &path,
+            r#"{ currentID: { value: 1 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+                { Key: { value: 1 }, Value: { x: 1 } },
+            ] } }"#,
+        )
+        .unwrap();
+        let group = "PavonisInteractive.TerraInvicta.TITest";
+        let mut app = TiseApp {
+            save: Some(crate::LoadedSave::load_path(&path).unwrap()),
+            ..TiseApp::default()
+        };
+        let copy = TiValue::parse_json5("{ x: 1 }").unwrap();
+        let save = app.save.as_mut().unwrap();
+        save.insert_object(group, 2, copy.as_object().unwrap().clone())
+            .unwrap();
+        save.set_property(group, 2, "x", TiValue::Number(TiNumber::I64(5)))
+            .unwrap();
+        save.rebuild_index();
+        app.record_action(EditAction {
+            group: group.to_string(),
+            object_id: 2,
+            prop: String::new(),
+            before: None,
+            after: Some(copy),
+            description: String::new(),
+            batch: None,
+        });
+        app.record_action(EditAction {
+            group: group.to_string(),
+            object_id: 2,
+            prop: "x".to_string(),
+            before: Some(TiValue::Number(TiNumber::I64(1))),
+            after: Some(TiValue::Number(TiNumber::I64(5))),
+            description: String::new(),
+            batch: None,
+        });
+        let x = |app: &TiseApp| {
+            app.save
+                .as_ref()
+                .unwrap()
+                .get_object_value(group, 2)
+                .map(|o| o["x"].to_json5_compact())
+        };
+
+        app.step_history(false, true);
+        assert_eq!(x(&app), None);
+        app.step_history(true, true);
+        assert_eq!(app.last_error, None);
+        assert_eq!(x(&app).as_deref(), Some("5"));
+        assert!(app.redo_stack.is_empty());
+    }
+
+    #[test]
+    fn navigating_to_a_property_overrides_the_sticky_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        std::fs::write(
+            &path,
+            r#"{ currentID: { value: 2 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+                { Key: { value: 1 }, Value: { a: 1, b: 2 } },
+                { Key: { value: 2 }, Value: { a: 3, b: 4 } },
+            ] } }"#,
+        )
+        .unwrap();
+        let group = "PavonisInteractive.TerraInvicta.TITest";
+        let save = crate::LoadedSave::load_path(&path).unwrap();
+        let mut app = TiseApp {
+            settings: crate::settings::Settings {
+                sticky_property: true,
+                ..Default::default()
+            },
+            selected_group: Some(group.to_string()),
+            selected_object_id: Some(1),
+            selected_property: Some("a".to_string()),
+            ..TiseApp::default()
+        };
+        let action = EditAction {
+            group: group.to_string(),
+            object_id: 2,
+            prop: "b".to_string(),
+            before: None,
+            after: None,
+            description: String::new(),
+            batch: None,
+        };
+        app.navigate_to_action_target(&save, &action);
+        app.apply_pending_property(&save);
+        assert_eq!(app.selected_object_id, Some(2));
+        assert_eq!(app.selected_property.as_deref(), Some("b"));
+
+        // Plain navigation still carries the property over.
+        app.select_object_programmatic(group, 1, false, false);
+        app.apply_pending_property(&save);
+        assert_eq!(app.selected_property.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn undoing_a_delete_clears_selection_and_keeps_later_objects_resolved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        std::fs::write(
+            &path,
+            r#"{ currentID: { value: 3 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+                { Key: { value: 1 }, Value: { displayName: "first" } },
+                { Key: { value: 2 }, Value: { displayName: "second" } },
+            ] } }"#,
+        )
+        .unwrap();
+        let group = "PavonisInteractive.TerraInvicta.TITest";
+        let mut app = TiseApp {
+            save: Some(crate::LoadedSave::load_path(&path).unwrap()),
+            ..TiseApp::default()
+        };
+
+        // Record deleting the earlier object, then select the later one.
+        let save = app.save.as_mut().unwrap();
+        let removed = save.remove_object(group, 1).unwrap();
+        save.rebuild_index();
+        app.record_action(EditAction {
+            group: group.to_string(),
+            object_id: 1,
+            prop: String::new(),
+            before: Some(TiValue::Object(removed)),
+            after: None,
+            description: String::new(),
+            batch: None,
+        });
+        app.select_object_programmatic(group, 2, false, false);
+        let name = |app: &TiseApp| {
+            let save = app.save.as_ref().unwrap();
+            save.get_object_value(group, app.selected_object_id?)?
+                .get("displayName")?
+                .as_str()
+                .map(str::to_string)
+        };
+        assert_eq!(name(&app).as_deref(), Some("second"));
+
+        // Undo re-adds it (at the end) and selects it; redo deletes it again.
+        app.undo();
+        assert_eq!(app.selected_object_id, Some(1));
+        assert_eq!(name(&app).as_deref(), Some("first"));
+        app.redo();
+        assert_eq!(app.selected_object_id, None);
+        assert_eq!(app.selected_group.as_deref(), Some(group));
+
+        app.select_object_programmatic(group, 2, false, false);
+        assert_eq!(name(&app).as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn hiding_empty_groups_keeps_selected_and_malformed_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        std::fs::write(
+            &path,
+            r#"{ currentID: { value: 1 }, gamestates: {
+                Full: [{ Key: { value: 1 }, Value: {} }], Empty: [], Picked: [], Broken: [7],
+            } }"#,
+        )
+        .unwrap();
+        let save = crate::LoadedSave::load_path(&path).unwrap();
+        let mut groups: Vec<String> = ["Full", "Empty", "Picked", "Broken"]
+            .map(String::from)
+            .to_vec();
+        TiseApp::retain_nonempty_groups(&mut groups, &save, Some("Picked"));
+        assert_eq!(groups, ["Full", "Picked", "Broken"]);
+    }
+
+    #[test]
+    fn load_summary_names_format_version_and_date() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Autosave.json");
+        std::fs::write(
+            &path,
+            r#"{ gamestates: {
+                "PavonisInteractive.TerraInvicta.TIGlobalValuesState": [
+                    { Key: { value: 1 }, Value: { latestSaveVersion: "0.4.78" } },
+                ],
+                "PavonisInteractive.TerraInvicta.TIMetadataState": [
+                    { Key: { value: 2 }, Value: { gameTimeString: "16/04/2031 12:00:00" } },
+                ],
+            } }"#,
+        )
+        .unwrap();
+        let save = crate::LoadedSave::load_path(&path).unwrap();
+        assert_eq!(
+            TiseApp::load_summary(&save, &path),
+            "Opened Autosave.json as plain JSON | game version 0.4.78 | game date 2031-04-16"
+        );
+
+        std::fs::write(&path, "{ gamestates: {} }").unwrap();
+        let save = crate::LoadedSave::load_path(&path).unwrap();
+        assert!(
+            TiseApp::load_summary(&save, &path)
+                .ends_with("game version unknown | game date unknown")
+        );
+    }
+
+    #[test]
+    fn note_edits_are_written_when_the_selection_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let save_path = dir.path().join("save.json");
+        let sidecar = crate::notes::ObjectNotes::path_for(&save_path);
+        let mut app = TiseApp {
+            selected_object_id: Some(1),
+            ..TiseApp::default()
+        };
+        app.notes.set(1, "saboteur".to_string());
+        app.notes_unsaved = Some(save_path.clone());
+
+        // Re-selecting the same object keeps the edit pending.
+        app.select_object_internal("G", 1, false, false, false);
+        assert!(!sidecar.exists());
+
+        app.select_object_internal("G", 2, false, false, false);
+        assert!(app.notes_unsaved.is_none());
+        let loaded = crate::notes::ObjectNotes::load_for(&save_path).unwrap();
+        assert_eq!(loaded.get(1), Some("saboteur"));
+    }
+
+    #[test]
+    fn message_log_records_each_message_and_stays_bounded() {
+        let mut app = TiseApp::default();
+        app.set_status("Loaded".to_string());
+        app.set_error("bad".to_string());
+        app.set_error("bad".to_string());
+        app.set_status("Loaded".to_string());
+        assert_eq!(app.last_error.as_deref(), Some("bad"));
+        let texts: Vec<(bool, &str)> = app
+            .message_log
+            .iter()
+            .map(|m| (m.error, m.text.as_str()))
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                (false, "Loaded"),
+                (true, "bad"),
+                (true, "bad"),
+                (false, "Loaded")
+            ]
+        );
+
+        for i in 0..super::MESSAGE_LOG_LIMIT {
+            app.set_status(i.to_string());
+        }
+        assert_eq!(app.message_log.len(), super::MESSAGE_LOG_LIMIT);
+        assert_eq!(app.message_log.front().unwrap().text, "0");
+    }
+
+    #[test]
+    fn key_order_match_undoes_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        std::fs::write(
+            &path,
+            r#"{ currentID: { value: 3 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+                { Key: { value: 1 }, Value: { b: 1, a: 2 } },
+                { Key: { value: 2 }, Value: { a: 1, b: 2 } },
+                { Key: { value: 3 }, Value: { a: 1, b: 2 } },
+            ] } }"#,
+        )
+        .unwrap();
+        let group = "PavonisInteractive.TerraInvicta.TITest";
+        let mut save = crate::LoadedSave::load_path(&path).unwrap();
+        let mut app = TiseApp::default();
+        app.select_object_programmatic(group, 1, false, false);
+        app.match_key_order(&mut save, 2);
+        app.save = Some(save);
+
+        let state = |app: &TiseApp| {
+            let save = app.save.as_ref().unwrap();
+            let keys: Vec<String> = save
+                .get_object_value(group, 1)
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect();
+            (keys, save.index.id_lookup.get(&1).map(|(_, idx)| *idx))
+        };
+        assert_eq!(state(&app), (vec!["a".into(), "b".into()], Some(0)));
+        app.undo();
+        assert_eq!(state(&app), (vec!["b".into(), "a".into()], Some(0)));
+        app.redo();
+        assert_eq!(state(&app), (vec!["a".into(), "b".into()], Some(0)));
+    }
+
+    #[test]
+    fn add_property_adds_the_value_as_an_undoable_edit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        std::fs::write(
+            &path,
+            r#"{ gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+                { Key: { value: 1 }, Value: { a: 1 } },
+            ] } }"#,
+        )
+        .unwrap();
+        let group = "PavonisInteractive.TerraInvicta.TITest";
+        let mut save = crate::LoadedSave::load_path(&path).unwrap();
+        let mut app = TiseApp::default();
+        app.select_object_programmatic(group, 1, false, false);
+        let zero = TiseApp::coerce_value_to_type(statics::EN_TYPE_I64, &TiValue::Null);
+        app.add_property(&mut save, group, 1, "b", zero.clone());
+        assert_eq!(app.selected_property.as_deref(), Some("b"));
+        assert_eq!(
+            save.get_object_value(group, 1).unwrap().get("b"),
+            Some(&zero)
+        );
+
+        app.add_property(&mut save, group, 1, "a", TiValue::Null);
+        assert!(app.last_error.is_some());
+        assert_eq!(app.undo_stack.len(), 1);
+
+        app.save = Some(save);
+        app.undo();
+        let keys: Vec<&String> = app
+            .save
+            .as_ref()
+            .unwrap()
+            .get_object_value(group, 1)
+            .unwrap()
+            .keys()
+            .collect();
+        assert_eq!(keys, ["a"]);
+    }
+
+    #[test]
+    fn fixing_current_id_is_undoable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        std::fs::write(
+            &path,
+            r#"{ currentID: { value: 1 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+                { Key: { value: 3 }, Value: { a: 1 } },
+            ] } }"#,
+        )
+        .unwrap();
+        let mut save = crate::LoadedSave::load_path(&path).unwrap();
+        let mut app = TiseApp::default();
+        app.bump_current_id(&mut save);
+        assert_eq!(save.game_id(), Some(3));
+        assert_eq!(app.undo_stack.len(), 1);
+
+        app.save = Some(save);
+        app.undo();
+        let save = app.save.as_ref().unwrap();
+        assert_eq!(save.game_id(), Some(1));
+    }
+
+    #[test]
+    fn take_history_step_takes_whole_trailing_batch() {
+        let action = |prop: &str, batch| EditAction {
+            group: "G".to_string(),
+            object_id: 1,
+            prop: prop.to_string(),
+            before: None,
+            after: None,
+            description: String::new(),
+            batch,
+        };
+        let mut stack = vec![
+            action("a", None),
+            action("b", Some(1)),
+            action("c", Some(2)),
+            action("d", Some(2)),
+        ];
+
+        let step = TiseApp::take_history_step(&mut stack);
+        assert_eq!(
+            step.iter().map(|a| a.prop.as_str()).collect::<Vec<_>>(),
+            ["c", "d"]
+        );
+        assert_eq!(TiseApp::take_history_step(&mut stack).len(), 1);
+        assert_eq!(TiseApp::take_history_step(&mut stack)[0].prop, "a");
+        assert!(TiseApp::take_history_step(&mut stack).is_empty());
+    }
+
+    #[test]
+    fn take_all_history_steps_keeps_redo_order() {
+        let action = |prop: &str, batch| EditAction {
+            group: "G".to_string(),
+            object_id: 1,
+            prop: prop.to_string(),
+            before: None,
+            after: None,
+            description: String::new(),
+            batch,
+        };
+        let mut undo = vec![
+            action("a", None),
+            action("b", Some(1)),
+            action("c", Some(1)),
+        ];
+        let mut redo = Vec::new();
+
+        let steps = TiseApp::take_history_steps(&mut undo, true);
+        assert!(undo.is_empty());
+        assert_eq!(steps.len(), 2);
+        redo.extend(steps.into_iter().flatten());
+
+        // Redoing one step at a time must replay "a" first, then the batch.
+        let next = TiseApp::take_history_steps(&mut redo, false);
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0][0].prop, "a");
+        let next = TiseApp::take_history_step(&mut redo);
+        assert_eq!(
+            next.iter().map(|a| a.prop.as_str()).collect::<Vec<_>>(),
+            ["b", "c"]
+        );
+    }
+
+    #[test]
+    fn edit_log_has_readable_lines_and_exact_records() {
+        let actions = vec![
+            EditAction {
+                group: "G".to_string(),
+                object_id: 5,
+                prop: "name".to_string(),
+                before: Some(TiValue::String("old".to_string())),
+                after: None,
+                description: "Removed name".to_string(),
+                batch: None,
+            },
+            EditAction {
+                group: "G".to_string(),
+                object_id: 6,
+                prop: String::new(),
+                before: Some(TiValue::parse_json5("{ a: 1 }").unwrap()),
+                after: None,
+                description: "Deleted 6".to_string(),
+                batch: None,
+            },
+        ];
+        let log = TiseApp::format_edit_log(&actions);
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(
+            &lines[1..],
+            [
+                "",
+                "# 1. Removed name",
+                &format!("# 5.name: old -> {}", statics::EN_LOG_ABSENT),
+                r#"{"group":"G", "id":5, "prop":"name", "before":"old"}"#,
+                "",
+                "# 2. Deleted 6",
+                &format!("# 6: {}", statics::EN_LOG_NOT_REPLAYABLE),
+            ]
+        );
+    }
+
+    #[test]
+    fn councilor_form_reports_only_changed_properties() {
+        let obj = TiValue::parse_json5(
+            r#"{ personalName: "Matt", familyName: "Price", displayName: "Matt Price",
+                attributes: { Persuasion: 5, Loyalty: 7, Extra: 1.5 },
+                traitTemplateNames: ["Government", "Expert"] }"#,
+        )
+        .unwrap();
+        let obj = obj.as_object().unwrap();
+
+        let mut form = TiseApp::councilor_form(3862, obj);
+        assert_eq!(
+            form.attributes,
+            [("Persuasion".to_string(), 5), ("Loyalty".to_string(), 7)]
+        );
+        assert_eq!(form.traits, ["Government", "Expert"]);
+        assert!(TiseApp::councilor_form_changes(&form, obj).is_empty());
+
+        form.family_name = "Smith".to_string();
+        form.attributes[1].1 = 9;
+        form.traits.remove(0);
+        let changes: Vec<(&str, String)> = TiseApp::councilor_form_changes(&form, obj)
+            .into_iter()
+            .map(|(p, v)| (p, v.to_json5_compact()))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                (statics::TI_PROP_FAMILY_NAME, r#""Smith""#.to_string()),
+                (
+                    statics::TI_PROP_ATTRIBUTES,
+                    r#"{"Persuasion":5, "Loyalty":9, "Extra":1.5}"#.to_string()
+                ),
+                (
+                    statics::TI_PROP_TRAIT_TEMPLATE_NAMES,
+                    r#"["Expert"]"#.to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn known_defaults_parse_and_match_group_and_property() {
+        for (group, prop, _) in statics::TI_FIELD_DEFAULTS {
+            assert!(TiseApp::known_default(group, prop).is_some(), "{prop}");
+        }
+        assert_eq!(
+            TiseApp::known_default(statics::TI_GROUP_COUNCILOR_STATE, "repeatOrder"),
+            Some(TiValue::Bool(false))
+        );
+        assert_eq!(
+            TiseApp::known_default(statics::TI_GROUP_NATION_STATE, "repeatOrder"),
+            None
+        );
+    }
+
+    #[test]
+    fn percent_edit_defaults_on_for_fraction_fields() {
+        assert!(TiseApp::percent_edit_default("bankingPercentage"));
+        assert!(!TiseApp::percent_edit_default("cohesion"));
+        assert!(!TiseApp::percent_edit_default("somethingElse"));
+    }
+
+    #[test]
+    fn unit_hints_cover_known_fields_only() {
+        assert_eq!(
+            TiseApp::unit_hint("autofailMissionsValue"),
+            Some(statics::EN_UNIT_FRACTION)
+        );
+        assert_eq!(
+            TiseApp::unit_hint("cohesion"),
+            Some(statics::EN_UNIT_SCALE_10)
+        );
+        assert_eq!(TiseApp::unit_hint("somethingElse"), None);
+        assert_eq!(
+            TiseApp::unit_range(TiseApp::unit_hint("bankingPercentage")),
+            0.0..=1.0
+        );
+        assert_eq!(
+            *TiseApp::unit_range(TiseApp::unit_hint("completionTime_days")).end(),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn ref_previews_hide_type_when_names_are_given() {
+        let r = TiValue::parse_json5(
+            r#"{ value: 2860, "$type": "PavonisInteractive.TerraInvicta.TIRegionState" }"#,
+        )
+        .unwrap();
+        let names = std::collections::HashMap::from([(2860, "Texas".to_string())]);
+        assert_eq!(super::value_preview_refs(&r, Some(&names)), "2860: Texas");
+        assert_eq!(
+            super::value_preview_refs(&r, Some(&std::collections::HashMap::new())),
+            "2860"
+        );
+        assert_eq!(
+            super::value_preview_refs(&r, None),
+            super::value_preview(&r)
+        );
+    }
+
+    #[test]
+    fn differing_props_includes_keys_missing_on_either_side() {
+        let a = TiValue::parse_json5("{ same: 1, changed: 2, onlyA: 3 }").unwrap();
+        let b = TiValue::parse_json5("{ same: 1, changed: 5, onlyB: 4 }").unwrap();
+        let mut diff: Vec<String> =
+            TiseApp::differing_props(a.as_object().unwrap(), b.as_object().unwrap())
+                .into_iter()
+                .collect();
+        diff.sort();
+        assert_eq!(diff, ["changed", "onlyA", "onlyB"]);
+    }
+
+    #[test]
+    fn list_insert_types_produce_zero_values() {
+        let values: Vec<String> = super::LIST_INSERT_TYPES
+            .iter()
+            .map(|t| TiseApp::coerce_value_to_type(t, &TiValue::Null).to_json5_compact())
+            .collect();
+        assert_eq!(values, ["null", "0", "0.0", "\"\"", "false"]);
+    }
+
+    #[test]
+    fn change_type_data_loss_flags_only_lossy_conversions() {
+        let parse = |t: &str| TiValue::parse_json5(t).unwrap();
+        let loss = |label: &str, src: &TiValue| {
+            let converted = TiseApp::coerce_value_to_type(label, src);
+            TiseApp::change_type_data_loss(label, src, &converted)
+        };
+
+        assert!(loss(statics::EN_TYPE_STRING, &parse("{ a: 1 }")).is_some());
+        assert!(loss(statics::EN_TYPE_OBJECT, &parse("[1, 2]")).is_some());
+        assert!(loss(statics::EN_TYPE_ARRAY, &parse("[1, 2]")).is_none());
+        assert!(loss(statics::EN_TYPE_STRING, &parse("{}")).is_none());
+        assert!(loss(statics::EN_TYPE_REFERENCE, &parse("{ value: 5 }")).is_none());
+
+        assert!(loss(statics::EN_TYPE_I64, &parse("\"abc\"")).is_some());
+        assert!(loss(statics::EN_TYPE_I64, &parse("1.5")).is_some());
+        assert!(loss(statics::EN_TYPE_I64, &parse("\"42\"")).is_none());
+        assert!(loss(statics::EN_TYPE_ARRAY, &parse("7")).is_none());
+        assert!(loss(statics::EN_TYPE_REFERENCE, &parse("7")).is_none());
+        assert!(loss(statics::EN_TYPE_OBJECT, &parse("7")).is_some());
+        assert!(loss(statics::EN_TYPE_STRING, &TiValue::Null).is_none());
+    }
+
+    #[test]
+    fn number_subtype_changes_are_named_and_large_floats_warned() {
+        let parse = |t: &str| TiValue::parse_json5(t).unwrap();
+        let convert = |label: &str, src: &TiValue| TiseApp::coerce_value_to_type(label, src);
+
+        let small = parse("-5");
+        let as_float = convert(statics::EN_TYPE_F64, &small);
+        assert_eq!(
+            TiseApp::number_subtype_change(&small, &as_float),
+            Some((statics::EN_TYPE_I64, statics::EN_TYPE_F64))
+        );
+        assert_eq!(TiseApp::number_subtype_warning(&small, &as_float), None);
+        assert_eq!(TiseApp::number_subtype_change(&small, &small), None);
+
+        let big = parse("18014398509481984");
+        let big_float = convert(statics::EN_TYPE_F64, &big);
+        assert!(TiseApp::number_subtype_warning(&big, &big_float).is_some());
+        let big_signed = convert(statics::EN_TYPE_I64, &big);
+        assert_eq!(TiseApp::number_subtype_warning(&big, &big_signed), None);
+        assert_eq!(
+            TiseApp::number_subtype_change(&big, &convert(statics::EN_TYPE_STRING, &big)),
+            None
+        );
+    }
+
+    #[test]
+    fn take_pasted_integer_consumes_only_integer_pastes() {
+        let mut input = eframe::egui::InputState::default();
+        input.events = vec![
+            eframe::egui::Event::Paste("abc".to_string()),
+            eframe::egui::Event::Paste(" 4242\n".to_string()),
+        ];
+        assert_eq!(TiseApp::take_pasted_integer(&mut input), Some(4242));
+        assert_eq!(input.events.len(), 1);
+        assert_eq!(TiseApp::take_pasted_integer(&mut input), None);
+
+        assert_eq!(
+            TiseApp::make_reference(Some("T".to_string()), 7).to_json5_compact(),
+            r#"{"$type":"T", "value":7}"#
+        );
+    }
+
+    #[test]
+    fn is_object_list_requires_nonempty_objects_only() {
+        let obj = TiValue::Object(IndexMap::new());
+        assert!(TiseApp::is_object_list(&[obj.clone(), obj.clone()]));
+        assert!(!TiseApp::is_object_list(&[]));
+        assert!(!TiseApp::is_object_list(&[obj, TiValue::Null]));
+    }
+
+    #[test]
+    fn object_list_item_label_uses_first_string_field() {
+        let mut map = IndexMap::new();
+        map.insert("count".to_string(), TiValue::Number(TiNumber::I64(2)));
+        map.insert(
+            "templateName".to_string(),
+            TiValue::String("Laser".to_string()),
+        );
+        assert_eq!(TiseApp::object_list_item_label(3, &map), "[3] Laser {2}");
+
+        map.shift_remove("templateName");
+        assert_eq!(TiseApp::object_list_item_label(0, &map), "[0] {1}");
+    }
+
+    #[test]
+    fn numeric_sum_needs_every_value_to_be_a_number() {
+        let mut map = IndexMap::new();
+        map.insert("a".to_string(), TiValue::Number(TiNumber::F64(0.25)));
+        map.insert("b".to_string(), TiValue::Number(TiNumber::I64(2)));
+        assert_eq!(TiseApp::numeric_sum(&map), Some(2.25));
+        map.insert("c".to_string(), TiValue::Null);
+        assert_eq!(TiseApp::numeric_sum(&map), None);
+    }
+
     #[test]
     fn is_simple_object_accepts_nonempty_primitives_only() {
         let mut map = IndexMap::new();
@@ -2,11 +2,21 @@
 //! Provides JSON5 parsing/serialization tailored for Terra Invicta save files, including
 //! round-trip guarantees and efficient indexing.
 
+pub mod batch;
+mod cli;
+pub mod date;
+pub mod diff;
+pub mod editlog;
+pub mod flatten;
 mod gui;
+mod notes;
 mod save;
+pub mod scramble;
+mod settings;
 pub mod statics;
 mod value;
 
+pub use cli::run_cli;
 pub use gui::run_gui;
-pub use save::{LoadedSave, SaveFormat};
-pub use value::TiValue;
+pub use save::{CopiedObject, LoadedSave, MissingRefTarget, NonFiniteNumber, SaveFormat};
+pub use value::{FloatLiterals, ParseError, SaveStyle, TiValue};
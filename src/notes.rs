@@ -0,0 +1,103 @@
+//! Free-text notes about objects, kept in a sidecar file next to the save
+//! (`<save>.tise-notes.json`) so they never touch game data.
+
+use crate::statics;
+use anyhow::Context;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Notes by object ID. Empty notes are never stored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectNotes {
+    notes: BTreeMap<i64, String>,
+}
+
+impl ObjectNotes {
+    /// The sidecar file for a save at `save_path`.
+    pub fn path_for(save_path: &Path) -> PathBuf {
+        let mut name = save_path.as_os_str().to_owned();
+        name.push(statics::NOTES_FILE_SUFFIX);
+        PathBuf::from(name)
+    }
+
+    /// Notes for the save at `save_path`; none if it has no sidecar file yet.
+    pub fn load_for(save_path: &Path) -> anyhow::Result<Self> {
+        let path = Self::path_for(save_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(&path).with_context(|| format!("reading {path:?}"))?;
+        let raw: BTreeMap<String, String> =
+            json5::from_str(&text).with_context(|| format!("parsing {path:?}"))?;
+        let mut notes = Self::default();
+        for (id, note) in raw {
+            let id = id
+                .parse()
+                .with_context(|| format!("{path:?}: {id:?} is not an object ID"))?;
+            notes.set(id, note);
+        }
+        Ok(notes)
+    }
+
+    /// Write the sidecar file for the save at `save_path`, or remove it once there are no notes.
+    pub fn save_for(&self, save_path: &Path) -> anyhow::Result<()> {
+        let path = Self::path_for(save_path);
+        if self.notes.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path).with_context(|| format!("removing {path:?}"))?;
+            }
+            return Ok(());
+        }
+        let raw: BTreeMap<String, &String> = self
+            .notes
+            .iter()
+            .map(|(id, n)| (id.to_string(), n))
+            .collect();
+        let text = json5::to_string(&raw).context("serializing notes")?;
+        std::fs::write(&path, text).with_context(|| format!("writing {path:?}"))
+    }
+
+    pub fn get(&self, id: i64) -> Option<&str> {
+        self.notes.get(&id).map(String::as_str)
+    }
+
+    /// Set an object's note; an empty note removes it.
+    pub fn set(&mut self, id: i64, note: String) {
+        if note.is_empty() {
+            self.notes.remove(&id);
+        } else {
+            self.notes.insert(id, note);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ObjectNotes;
+
+    #[test]
+    fn notes_round_trip_through_sidecar_and_empty_notes_remove_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let save_path = dir.path().join("Autosave.gz");
+        let sidecar = ObjectNotes::path_for(&save_path);
+        assert_eq!(sidecar.file_name().unwrap(), "Autosave.gz.tise-notes.json");
+        assert!(ObjectNotes::load_for(&save_path).unwrap().is_empty());
+
+        let mut notes = ObjectNotes::default();
+        notes.set(42, "the saboteur".to_string());
+        notes.set(-1, String::new());
+        notes.save_for(&save_path).unwrap();
+        let loaded = ObjectNotes::load_for(&save_path).unwrap();
+        assert_eq!(loaded, notes);
+        assert_eq!(loaded.get(42), Some("the saboteur"));
+        assert_eq!(loaded.get(-1), None);
+
+        notes.set(42, String::new());
+        notes.save_for(&save_path).unwrap();
+        assert!(!sidecar.exists());
+    }
+}
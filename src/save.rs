@@ -1,12 +1,20 @@
-use crate::{TiValue, statics};
+use crate::{
+    TiValue,
+    date::TiDate,
+    diff::{self, DiffHunk},
+    statics,
+    value::{self, SaveStyle},
+};
 use anyhow::Context;
 use flate2::{Compression, GzBuilder, read::GzDecoder};
 use indexmap::IndexMap;
 use std::{
+    borrow::Cow,
     collections::HashMap,
     fs,
     io::{Read, Write},
     path::{Path, PathBuf},
+    sync::OnceLock,
 };
 
 const COMMON_NAMESPACE: &str = "PavonisInteractive.TerraInvicta.";
@@ -17,12 +25,94 @@ pub enum SaveFormat {
     GzipJson5,
 }
 
+impl SaveFormat {
+    /// The format `LoadedSave::save_to_path` writes to `path`: gzip for a `.gz` extension
+    /// (including `.json.gz`, in any case), plain JSON5 otherwise.
+    pub fn for_path(path: &Path) -> SaveFormat {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        if ext.eq_ignore_ascii_case("gz") {
+            SaveFormat::GzipJson5
+        } else {
+            SaveFormat::Json5
+        }
+    }
+
+    /// Split a save's file name into stem and extension, keeping a compressed save's double
+    /// extension whole: `Save.json.gz` gives `("Save", "json.gz")`, `Save.json` `("Save", "json")`.
+    /// The extension is empty if there is none.
+    pub fn split_file_name(name: &str) -> (&str, &str) {
+        fn split(n: &str) -> Option<(&str, &str)> {
+            n.rsplit_once('.').filter(|(stem, _)| !stem.is_empty())
+        }
+        let Some((rest, last)) = split(name) else {
+            return (name, "");
+        };
+        if last.eq_ignore_ascii_case("gz")
+            && let Some((stem, inner)) = split(rest)
+            && ["json", "json5"]
+                .iter()
+                .any(|e| inner.eq_ignore_ascii_case(e))
+        {
+            return (stem, &name[stem.len() + 1..]);
+        }
+        (rest, last)
+    }
+
+    /// `path` with `.gz` added or removed so that `for_path` gives this format.
+    pub fn adjust_path(self, path: &Path) -> PathBuf {
+        match (self, SaveFormat::for_path(path)) {
+            (SaveFormat::GzipJson5, SaveFormat::Json5) => {
+                let mut name = path.as_os_str().to_owned();
+                name.push(".gz");
+                PathBuf::from(name)
+            }
+            (SaveFormat::Json5, SaveFormat::GzipJson5) => path.with_extension(""),
+            _ => path.to_path_buf(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LineEnding {
     Lf,
     CrLf,
 }
 
+/// What `LoadedSave::copy_object_from` did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopiedObject {
+    /// ID of the new object.
+    pub id: i64,
+    /// References pointed at a same-named object in the destination.
+    pub remapped_refs: usize,
+    /// References to other objects left at their source IDs.
+    pub unresolved_refs: usize,
+}
+
+/// A `NaN` or infinite number, as listed by `LoadedSave::non_finite_numbers`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonFiniteNumber {
+    pub group: String,
+    pub object_id: i64,
+    pub prop: String,
+    /// JSON Pointer from the property's value to the number; empty if the property is the number.
+    pub path: String,
+    pub value: f64,
+}
+
+/// A reference target ID that no object has, as listed by `LoadedSave::missing_ref_targets`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingRefTarget {
+    pub id: i64,
+    /// How many references point at it.
+    pub count: usize,
+    /// The first object (group, ID, property) found referencing it.
+    pub referrer: (String, i64, String),
+}
+
 #[derive(Debug, Clone)]
 pub struct ObjectSummary {
     pub id: i64,
@@ -38,6 +128,14 @@ pub struct SaveIndex {
     pub objects_by_group: HashMap<String, Vec<ObjectSummary>>,
     pub id_lookup: HashMap<i64, (String, usize)>,
     pub id_to_display_name: HashMap<i64, String>,
+    /// Highest object ID in use, if there are any objects.
+    pub max_id: Option<i64>,
+    /// Array indices of entries skipped because they aren't objects or lack an integer `Key`,
+    /// for each group that has any.
+    pub malformed_by_group: HashMap<String, Vec<usize>>,
+    /// Root key the groups were found under: `gamestates`, or another key with its shape if a
+    /// save has none. `None` if no key looks like it, which leaves the index empty.
+    pub gamestates_key: Option<String>,
 }
 
 impl SaveIndex {
@@ -47,6 +145,9 @@ impl SaveIndex {
             objects_by_group: HashMap::new(),
             id_lookup: HashMap::new(),
             id_to_display_name: HashMap::new(),
+            max_id: None,
+            malformed_by_group: HashMap::new(),
+            gamestates_key: None,
         }
     }
 }
@@ -59,38 +160,65 @@ pub struct LoadedSave {
     pub format: SaveFormat,
     pub line_ending: LineEnding,
     pub original_bytes: Vec<u8>,
+    /// `content_hash` of the data in `original_bytes`, so most edits are seen as changes
+    /// without serializing the whole save (see `refresh_dirty`).
+    original_hash: u64,
     pub root: TiValue,
     pub dirty: bool,
     pub index: SaveIndex,
+    /// Single-quoted strings in the loaded text. They are written back double-quoted, so once
+    /// the save is regenerated it no longer matches the source byte for byte.
+    pub single_quoted_strings: usize,
+    /// When writing an edited save, keep the original text of every root property, group and
+    /// `gamestates` entry that hasn't changed, and only serialize the changed ones (see
+    /// `serialize_text`).
+    pub minimize_diff: bool,
+    /// Write empty objects the way the game does (see `SaveStyle::empty_object_quirk`). On for
+    /// loaded saves; turn it off when the file is meant for other JSON5 tools.
+    pub empty_object_quirk: bool,
+    /// How the loaded text spelled floats we'd format differently; unedited values are written
+    /// back that way.
+    pub float_literals: value::FloatLiterals,
+    /// `original_bytes` parsed for `minimize_diff` on first use; `None` inside if they can't be.
+    /// Reset whenever `original_bytes` changes.
+    splice_source: OnceLock<Option<SpliceSource>>,
+}
+
+/// The original save text with its parsed value and value spans, see `splice_into_original`.
+#[derive(Debug, Clone)]
+struct SpliceSource {
+    text: String,
+    root: TiValue,
+    span: value::ValueSpan,
 }
 
 impl LoadedSave {
     pub fn load_path(path: &Path) -> anyhow::Result<Self> {
         let bytes = fs::read(path).with_context(|| format!("reading {path:?}"))?;
         let format = detect_format(path, &bytes);
-        let text_bytes = match format {
-            SaveFormat::Json5 => bytes.clone(),
-            SaveFormat::GzipJson5 => {
-                let mut decoder = GzDecoder::new(&bytes[..]);
-                let mut out = Vec::new();
-                decoder.read_to_end(&mut out).context("gzip decompress")?;
-                out
-            }
-        };
+        let text_bytes = decode_bytes(format, &bytes)?;
 
         let line_ending = detect_line_ending(&text_bytes);
 
         let text = std::str::from_utf8(&text_bytes).context("save file is not valid UTF-8")?;
         let root = TiValue::parse_json5(text).context("parsing JSON5")?;
+        let single_quoted_strings = value::count_single_quoted_strings(text);
+        let float_literals = value::FloatLiterals::scan(text);
 
         let mut save = Self {
             source_path: Some(path.to_path_buf()),
             format,
             line_ending,
             original_bytes: bytes,
+            original_hash: root.content_hash(),
             root,
             dirty: false,
             index: SaveIndex::empty(),
+            single_quoted_strings,
+            minimize_diff: false,
+            empty_object_quirk: true,
+            float_literals,
+            splice_source: OnceLock::new(),
         };
         save.rebuild_index();
         Ok(save)
@@ -100,13 +228,64 @@ impl LoadedSave {
         self.index = build_index(&self.root);
     }
 
+    /// The root key holding every group, see `SaveIndex::gamestates_key`.
+    pub fn gamestates_key(&self) -> &str {
+        self.index
+            .gamestates_key
+            .as_deref()
+            .unwrap_or(statics::TI_GAMESTATES)
+    }
+
+    /// A note on where the groups were found, when it isn't the usual `gamestates` key.
+    pub fn gamestates_report(&self) -> Option<String> {
+        match &self.index.gamestates_key {
+            Some(key) if key == statics::TI_GAMESTATES => None,
+            Some(key) => Some(format!(
+                "No {:?} key; using {key:?}, which holds {} group(s) of keyed objects",
+                statics::TI_GAMESTATES,
+                self.index.groups.len()
+            )),
+            None => Some(format!(
+                "No {:?} key, and no top-level key holds groups of keyed objects",
+                statics::TI_GAMESTATES
+            )),
+        }
+    }
+
+    /// `gamestates` holds every group, so it is not editable as a root property.
+    fn check_root_prop(&self, group: &str, prop: &str) -> anyhow::Result<()> {
+        if group == statics::ROOT_GROUP && prop == self.gamestates_key() {
+            anyhow::bail!("{prop:?} can't be edited as a property of {group}");
+        }
+        Ok(())
+    }
+
+    fn gamestates(&self) -> Option<&TiValue> {
+        self.root.get(self.gamestates_key())
+    }
+
+    fn gamestates_mut(&mut self) -> Option<&mut TiValue> {
+        let key = self
+            .index
+            .gamestates_key
+            .as_deref()
+            .unwrap_or(statics::TI_GAMESTATES);
+        self.root.get_mut(key)
+    }
+
     pub fn mark_dirty(&mut self) {
         self.dirty = true;
     }
 
     /// Recompute `dirty` by comparing the current serialized bytes to `original_bytes`.
     /// This is used by UI features like Undo/Redo so "dirty" can clear when changes are undone.
+    /// Different data is dirty straight away; only the same data (possibly reordered or
+    /// formatted differently) needs serializing to compare.
     pub fn refresh_dirty(&mut self) {
+        if self.content_hash() != self.original_hash {
+            self.dirty = true;
+            return;
+        }
         // If the format changes, we consider the save dirty.
         let Ok(current) = self.generate_bytes_for_format(self.format) else {
             self.dirty = true;
@@ -115,13 +294,85 @@ impl LoadedSave {
         self.dirty = current != self.original_bytes;
     }
 
+    /// Serialize the save as it would be written (float spellings, empty-object style and the
+    /// minimize-diff splice included) and parse the text back. Fails if the text doesn't parse or
+    /// holds different data than `root` (reporting the first differing line), i.e. if writing the
+    /// save now would produce a file that doesn't load back as the save currently is.
+    pub fn check_round_trip(&self) -> anyhow::Result<()> {
+        let text = self.serialize_text(statics::NL_LF);
+        let reparsed = TiValue::parse_json5(&text).context("serialized save does not parse")?;
+        // Compared as our own formatting so NaNs match and key order still counts.
+        let (expected, actual) = (self.root.to_ti_save_pretty(), reparsed.to_ti_save_pretty());
+        if let Some(hunk) = diff::changed_lines(&expected, &actual, 0).first() {
+            anyhow::bail!(
+                "serialized save reads back differently, first at line {}",
+                hunk.old_start
+            );
+        }
+        Ok(())
+    }
+
+    /// `TiValue::content_hash` of the whole save: equal for saves holding the same data, whatever
+    /// their key order, formatting or compression.
+    pub fn content_hash(&self) -> u64 {
+        self.root.content_hash()
+    }
+
+    /// The save text as it was loaded (or last saved), decompressed if needed.
+    pub fn original_text(&self) -> anyhow::Result<String> {
+        let text_bytes = decode_bytes(self.format, &self.original_bytes)?;
+        String::from_utf8(text_bytes).context("save file is not valid UTF-8")
+    }
+
+    /// Lines that would change if the current state were written out, compared to `original_bytes`.
+    pub fn pending_line_changes(&self, context: usize) -> anyhow::Result<Vec<DiffHunk>> {
+        let old = self.original_text()?;
+        let new = self.serialize_text(statics::NL_LF);
+        Ok(diff::changed_lines(&old, &new, context))
+    }
+
+    /// The save text to write. With `minimize_diff` this is the original text with only the
+    /// changed values re-serialized, falling back to serializing everything if the original
+    /// can't be re-read.
+    fn serialize_text(&self, newline: &str) -> String {
+        let style = SaveStyle {
+            newline,
+            empty_object_quirk: self.empty_object_quirk,
+            float_literals: Some(&self.float_literals),
+        };
+        if self.minimize_diff
+            && let Some(text) = self.splice_into_original(style)
+        {
+            return text;
+        }
+        self.root.to_ti_save_styled(0, style)
+    }
+
+    fn splice_into_original(&self, style: SaveStyle) -> Option<String> {
+        let source = self
+            .splice_source
+            .get_or_init(|| {
+                let text = self.original_text().ok()?;
+                let root = TiValue::parse_json5(&text).ok()?;
+                let span = value::scan_value_spans(&text, SPLICE_DEPTH)?;
+                Some(SpliceSource { text, root, span })
+            })
+            .as_ref()?;
+        let (original, span) = (&source.text, &source.span);
+        let mut out = String::with_capacity(original.len());
+        out.push_str(&original[..span.start]);
+        splice_value(&mut out, original, span, &source.root, &self.root, style);
+        out.push_str(&original[span.end..]);
+        Some(out)
+    }
+
     /// Generate bytes for a format regardless of current `dirty` state.
     pub fn generate_bytes_for_format(&self, format: SaveFormat) -> anyhow::Result<Vec<u8>> {
         let newline = match self.line_ending {
             LineEnding::Lf => statics::NL_LF,
             LineEnding::CrLf => statics::NL_CRLF,
         };
-        let text = self.root.to_ti_save_pretty_with_newline(newline);
+        let text = self.serialize_text(newline);
         let text_bytes = text.as_bytes();
 
         match format {
@@ -152,16 +403,94 @@ impl LoadedSave {
             })
     }
 
+    /// The in-game date: the time state's `currentDateTime`, or failing that the metadata's
+    /// `gameTimeString` (`DD/MM/YYYY HH:MM:SS`). `None` when the save has neither.
+    pub fn game_date(&self) -> Option<TiDate> {
+        if let Some(date) = self
+            .first_object_value(statics::TI_GROUP_TIME_STATE)
+            .and_then(|o| o.get(statics::TI_PROP_CURRENT_DATE_TIME))
+            .and_then(TiDate::from_value)
+        {
+            return Some(date);
+        }
+
+        let text = self
+            .first_object_value(statics::TI_GROUP_METADATA_STATE)?
+            .get(statics::TI_PROP_GAME_TIME_STRING)?
+            .as_str()?;
+        let (date, time) = text.split_once(' ').unwrap_or((text, ""));
+        let [day, month, year] = date.split('/').collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        TiDate::parse(&format!("{year}-{month}-{day} {time}")).ok()
+    }
+
+    /// The game version that last wrote the save (`latestSaveVersion` on the global values
+    /// state), e.g. `0.4.78`.
+    pub fn game_version(&self) -> Option<&str> {
+        self.first_object_value(statics::TI_GROUP_GLOBAL_VALUES_STATE)?
+            .get(statics::TI_PROP_LATEST_SAVE_VERSION)?
+            .as_str()
+    }
+
+    /// Days since the campaign started (`daysInCampaign` on the time state), if present.
+    pub fn days_in_campaign(&self) -> Option<i64> {
+        match self
+            .first_object_value(statics::TI_GROUP_TIME_STATE)?
+            .get(statics::TI_PROP_DAYS_IN_CAMPAIGN)?
+        {
+            TiValue::Number(n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// Value of the first object in a group; for singleton states like the time state.
+    fn first_object_value(&self, group: &str) -> Option<&IndexMap<String, TiValue>> {
+        self.gamestates()?
+            .get(group)?
+            .as_array()?
+            .first()?
+            .get(statics::TI_FIELD_VALUE_CAP)?
+            .as_object()
+    }
+
+    /// `Some((current_id, max_id))` when `currentID` is below an ID already in use, so the game
+    /// could hand that ID out again. Real saves keep `currentID` equal to the last assigned ID.
+    pub fn current_id_too_low(&self) -> Option<(i64, i64)> {
+        let current = self.game_id()?;
+        let max = self.index.max_id?;
+        (current < max).then_some((current, max))
+    }
+
+    /// Raise `currentID` to the highest ID in use. Returns the previous value.
+    pub fn bump_current_id(&mut self) -> anyhow::Result<i64> {
+        let (current, max) = self
+            .current_id_too_low()
+            .context("currentID is already at or above every used ID")?;
+        let value = self
+            .root
+            .get_mut(statics::TI_PROP_CURRENT_ID)
+            .and_then(|v| v.get_mut(statics::TI_REF_FIELD_VALUE))
+            .context("currentID not found")?;
+        *value = TiValue::Number(value::TiNumber::I64(max));
+        self.mark_dirty();
+        Ok(current)
+    }
+
+    /// An object's value. `ROOT_GROUP`/`ROOT_OBJECT_ID` give the save's root object, which
+    /// includes `gamestates`; see `object_properties` for display.
     pub fn get_object_value_mut(
         &mut self,
         group: &str,
         object_id: i64,
     ) -> Option<&mut IndexMap<String, TiValue>> {
-        let (real_group, idx) = self.index.id_lookup.get(&object_id)?.clone();
-        if real_group != group {
-            return None;
+        if group == statics::ROOT_GROUP {
+            return (object_id == statics::ROOT_OBJECT_ID)
+                .then(|| self.root.as_object_mut())
+                .flatten();
         }
-        let gamestates = self.root.get_mut(statics::TI_GAMESTATES)?.as_object_mut()?;
+        let idx = self.object_position(group, object_id)?;
+        let gamestates = self.gamestates_mut()?.as_object_mut()?;
         let group_list = gamestates.get_mut(group)?.as_array_mut()?;
         let entry = group_list.get_mut(idx)?.as_object_mut()?;
         let value = entry
@@ -175,30 +504,555 @@ impl LoadedSave {
         group: &str,
         object_id: i64,
     ) -> Option<&IndexMap<String, TiValue>> {
-        let (real_group, idx) = self.index.id_lookup.get(&object_id)?.clone();
-        if real_group != group {
-            return None;
+        if group == statics::ROOT_GROUP {
+            return (object_id == statics::ROOT_OBJECT_ID)
+                .then(|| self.root.as_object())
+                .flatten();
         }
-        let gamestates = self.root.get(statics::TI_GAMESTATES)?.as_object()?;
+        let idx = self.object_position(group, object_id)?;
+        let gamestates = self.gamestates()?.as_object()?;
         let group_list = gamestates.get(group)?.as_array()?;
         let entry = group_list.get(idx)?.as_object()?;
         let value = entry.get(statics::TI_FIELD_VALUE_CAP)?.as_object()?;
         Some(value)
     }
 
-    pub fn save_to_path(&mut self, path: &Path) -> anyhow::Result<()> {
-        let target_format = if path.extension().and_then(|e| e.to_str()) == Some("gz") {
-            SaveFormat::GzipJson5
-        } else {
-            SaveFormat::Json5
+    /// Where an object sits in its group's array. The index is trusted only if the entry there
+    /// still has this ID; otherwise the group is scanned, so lookups stay correct after
+    /// `insert_object`/`remove_object` shift entries and before the next `rebuild_index`.
+    fn object_position(&self, group: &str, object_id: i64) -> Option<usize> {
+        let list = self.gamestates()?.get(group)?.as_array()?;
+        if let Some((real_group, idx)) = self.index.id_lookup.get(&object_id) {
+            if real_group != group {
+                return None;
+            }
+            if list.get(*idx).and_then(entry_id) == Some(object_id) {
+                return Some(*idx);
+            }
+        }
+        list.iter().position(|e| entry_id(e) == Some(object_id))
+    }
+
+    /// An object's properties as shown to the user: its value, except that the root pseudo-object
+    /// leaves out `gamestates` (every other group's data) and is therefore a copy.
+    pub fn object_properties(
+        &self,
+        group: &str,
+        object_id: i64,
+    ) -> Option<Cow<'_, IndexMap<String, TiValue>>> {
+        let value = self.get_object_value(group, object_id)?;
+        if group != statics::ROOT_GROUP {
+            return Some(Cow::Borrowed(value));
+        }
+        Some(Cow::Owned(
+            value
+                .iter()
+                .filter(|(k, _)| k.as_str() != self.gamestates_key())
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        ))
+    }
+
+    /// Every property name used by objects of `group`, with how many objects have it; most
+    /// common first, then by name.
+    pub fn group_property_keys(&self, group: &str) -> Vec<(String, usize)> {
+        let ids = self.group_object_ids(group);
+        self.count_property_keys(ids.into_iter().map(|id| (group, id)))
+    }
+
+    /// As `group_property_keys`, over the objects of every group and the root pseudo-object.
+    pub fn all_property_keys(&self) -> Vec<(String, usize)> {
+        self.count_property_keys(self.all_object_ids().into_iter())
+    }
+
+    fn count_property_keys<'a>(
+        &self,
+        objects: impl Iterator<Item = (&'a str, i64)>,
+    ) -> Vec<(String, usize)> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for (group, id) in objects {
+            if let Some(props) = self.object_properties(group, id) {
+                for key in props.keys() {
+                    *counts.entry(key.clone()).or_default() += 1;
+                }
+            }
+        }
+        let mut keys: Vec<(String, usize)> = counts.into_iter().collect();
+        keys.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        keys
+    }
+
+    /// Every object that has a property named `prop` (whatever its value) as (group, ID), in
+    /// `group` or across all groups; root pseudo-object first, then by group and ID.
+    pub fn objects_with_property(&self, group: Option<&str>, prop: &str) -> Vec<(String, i64)> {
+        let candidates: Vec<(&str, i64)> = match group {
+            Some(group) => {
+                let mut ids = self.group_object_ids(group);
+                ids.sort_unstable();
+                ids.into_iter().map(|id| (group, id)).collect()
+            }
+            None => self.all_object_ids(),
         };
+        candidates
+            .into_iter()
+            .filter(|&(group, id)| {
+                self.object_properties(group, id)
+                    .is_some_and(|props| props.contains_key(prop))
+            })
+            .map(|(group, id)| (group.to_string(), id))
+            .collect()
+    }
+
+    fn group_object_ids(&self, group: &str) -> Vec<i64> {
+        if group == statics::ROOT_GROUP {
+            return vec![statics::ROOT_OBJECT_ID];
+        }
+        self.index
+            .objects_by_group
+            .get(group)
+            .map(|objects| objects.iter().map(|o| o.id).collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether the root has keys besides `gamestates`, i.e. whether `ROOT_GROUP` has anything to show.
+    pub fn has_root_properties(&self) -> bool {
+        self.root
+            .as_object()
+            .is_some_and(|o| o.keys().any(|k| k != self.gamestates_key()))
+    }
+
+    /// The group an object ID belongs to, including the root pseudo-object.
+    pub fn object_group(&self, object_id: i64) -> Option<&str> {
+        if object_id == statics::ROOT_OBJECT_ID {
+            return Some(statics::ROOT_GROUP);
+        }
+        self.index
+            .id_lookup
+            .get(&object_id)
+            .map(|(g, _)| g.as_str())
+    }
+
+    /// The group holding an object last seen in `group`: `group` if it's still there, otherwise
+    /// wherever a rebuilt index finds it (e.g. after entries were moved between groups).
+    pub fn relocate_object(&mut self, group: &str, object_id: i64) -> Option<String> {
+        if self.get_object_value(group, object_id).is_some() {
+            return Some(group.to_string());
+        }
+        self.rebuild_index();
+        let group = self.object_group(object_id)?.to_string();
+        self.get_object_value(&group, object_id)
+            .is_some()
+            .then_some(group)
+    }
+
+    /// An object's whole `{Key, Value}` entry in its group's array, as the game writes it.
+    /// `None` for the root pseudo-object, which isn't an entry.
+    pub fn object_entry(&self, group: &str, object_id: i64) -> Option<&TiValue> {
+        let idx = self.object_position(group, object_id)?;
+        self.group_entry(group, idx)
+    }
+
+    /// A raw entry of a group's array by position, including entries the index skipped.
+    pub fn group_entry(&self, group: &str, idx: usize) -> Option<&TiValue> {
+        self.gamestates()?.get(group)?.as_array()?.get(idx)
+    }
+
+    /// Resolve a group by its full name or by its short name (without the common namespace).
+    pub fn resolve_group(&self, name: &str) -> Option<&str> {
+        if name == statics::ROOT_GROUP {
+            return Some(statics::ROOT_GROUP);
+        }
+        self.index
+            .groups
+            .iter()
+            .find(|g| *g == name || g.strip_prefix(COMMON_NAMESPACE) == Some(name))
+            .map(String::as_str)
+    }
+
+    fn object_value_mut_or_err(
+        &mut self,
+        group: &str,
+        object_id: i64,
+    ) -> anyhow::Result<&mut IndexMap<String, TiValue>> {
+        let group = self
+            .resolve_group(group)
+            .with_context(|| format!("group {group:?} not found"))?
+            .to_string();
+        self.get_object_value_mut(&group, object_id)
+            .with_context(|| format!("object {object_id} not found in {group}"))
+    }
+
+    /// JSON Pointer to a property of an object's value (`/gamestates/<group>/<index>/Value/<prop>`),
+    /// the inverse of `object_prop_for_pointer`. Returns `None` if the object isn't in `group`.
+    /// Root properties are simply `/<prop>`.
+    pub fn pointer_for_property(&self, group: &str, object_id: i64, prop: &str) -> Option<String> {
+        if group == statics::ROOT_GROUP {
+            return (object_id == statics::ROOT_OBJECT_ID)
+                .then(|| format!("/{}", value::escape_pointer_token(prop)));
+        }
+        let idx = self.object_position(group, object_id)?;
+        Some(format!(
+            "/{}/{}/{idx}/{}/{}",
+            value::escape_pointer_token(self.gamestates_key()),
+            value::escape_pointer_token(group),
+            statics::TI_FIELD_VALUE_CAP,
+            value::escape_pointer_token(prop)
+        ))
+    }
+
+    /// Map a JSON Pointer inside an object's value (`/gamestates/<group>/<index>/Value/<prop>/...`)
+    /// to that object's `(group, id, prop)`. Returns `None` for pointers elsewhere in the save.
+    pub fn object_prop_for_pointer(&self, pointer: &str) -> Option<(String, i64, String)> {
+        let mut tokens = pointer.strip_prefix('/')?.split('/');
+        if value::unescape_pointer_token(tokens.next()?) != self.gamestates_key() {
+            return None;
+        }
+        let group = value::unescape_pointer_token(tokens.next()?);
+        let idx: usize = tokens.next()?.parse().ok()?;
+        if tokens.next()? != statics::TI_FIELD_VALUE_CAP {
+            return None;
+        }
+        let prop = value::unescape_pointer_token(tokens.next()?);
+
+        let entry = self.gamestates()?.get(&group)?.as_array()?.get(idx)?;
+        let id = entry.get(statics::TI_FIELD_KEY_CAP)?.is_relational_ref()?;
+        Some((group, id, prop))
+    }
+
+    /// Set a top-level property on an object, returning the previous value if there was one.
+    /// Marks the save dirty; call `rebuild_index` afterwards if names may have changed.
+    pub fn set_property(
+        &mut self,
+        group: &str,
+        object_id: i64,
+        prop: &str,
+        value: TiValue,
+    ) -> anyhow::Result<Option<TiValue>> {
+        self.check_root_prop(group, prop)?;
+        let obj = self.object_value_mut_or_err(group, object_id)?;
+        let previous = obj.insert(prop.to_string(), value);
+        self.mark_dirty();
+        Ok(previous)
+    }
+
+    /// Remove a top-level property from an object, preserving the order of the remaining keys.
+    /// Returns the removed value, or `None` if the property did not exist.
+    pub fn remove_property(
+        &mut self,
+        group: &str,
+        object_id: i64,
+        prop: &str,
+    ) -> anyhow::Result<Option<TiValue>> {
+        self.check_root_prop(group, prop)?;
+        let obj = self.object_value_mut_or_err(group, object_id)?;
+        let previous = obj.shift_remove(prop);
+        if previous.is_some() {
+            self.mark_dirty();
+        }
+        Ok(previous)
+    }
+
+    /// Deep-merge `patch` (which must be an object) into an object's value. See `TiValue::deep_merge`.
+    pub fn deep_merge(
+        &mut self,
+        group: &str,
+        object_id: i64,
+        patch: &TiValue,
+    ) -> anyhow::Result<()> {
+        let patch_obj = patch.as_object().context("merge patch must be an object")?;
+        if patch_obj.contains_key(self.gamestates_key()) {
+            self.check_root_prop(group, self.gamestates_key())?;
+        }
+        let obj = self.object_value_mut_or_err(group, object_id)?;
+        value::deep_merge_object(obj, patch_obj);
+        self.mark_dirty();
+        Ok(())
+    }
+
+    /// Reorder an object's keys to follow `reference_id`, another object of the same group; keys
+    /// the reference doesn't have keep their relative order after the rest. Returns the object's
+    /// value before and after, or `None` if its keys were already in that order.
+    pub fn match_key_order(
+        &mut self,
+        group: &str,
+        object_id: i64,
+        reference_id: i64,
+    ) -> anyhow::Result<Option<(TiValue, TiValue)>> {
+        let rank: HashMap<String, usize> = self
+            .get_object_value(group, reference_id)
+            .with_context(|| format!("object {reference_id} not found in {group}"))?
+            .keys()
+            .enumerate()
+            .map(|(i, k)| (k.clone(), i))
+            .collect();
+        let obj = self.object_value_mut_or_err(group, object_id)?;
+        let before = obj.clone();
+        // Stable, so extra keys stay in their current order.
+        obj.sort_by(|a, _, b, _| {
+            let pos = |k: &String| rank.get(k).copied().unwrap_or(usize::MAX);
+            pos(a).cmp(&pos(b))
+        });
+        if obj.keys().eq(before.keys()) {
+            return Ok(None);
+        }
+        let after = obj.clone();
+        self.mark_dirty();
+        Ok(Some((TiValue::Object(before), TiValue::Object(after))))
+    }
+
+    /// Append an object (`{Key: {value: id}, Value: value}`) to the end of a group.
+    /// Fails if the group is missing or the ID is already in use there. Does not touch the index;
+    /// call `rebuild_index` afterwards.
+    pub fn insert_object(
+        &mut self,
+        group: &str,
+        object_id: i64,
+        value: IndexMap<String, TiValue>,
+    ) -> anyhow::Result<()> {
+        let list = self.group_list_mut(group)?;
+        if list.iter().any(|e| entry_id(e) == Some(object_id)) {
+            anyhow::bail!("object {object_id} already exists in {group}");
+        }
+        let mut key = IndexMap::new();
+        key.insert(
+            statics::TI_REF_FIELD_VALUE.to_string(),
+            TiValue::Number(value::TiNumber::I64(object_id)),
+        );
+        let mut entry = IndexMap::new();
+        entry.insert(statics::TI_FIELD_KEY_CAP.to_string(), TiValue::Object(key));
+        entry.insert(
+            statics::TI_FIELD_VALUE_CAP.to_string(),
+            TiValue::Object(value),
+        );
+        list.push(TiValue::Object(entry));
+        self.mark_dirty();
+        Ok(())
+    }
+
+    /// Remove an object from its group, returning its value. Like `insert_object` this finds the
+    /// entry by scanning the group rather than through the index, so the two can be mixed freely
+    /// before the next `rebuild_index`.
+    pub fn remove_object(
+        &mut self,
+        group: &str,
+        object_id: i64,
+    ) -> anyhow::Result<IndexMap<String, TiValue>> {
+        let list = self.group_list_mut(group)?;
+        let idx = list
+            .iter()
+            .position(|e| entry_id(e) == Some(object_id))
+            .with_context(|| format!("object {object_id} not found in {group}"))?;
+        let entry = list.remove(idx);
+        self.mark_dirty();
+        match entry.get(statics::TI_FIELD_VALUE_CAP) {
+            Some(TiValue::Object(value)) => Ok(value.clone()),
+            _ => Ok(IndexMap::new()),
+        }
+    }
+
+    fn group_list_mut(&mut self, group: &str) -> anyhow::Result<&mut Vec<TiValue>> {
+        self.gamestates_mut()
+            .and_then(|g| g.get_mut(group))
+            .and_then(TiValue::as_array_mut)
+            .with_context(|| format!("group {group:?} not found"))
+    }
+
+    /// Deep-copy an object from `source` into the same group of this save under a fresh ID
+    /// (one past both `currentID` and the highest used ID; `currentID` is raised to it).
+    /// References to the object itself follow it to the new ID. With `remap_refs`, references
+    /// to other objects are pointed at the object of the same group and display name here, when
+    /// exactly one exists; all other references keep their source IDs and are counted as
+    /// unresolved. Rebuilds the index.
+    pub fn copy_object_from(
+        &mut self,
+        source: &LoadedSave,
+        group: &str,
+        object_id: i64,
+        remap_refs: bool,
+    ) -> anyhow::Result<CopiedObject> {
+        let mut value = source
+            .get_object_value(group, object_id)
+            .with_context(|| format!("object {object_id} not found in {group}"))?
+            .clone();
+        let current = self.game_id().context("currentID not found")?;
+        let new_id = current.max(self.index.max_id.unwrap_or(current)) + 1;
+
+        let mut copied = CopiedObject {
+            id: new_id,
+            remapped_refs: 0,
+            unresolved_refs: 0,
+        };
+        let mut remap = |id: i64| -> Option<i64> {
+            if id == object_id {
+                return Some(new_id);
+            }
+            // Anything not an object ID in the source is just a number that looks like a ref.
+            let (target_group, _) = source.index.id_lookup.get(&id)?;
+            let found = remap_refs
+                .then(|| {
+                    self.object_id_by_name(target_group, source.index.id_to_display_name.get(&id)?)
+                })
+                .flatten();
+            match found {
+                Some(_) => copied.remapped_refs += 1,
+                None => copied.unresolved_refs += 1,
+            }
+            found
+        };
+        for v in value.values_mut() {
+            remap_refs_in(v, &mut remap);
+        }
+
+        self.insert_object(group, new_id, value)?;
+        let current_id = self
+            .root
+            .get_mut(statics::TI_PROP_CURRENT_ID)
+            .and_then(|v| v.get_mut(statics::TI_REF_FIELD_VALUE))
+            .context("currentID not found")?;
+        *current_id = TiValue::Number(value::TiNumber::I64(new_id));
+        self.rebuild_index();
+        Ok(copied)
+    }
+
+    /// The only object in `group` with this display name, if there is exactly one.
+    fn object_id_by_name(&self, group: &str, name: &str) -> Option<i64> {
+        if name.is_empty() {
+            return None;
+        }
+        let mut matches = self
+            .index
+            .objects_by_group
+            .get(group)?
+            .iter()
+            .filter(|o| o.display_name == name);
+        let first = matches.next()?;
+        matches.next().is_none().then_some(first.id)
+    }
+
+    /// Reference targets that aren't in the index ("orphan targets"), most referenced first.
+    pub fn missing_ref_targets(&self) -> Vec<MissingRefTarget> {
+        let mut missing: HashMap<i64, MissingRefTarget> = HashMap::new();
+        for (group, object_id) in self.all_object_ids() {
+            let Some(props) = self.object_properties(group, object_id) else {
+                continue;
+            };
+            for (prop, val) in props.iter() {
+                // The root's `currentID` has the shape of a reference but is the ID counter.
+                if group == statics::ROOT_GROUP && prop == statics::TI_PROP_CURRENT_ID {
+                    continue;
+                }
+                collect_refs(val, &mut |id| {
+                    if self.index.id_lookup.contains_key(&id) {
+                        return;
+                    }
+                    missing
+                        .entry(id)
+                        .or_insert_with(|| MissingRefTarget {
+                            id,
+                            count: 0,
+                            referrer: (group.to_string(), object_id, prop.clone()),
+                        })
+                        .count += 1;
+                });
+            }
+        }
+        let mut missing: Vec<_> = missing.into_values().collect();
+        missing.sort_by_key(|m| (std::cmp::Reverse(m.count), m.id));
+        missing
+    }
+
+    /// Every object as (group, ID): the root pseudo-object first, then by group and ID.
+    fn all_object_ids(&self) -> Vec<(&str, i64)> {
+        let mut objects = Vec::new();
+        if self.has_root_properties() {
+            objects.push((statics::ROOT_GROUP, statics::ROOT_OBJECT_ID));
+        }
+        let mut groups: Vec<&String> = self.index.objects_by_group.keys().collect();
+        groups.sort();
+        for group in groups {
+            let mut ids: Vec<i64> = self.index.objects_by_group[group]
+                .iter()
+                .map(|o| o.id)
+                .collect();
+            ids.sort_unstable();
+            objects.extend(ids.into_iter().map(|id| (group.as_str(), id)));
+        }
+        objects
+    }
+
+    /// Every `NaN`/`Infinity` number in the save's objects (root pseudo-object first, then by
+    /// group and ID). The game writes these in a few places, so finding one isn't an error.
+    pub fn non_finite_numbers(&self) -> Vec<NonFiniteNumber> {
+        let mut found = Vec::new();
+        for (group, object_id) in self.all_object_ids() {
+            let Some(props) = self.object_properties(group, object_id) else {
+                continue;
+            };
+            for (prop, val) in props.iter() {
+                let mut path = String::new();
+                collect_non_finite(val, &mut path, &mut |path, value| {
+                    found.push(NonFiniteNumber {
+                        group: group.to_string(),
+                        object_id,
+                        prop: prop.clone(),
+                        path: path.to_string(),
+                        value,
+                    });
+                });
+            }
+        }
+        found
+    }
+
+    /// Replace a number found by `non_finite_numbers` with `0`, returning the property's value
+    /// before and after. Fails if the number has since been changed to a finite one.
+    pub fn zero_non_finite(
+        &mut self,
+        found: &NonFiniteNumber,
+    ) -> anyhow::Result<(TiValue, TiValue)> {
+        let before = self
+            .get_object_value(&found.group, found.object_id)
+            .and_then(|o| o.get(&found.prop))
+            .cloned()
+            .with_context(|| {
+                format!(
+                    "{} {}.{} not found",
+                    found.group, found.object_id, found.prop
+                )
+            })?;
+        let mut after = before.clone();
+        match after.pointer_mut(&found.path) {
+            Some(TiValue::Number(value::TiNumber::F64(x))) if !x.is_finite() => {
+                *x = 0.0;
+            }
+            _ => anyhow::bail!(
+                "{} {}.{}{} is no longer NaN or infinite",
+                found.group,
+                found.object_id,
+                found.prop,
+                found.path
+            ),
+        }
+        self.set_property(&found.group, found.object_id, &found.prop, after.clone())?;
+        Ok((before, after))
+    }
+
+    /// Write the save to `path` in the format its extension implies (see `SaveFormat::for_path`).
+    pub fn save_to_path(&mut self, path: &Path) -> anyhow::Result<()> {
+        let target_format = SaveFormat::for_path(path);
+        let regenerated = self.dirty || target_format != self.format;
 
         let bytes = self.save_bytes_for_format(target_format)?;
         fs::write(path, &bytes).with_context(|| format!("writing {path:?}"))?;
 
+        if regenerated {
+            self.single_quoted_strings = 0;
+        }
         self.source_path = Some(path.to_path_buf());
         self.format = target_format;
         self.original_bytes = bytes;
+        self.original_hash = self.content_hash();
+        self.splice_source = OnceLock::new();
         self.dirty = false;
         Ok(())
     }
@@ -212,6 +1066,58 @@ impl LoadedSave {
     }
 }
 
+/// Levels below the root that `minimize_diff` keeps apart: root properties, groups, entries.
+const SPLICE_DEPTH: usize = 3;
+
+/// Write `new` in place of the text `span` held for `old`. Unchanged values are copied as they
+/// were; objects with the same keys in the same order and arrays of the same length are spliced
+/// member by member; anything else is serialized at the indentation of the line it starts on.
+fn splice_value(
+    out: &mut String,
+    src: &str,
+    span: &value::ValueSpan,
+    old: &TiValue,
+    new: &TiValue,
+    style: SaveStyle,
+) {
+    if old == new {
+        out.push_str(&src[span.start..span.end]);
+        return;
+    }
+    let pairs: Vec<(&TiValue, &TiValue)> = match (old, new) {
+        (TiValue::Object(a), TiValue::Object(b)) if a.keys().eq(b.keys()) => {
+            a.values().zip(b.values()).collect()
+        }
+        (TiValue::Array(a), TiValue::Array(b)) if a.len() == b.len() => a.iter().zip(b).collect(),
+        _ => Vec::new(),
+    };
+    if !pairs.is_empty() && pairs.len() == span.children.len() {
+        let mut pos = span.start;
+        for ((old, new), child) in pairs.into_iter().zip(&span.children) {
+            out.push_str(&src[pos..child.start]);
+            splice_value(out, src, child, old, new, style);
+            pos = child.end;
+        }
+        out.push_str(&src[pos..span.end]);
+        return;
+    }
+    let line_start = src[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let indent = src[line_start..].bytes().take_while(|b| *b == b' ').count();
+    out.push_str(&new.to_ti_save_styled(indent, style));
+}
+
+fn decode_bytes(format: SaveFormat, bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    match format {
+        SaveFormat::Json5 => Ok(bytes.to_vec()),
+        SaveFormat::GzipJson5 => {
+            let mut decoder = GzDecoder::new(bytes);
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out).context("gzip decompress")?;
+            Ok(out)
+        }
+    }
+}
+
 fn detect_line_ending(text_bytes: &[u8]) -> LineEnding {
     // Detect by counting actual newline terminators.
     // Using "any CRLF anywhere" can mis-detect if the file contains occasional CRLF
@@ -238,7 +1144,7 @@ fn detect_line_ending(text_bytes: &[u8]) -> LineEnding {
 }
 
 fn detect_format(path: &Path, bytes: &[u8]) -> SaveFormat {
-    if path.extension().and_then(|e| e.to_str()) == Some("gz") {
+    if SaveFormat::for_path(path) == SaveFormat::GzipJson5 {
         return SaveFormat::GzipJson5;
     }
     // Gzip magic: 1F 8B
@@ -248,10 +1154,101 @@ fn detect_format(path: &Path, bytes: &[u8]) -> SaveFormat {
     SaveFormat::Json5
 }
 
+/// Call `found` with the target ID of every relational reference in `val`.
+fn collect_refs(val: &TiValue, found: &mut impl FnMut(i64)) {
+    if let Some(id) = val.is_relational_ref() {
+        found(id);
+        return;
+    }
+    match val {
+        TiValue::Array(values) => values.iter().for_each(|v| collect_refs(v, found)),
+        TiValue::Object(map) => map.values().for_each(|v| collect_refs(v, found)),
+        _ => {}
+    }
+}
+
+/// Call `found` with the pointer (relative to `val`) and value of every non-finite number in `val`.
+fn collect_non_finite(val: &TiValue, path: &mut String, found: &mut impl FnMut(&str, f64)) {
+    let len = path.len();
+    match val {
+        TiValue::Number(value::TiNumber::F64(x)) if !x.is_finite() => found(path, *x),
+        TiValue::Array(values) => {
+            for (i, v) in values.iter().enumerate() {
+                path.push('/');
+                path.push_str(&i.to_string());
+                collect_non_finite(v, path, found);
+                path.truncate(len);
+            }
+        }
+        TiValue::Object(map) => {
+            for (k, v) in map {
+                path.push('/');
+                path.push_str(&value::escape_pointer_token(k));
+                collect_non_finite(v, path, found);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// ID from a group entry's `Key`.
+fn entry_id(entry: &TiValue) -> Option<i64> {
+    entry.get(statics::TI_FIELD_KEY_CAP)?.is_relational_ref()
+}
+
+/// Rewrite every reference (`{value: <int>}`) inside `val` for which `remap` returns a new ID.
+fn remap_refs_in(val: &mut TiValue, remap: &mut impl FnMut(i64) -> Option<i64>) {
+    if let Some(id) = val.is_relational_ref() {
+        if let Some(new_id) = remap(id)
+            && let Some(v) = val.get_mut(statics::TI_REF_FIELD_VALUE)
+        {
+            *v = TiValue::Number(value::TiNumber::I64(new_id));
+        }
+        return;
+    }
+    match val {
+        TiValue::Array(items) => items.iter_mut().for_each(|v| remap_refs_in(v, remap)),
+        TiValue::Object(map) => map.values_mut().for_each(|v| remap_refs_in(v, remap)),
+        _ => {}
+    }
+}
+
+/// The root key holding the groups: `gamestates` if present, otherwise the first top-level
+/// object whose values are all arrays and which has at least one `{Key: {value: id}}` entry, in
+/// case a game version renames it.
+fn detect_gamestates_key(root: &TiValue) -> Option<String> {
+    let root = root.as_object()?;
+    if root
+        .get(statics::TI_GAMESTATES)
+        .is_some_and(|v| v.as_object().is_some())
+    {
+        return Some(statics::TI_GAMESTATES.to_string());
+    }
+    root.iter()
+        .find(|(_, v)| {
+            v.as_object().is_some_and(|groups| {
+                groups.values().all(|g| g.as_array().is_some())
+                    && groups
+                        .values()
+                        .filter_map(TiValue::as_array)
+                        .flatten()
+                        .any(|e| entry_id(e).is_some())
+            })
+        })
+        .map(|(k, _)| k.clone())
+}
+
 fn build_index(root: &TiValue) -> SaveIndex {
     let mut index = SaveIndex::empty();
 
-    let Some(gamestates) = root.get(statics::TI_GAMESTATES).and_then(|v| v.as_object()) else {
+    index.gamestates_key = detect_gamestates_key(root);
+    let Some(gamestates) = index
+        .gamestates_key
+        .as_deref()
+        .and_then(|key| root.get(key))
+        .and_then(|v| v.as_object())
+    else {
         return index;
     };
 
@@ -262,8 +1259,10 @@ fn build_index(root: &TiValue) -> SaveIndex {
         };
 
         let mut summaries = Vec::new();
+        let mut malformed = Vec::new();
         for (idx, item) in items.iter().enumerate() {
             let Some(item_obj) = item.as_object() else {
+                malformed.push(idx);
                 continue;
             };
             let id = item_obj
@@ -275,6 +1274,7 @@ fn build_index(root: &TiValue) -> SaveIndex {
                     _ => None,
                 });
             let Some(id) = id else {
+                malformed.push(idx);
                 continue;
             };
 
@@ -295,11 +1295,19 @@ fn build_index(root: &TiValue) -> SaveIndex {
                             .and_then(|v| v.as_str())
                             .map(str::trim)
                             .filter(|s| !s.is_empty())
+                            .map(str::to_string)
+                    })
+                    // Unnamed objects that carry their type read as e.g. "(TIShipState)".
+                    .or_else(|| {
+                        o.get(statics::TI_REF_FIELD_TYPE)
+                            .and_then(|v| v.as_str())
+                            .filter(|s| !s.is_empty())
+                            .map(|t| format!("({})", LoadedSave::group_display_name(t)))
                     })
                 })
-                .unwrap_or(statics::EN_EMPTY)
-                .to_string();
+                .unwrap_or_else(|| statics::EN_EMPTY.to_string());
 
+            index.max_id = index.max_id.max(Some(id));
             index.id_lookup.insert(id, (group.clone(), idx));
             index.id_to_display_name.insert(id, display_name.clone());
             summaries.push(ObjectSummary {
@@ -310,6 +1318,9 @@ fn build_index(root: &TiValue) -> SaveIndex {
         }
 
         index.objects_by_group.insert(group.clone(), summaries);
+        if !malformed.is_empty() {
+            index.malformed_by_group.insert(group.clone(), malformed);
+        }
     }
 
     index
@@ -342,6 +1353,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn adjust_path_adds_or_strips_gz_to_match_format() {
+        let plain = Path::new("dir/save.json");
+        let gz = Path::new("dir/save.json.gz");
+        assert_eq!(SaveFormat::GzipJson5.adjust_path(plain), gz);
+        assert_eq!(SaveFormat::Json5.adjust_path(gz), plain);
+        assert_eq!(SaveFormat::Json5.adjust_path(plain), plain);
+        assert_eq!(
+            SaveFormat::for_path(&SaveFormat::GzipJson5.adjust_path(Path::new("save"))),
+            SaveFormat::GzipJson5
+        );
+    }
+
+    #[test]
+    fn json_gz_double_extension_is_kept_whole() {
+        assert_eq!(
+            SaveFormat::for_path(Path::new("dir/Autosave.JSON.GZ")),
+            SaveFormat::GzipJson5
+        );
+        let split = SaveFormat::split_file_name;
+        assert_eq!(split("Autosave.json.gz"), ("Autosave", "json.gz"));
+        assert_eq!(split("My.Save.json5.gz"), ("My.Save", "json5.gz"));
+        assert_eq!(split("Autosave.json"), ("Autosave", "json"));
+        assert_eq!(split("backup.tar.gz"), ("backup.tar", "gz"));
+        assert_eq!(split("Autosave"), ("Autosave", ""));
+        assert_eq!(split(".json.gz"), (".json", "gz"));
+    }
+
     #[test]
     fn build_index_extracts_ids_and_display_names() {
         // Build a minimal TI-shaped structure:
@@ -391,6 +1430,12 @@ mod tests {
 
         let props4 = IndexMap::new();
 
+        let mut props5 = IndexMap::new();
+        props5.insert(
+            statics::TI_REF_FIELD_TYPE.to_string(),
+            TiValue::String("PavonisInteractive.TerraInvicta.TIShipState".to_string()),
+        );
+
         let group = "PavonisInteractive.TerraInvicta.TITest";
         let mut gamestates = IndexMap::new();
         gamestates.insert(
@@ -400,6 +1445,7 @@ mod tests {
                 make_entry(2, props2),
                 make_entry(3, props3),
                 make_entry(4, props4),
+                make_entry(5, props5),
             ]),
         );
 
@@ -416,6 +1462,23 @@ mod tests {
         assert_eq!(index.id_to_display_name.get(&2).unwrap(), "Name");
         assert_eq!(index.id_to_display_name.get(&3).unwrap(), "Event");
         assert_eq!(index.id_to_display_name.get(&4).unwrap(), "");
+        assert_eq!(index.id_to_display_name.get(&5).unwrap(), "(TIShipState)");
+        assert!(index.malformed_by_group.is_empty());
+    }
+
+    #[test]
+    fn build_index_records_skipped_entries() {
+        let root = TiValue::parse_json5(
+            r#"{ gamestates: {
+                Bad: [ { Key: { value: 1 }, Value: {} }, 5, { Key: 'x', Value: {} }, { Value: {} } ],
+                Good: [ { Key: { value: 2 }, Value: {} } ],
+            } }"#,
+        )
+        .unwrap();
+        let index = build_index(&root);
+        assert_eq!(index.objects_by_group["Bad"].len(), 1);
+        assert_eq!(index.malformed_by_group["Bad"], vec![1, 2, 3]);
+        assert!(!index.malformed_by_group.contains_key("Good"));
     }
 
     #[test]
@@ -0,0 +1,87 @@
+use crate::{
+    TiValue, statics,
+    value::{Fnv1a, TiNumber},
+};
+
+/// Properties whose string values identify people/places and are replaced when anonymizing.
+const NAME_PROPS: [&str; 5] = [
+    statics::TI_PROP_DISPLAY_NAME,
+    statics::TI_PROP_DISPLAY_NAME_WITH_ARTICLE,
+    statics::TI_PROP_NAME,
+    statics::TI_PROP_FAMILY_NAME,
+    statics::TI_PROP_PERSONAL_NAME,
+];
+
+/// Relative amount floats are nudged by when scrambling (e.g. 0.1 = up to +/-10%).
+const FLOAT_JITTER: f64 = 0.1;
+
+/// Produce a shareable copy of a save: names are anonymized and floats jittered.
+/// Structure, key order, value types, integers (IDs, references, enums) and all other strings
+/// (templates, `$type`) are left untouched so the result still behaves like a real save.
+pub fn scramble(root: &mut TiValue, seed: u64) {
+    anonymize_names(root);
+    jitter_floats(root, seed);
+}
+
+/// Replace the string value of every known name property with a stable placeholder.
+/// The same original name always maps to the same placeholder, so repeated names stay linked.
+pub fn anonymize_names(root: &mut TiValue) {
+    match root {
+        TiValue::Array(values) => values.iter_mut().for_each(anonymize_names),
+        TiValue::Object(map) => {
+            for (k, v) in map.iter_mut() {
+                if let TiValue::String(s) = v
+                    && NAME_PROPS.contains(&k.as_str())
+                    && !s.trim().is_empty()
+                {
+                    *s = format!("{} {:08x}", statics::EN_ANON_NAME_PREFIX, fnv1a(s) as u32);
+                } else {
+                    anonymize_names(v);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Multiply every finite float by a pseudo-random factor in `1 +/- FLOAT_JITTER`.
+/// Deterministic for a given `seed`, so a scrambled fixture can be regenerated exactly.
+pub fn jitter_floats(root: &mut TiValue, seed: u64) {
+    let mut state = seed;
+    jitter_floats_inner(root, &mut state);
+}
+
+fn jitter_floats_inner(val: &mut TiValue, state: &mut u64) {
+    match val {
+        TiValue::Number(TiNumber::F64(v)) if v.is_finite() && *v != 0.0 => {
+            // Map the next random u64 to [-1, 1).
+            let unit = (splitmix64(state) >> 11) as f64 / (1u64 << 52) as f64 - 1.0;
+            *v *= 1.0 + unit * FLOAT_JITTER;
+        }
+        TiValue::Array(values) => {
+            for v in values {
+                jitter_floats_inner(v, state);
+            }
+        }
+        TiValue::Object(map) => {
+            for v in map.values_mut() {
+                jitter_floats_inner(v, state);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn fnv1a(s: &str) -> u64 {
+    let mut hash = Fnv1a::default();
+    hash.write(s.as_bytes());
+    hash.0
+}
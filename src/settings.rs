@@ -0,0 +1,136 @@
+//! Preferences kept between sessions, stored as a small JSON5 file in the user's config directory
+//! (`%APPDATA%\tise` on Windows, `$XDG_CONFIG_HOME/tise` or `~/.config/tise` elsewhere).
+//! Missing keys take their defaults, so older files keep loading as settings are added.
+
+use crate::statics;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    pub theme_dark: bool,
+    pub show_groups_panel: bool,
+    pub show_objects_panel: bool,
+    /// Leave groups without objects out of the Groups panel.
+    pub hide_empty_groups: bool,
+    /// Multiplier on the OS display scaling, see `UI_SCALE_RANGE`.
+    pub ui_scale: f32,
+    /// Re-serialize and re-parse the whole save after every edit (slow on big saves).
+    pub validate_after_edit: bool,
+    /// Write edited saves with `LoadedSave::minimize_diff`.
+    pub minimize_diff: bool,
+    /// Show the line diff and ask for confirmation before writing a save.
+    pub preview_before_save: bool,
+    /// Write `{}` for empty objects instead of the game's blank line.
+    pub plain_empty_objects: bool,
+    /// Keep the selected property when moving to another object that has it.
+    pub sticky_property: bool,
+    /// Decimals shown by float drag values; `None` lets egui pick. Display only.
+    pub float_decimals: Option<usize>,
+    /// sRGB accent for selections and active widgets; `None` keeps egui's default.
+    pub accent_color: Option<[u8; 3]>,
+}
+
+/// Range offered for `Settings::ui_scale`; stored values outside it are clamped.
+pub const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.75..=2.0;
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme_dark: true,
+            show_groups_panel: true,
+            show_objects_panel: true,
+            hide_empty_groups: false,
+            ui_scale: 1.0,
+            validate_after_edit: false,
+            minimize_diff: false,
+            preview_before_save: false,
+            plain_empty_objects: false,
+            sticky_property: false,
+            float_decimals: None,
+            accent_color: None,
+        }
+    }
+}
+
+impl Settings {
+    /// `ui_scale` limited to `UI_SCALE_RANGE` (1.0 if the stored value isn't a number).
+    pub fn clamped_ui_scale(&self) -> f32 {
+        if self.ui_scale.is_nan() {
+            return 1.0;
+        }
+        self.ui_scale
+            .clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end())
+    }
+
+    /// Where settings are stored, or `None` if no config directory can be determined.
+    pub fn path() -> Option<PathBuf> {
+        let base = std::env::var_os("APPDATA")
+            .or_else(|| std::env::var_os("XDG_CONFIG_HOME"))
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+        Some(
+            base.join(statics::SETTINGS_DIR_NAME)
+                .join(statics::SETTINGS_FILE_NAME),
+        )
+    }
+
+    /// The stored settings, or the defaults if there are none or they can't be read.
+    pub fn load() -> Self {
+        Self::path()
+            .filter(|p| p.exists())
+            .and_then(|p| Self::load_from(&p).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("reading {path:?}"))?;
+        json5::from_str(&text).with_context(|| format!("parsing {path:?}"))
+    }
+
+    /// Write to `path()`, creating the directory if needed.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().context("no config directory to store settings in")?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {dir:?}"))?;
+        }
+        let text = json5::to_string(self).context("serializing settings")?;
+        std::fs::write(path, text).with_context(|| format!("writing {path:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Settings;
+
+    #[test]
+    fn settings_round_trip_and_missing_keys_use_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("settings.json5");
+        let settings = Settings {
+            show_groups_panel: false,
+            float_decimals: Some(2),
+            accent_color: Some([200, 120, 40]),
+            ..Settings::default()
+        };
+        settings.save_to(&path).unwrap();
+        assert_eq!(Settings::load_from(&path).unwrap(), settings);
+
+        std::fs::write(&path, "{ showObjectsPanel: false }").unwrap();
+        let loaded = Settings::load_from(&path).unwrap();
+        assert!(loaded.show_groups_panel);
+        assert!(!loaded.show_objects_panel);
+
+        let huge = Settings {
+            ui_scale: 10.0,
+            ..Settings::default()
+        };
+        assert_eq!(huge.clamped_ui_scale(), *super::UI_SCALE_RANGE.end());
+    }
+}
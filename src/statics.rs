@@ -8,9 +8,43 @@ pub const GITHUB_URL: &str = "https://github.com/staehle/tise";
 pub const EN_APP_TITLE: &str = "TISE: Terra Invicta Save Editor";
 
 pub const EN_BTN_OPEN: &str = "Open...";
+pub const EN_BTN_OPEN_OTHER: &str = "Compare With...";
 pub const EN_BTN_SAVE_AS: &str = "Save As...";
+pub const EN_BTN_FLATTEN_EXPORT: &str = "Flatten Export...";
+pub const EN_BTN_FLATTEN_IMPORT: &str = "Flatten Import...";
 pub const EN_BTN_ABOUT: &str = "About";
+pub const EN_BTN_LOG: &str = "Log";
 pub const EN_BTN_TOGGLE_THEME: &str = "Theme";
+pub const EN_MENU_SETTINGS: &str = "Settings";
+pub const EN_SETTING_COLLAPSE_REF_TYPES: &str = "Show references as ID and name";
+pub const EN_SETTING_PREVIEW_BEFORE_SAVE: &str = "Preview changes before saving";
+pub const EN_SETTING_ROUND_TRIP_COLUMN: &str = "Show round-trip check in Properties";
+pub const EN_SETTING_FULL_FLOAT_PRECISION: &str = "Show exact float values in previews";
+pub const EN_HINT_FULL_FLOAT_PRECISION: &str =
+    "Show the full decimal expansion of stored floats. Saving is unaffected.";
+pub const EN_SETTING_VALIDATE_AFTER_EDIT: &str = "Validate whole save after each edit";
+pub const EN_HINT_VALIDATE_AFTER_EDIT: &str =
+    "Re-serialize and re-parse the full save after every change. Slow on large saves.";
+pub const EN_SETTING_MINIMIZE_DIFF: &str = "Only rewrite changed objects when saving";
+pub const EN_HINT_MINIMIZE_DIFF: &str = "Keep the original text of every object you haven't edited, \
+    even where TISE would format it differently. Slower to save on large saves.";
+pub const EN_SETTING_PLAIN_EMPTY_OBJECTS: &str = "Write empty objects as {}";
+pub const EN_HINT_PLAIN_EMPTY_OBJECTS: &str = "The game writes an empty object as an opening brace, \
+    a blank line and a closing brace. Turn this on for files meant for other JSON5 tools; \
+    saves written this way no longer match the game's formatting.";
+pub const EN_SETTING_ACCENT_COLOR: &str = "Custom accent color";
+pub const EN_SETTING_FLOAT_DECIMALS: &str = "Float decimals:";
+pub const EN_FLOAT_DECIMALS_AUTO: &str = "Auto";
+pub const EN_SETTING_STICKY_PROPERTY: &str = "Keep selected property across objects";
+pub const EN_HINT_STICKY_PROPERTY: &str =
+    "When another object is selected, stay on the same property if it has one by that name.";
+pub const EN_WARN_SINGLE_QUOTED: &str =
+    "single-quoted string(s) will be written with double quotes if this save is edited and saved";
+pub const EN_ERR_VALIDATE_AFTER_EDIT: &str = "Save failed validation after the last edit";
+pub const EN_SETTING_SHOW_GROUPS_PANEL: &str = "Show Groups panel";
+pub const EN_SETTING_HIDE_EMPTY_GROUPS: &str = "Hide empty groups";
+pub const EN_SETTING_SHOW_OBJECTS_PANEL: &str = "Show Objects panel";
+pub const EN_SETTING_UI_SCALE: &str = "UI scale";
 
 pub const EN_NAV_BACK: &str = "<- Back";
 pub const EN_NAV_FORWARD: &str = "Forward ->";
@@ -21,30 +55,88 @@ pub const EN_BTN_REDO: &str = "Redo";
 pub const EN_BTN_CHANGES: &str = "Changes";
 pub const EN_BTN_SEARCH_REF_BROWSER: &str = "Search References";
 pub const EN_BTN_SEARCH_ITEMS: &str = "Search Items";
+pub const EN_BTN_FIND_PROPERTY: &str = "Find Property";
+pub const EN_HINT_FIND_PROPERTY: &str =
+    "List the objects that have a property with a given name, whatever its value.";
 
 pub const EN_WINDOW_ABOUT: &str = "About";
+pub const EN_WINDOW_LOG: &str = "Log";
+pub const EN_LOG_EMPTY: &str = "No messages yet.";
 pub const EN_WINDOW_GO_TO_ID: &str = "Go to ID";
 pub const EN_WINDOW_CHANGES: &str = "Changes";
+pub const EN_WINDOW_SAVE_PREVIEW: &str = "Confirm Save";
+pub const EN_HEADING_NOTES: &str = "Notes";
+pub const EN_HINT_NOTES: &str =
+    "Private notes about this object, kept next to the save file (never written into it).";
+pub const EN_GLYPH_NOTE: &str = "📝";
+pub const EN_WINDOW_FORMAT_CHANGE: &str = "Change File Format?";
+pub const EN_WINDOW_CONFIRM_SAVE_AS: &str = "Save As";
+pub const EN_CONFIRM_SAVE_AS_WRITING: &str = "Writing";
+pub const EN_CONFIRM_SAVE_AS_TO: &str = "to";
+pub const EN_BTN_OK: &str = "OK";
+pub const EN_FORMAT_CHANGE_LOADED: &str = "The save was loaded as";
+pub const EN_FORMAT_CHANGE_WOULD_WRITE: &str = "but this file name would be written as";
+pub const EN_FORMAT_JSON5: &str = "plain JSON";
+pub const EN_FORMAT_GZIP_JSON5: &str = "gzip-compressed JSON (.gz)";
+pub const EN_BTN_KEEP_FORMAT: &str = "Keep";
+pub const EN_BTN_SAVE_AS_FORMAT: &str = "Save as";
+pub const EN_WINDOW_IMPORT_REPORT: &str = "Import Problems";
+pub const EN_WINDOW_REPLAY_REPORT: &str = "Replay Problems";
 pub const EN_WINDOW_SEARCH_REF_BROWSER: &str = "Search References";
 pub const EN_WINDOW_SEARCH_ITEMS: &str = "Search Items";
+pub const EN_WINDOW_FIND_PROPERTY: &str = "Find Property";
+pub const EN_HINT_FIND_PROPERTY_NAME: &str = "exact property name, e.g. publicOpinion";
+pub const EN_LABEL_KNOWN_PROPERTIES: &str = "Known...";
+pub const EN_LABEL_ADD_PROPERTY: &str = "Add property:";
+pub const EN_HINT_ADD_PROPERTY_NAME: &str = "new property name";
+pub const EN_BTN_ADD_PROPERTY: &str = "Add";
+pub const EN_ERR_PROPERTY_EXISTS: &str = "The object already has a property named";
+pub const EN_ALL_GROUPS: &str = "(all groups)";
+pub const EN_BTN_FIND: &str = "Find";
+pub const EN_FIND_PROPERTY_ENTER: &str = "Enter a property name and press Find.";
+pub const EN_LABEL_FIND_PROPERTY_FOUND: &str = "object(s) have this property";
 
 pub const EN_ABOUT_HEADING: &str = "TISE: Terra Invicta Save Editor";
 pub const EN_ABOUT_VERSION: &str = "Version:";
+pub const EN_LABEL_VERSION: &str = "version:";
 pub const EN_ABOUT_SHORTCUTS: &str = "Shortcuts:";
 pub const EN_ABOUT_SHORTCUT_ALT: &str = "- Alt+Left / Alt+Right: Back/Forward";
 pub const EN_ABOUT_SHORTCUT_MOUSE: &str = "- Mouse back/forward buttons also work";
+pub const EN_ABOUT_SHORTCUT_OBJECT_NAV: &str =
+    "- Home / End / PageUp / PageDown: Move through the objects list";
+pub const EN_ABOUT_SHORTCUT_STEP_OBJECT: &str =
+    "- Ctrl+PageUp / Ctrl+PageDown: Previous/next object, keeping the selected property";
+pub const EN_ABOUT_SHORTCUT_THEME: &str = "- Ctrl+T: Toggle dark/light theme";
+pub const EN_ABOUT_SHORTCUT_APPLY: &str = "- Enter in a single-line value editor: Apply Property";
 pub const EN_PROJECT_REPO: &str = "GitHub Repo";
 
 pub const EN_HOME_HEADING: &str = "TISE: Terra Invicta Save Editor";
 pub const EN_HOME_INSTRUCTIONS: &str = "Open a Terra Invicta save (.json/.gz) to begin.";
+pub const EN_LOADED_OPENED: &str = "Opened";
+pub const EN_LOADED_AS: &str = "as";
+pub const EN_LOADED_VERSION: &str = "game version";
+pub const EN_LOADED_DATE: &str = "game date";
+pub const EN_LOADED_UNKNOWN: &str = "unknown";
+pub const EN_HINT_LOADED_SUMMARY: &str =
+    "Check this is the save you meant to open before editing it.";
 
 pub const EN_HEADING_GROUPS: &str = "Groups";
+pub const EN_LABEL_LIST_COLUMN: &str = "Show:";
+pub const EN_HINT_LIST_COLUMN: &str =
+    "A property shown after each object's name in this group (number of objects that have it)";
+pub const EN_LIST_COLUMN_NONE: &str = "(name only)";
 pub const EN_HEADING_OBJECTS: &str = "Objects";
 pub const EN_HEADING_PROPERTIES: &str = "Properties";
 pub const EN_HEADING_EDIT: &str = "Edit";
 
 pub const EN_LABEL_SEARCH: &str = "Search:";
+pub const EN_LABEL_FILTER: &str = "Filter:";
 pub const EN_HINT_SEARCH: &str = "ID or name";
+pub const EN_LABEL_ID_RANGE: &str = "ID range:";
+pub const EN_LABEL_ID_RANGE_TO: &str = "to";
+pub const EN_HINT_MIN: &str = "min";
+pub const EN_HINT_MAX: &str = "max";
+pub const EN_ERR_INVALID_ID_RANGE: &str = "ID range bounds must be whole numbers";
 pub const EN_HINT_SEARCH_ITEMS: &str = "key or value";
 pub const EN_SEARCH_ENTER_QUERY: &str = "Enter a search query.";
 pub const EN_SEARCH_NO_MATCHES: &str = "No matches.";
@@ -58,6 +150,14 @@ pub const EN_COL_VALUE: &str = "Value";
 pub const EN_COL_PROPERTY: &str = "Property";
 pub const EN_COL_VALUE_REF: &str = "Value / Ref";
 pub const EN_COL_TYPE: &str = "Type";
+pub const EN_COL_ROUND_TRIP: &str = "Round-trip";
+pub const EN_ROUND_TRIP_OK: &str = "✔";
+pub const EN_ROUND_TRIP_UNSTABLE: &str = "⚠ unstable";
+pub const EN_HINT_ROUND_TRIP_UNSTABLE: &str =
+    "Writing this value and reading it back gives different text; it may change on every save";
+pub const EN_ROUND_TRIP_RESPELLED: &str = "≈ respelled";
+pub const EN_HINT_ROUND_TRIP_RESPELLED: &str = "A number in this value is spelled more than one way \
+    in the file, so it is saved in this editor's formatting rather than as it was written";
 pub const EN_COL_REF: &str = "Ref";
 pub const EN_COL_ID: &str = "ID";
 pub const EN_COL_NAME: &str = "Name";
@@ -66,6 +166,8 @@ pub const EN_COL_GROUP: &str = "Group";
 pub const EN_LABEL_SORT: &str = "Sort:";
 pub const EN_SORT_NAME: &str = "Name";
 pub const EN_SORT_ID: &str = "ID";
+pub const EN_BTN_FIRST: &str = "First";
+pub const EN_BTN_LAST: &str = "Last";
 
 pub const EN_SELECT_GROUP: &str = "Select a group.";
 pub const EN_SELECT_GROUP_LEFT: &str = "Select a group from the left.";
@@ -74,17 +176,99 @@ pub const EN_SELECT_PROPERTY: &str = "Select a property to edit.";
 
 pub const EN_BTN_GO: &str = "Go";
 pub const EN_BTN_CANCEL: &str = "Cancel";
+pub const EN_BTN_SAVE: &str = "Save";
 
 pub const EN_BTN_APPLY_PROPERTY: &str = "Apply Property";
 pub const EN_BTN_SET_NULL: &str = "Set null";
+pub const EN_BTN_RESET_DEFAULT: &str = "Reset to default";
+pub const EN_HINT_RESET_DEFAULT: &str = "Set this field to the value the game starts it at:";
 pub const EN_BTN_GO_TO_REF: &str = "Go to Ref";
+pub const EN_BTN_FIX: &str = "Fix";
+pub const EN_WARN_CURRENT_ID_LOW: &str = "currentID too low:";
+pub const EN_WARN_CURRENT_ID_LOW_HINT: &str = "currentID is below an ID already in use, so the game may reuse IDs. Fix raises it to the highest used ID.";
+pub const EN_STATUS_CURRENT_ID_BUMPED: &str = "Raised currentID";
+pub const EN_HEADING_OTHER_SAVE: &str = "Other Save";
+pub const EN_OTHER_ID_MISSING: &str = "This ID is not in the other save.";
+pub const EN_OTHER_DIFFERING: &str = "properties differ (highlighted)";
+pub const EN_WARN_MALFORMED: &str = "⚠";
+pub const EN_WARN_NUMBER_BUFFER: &str = "⚠ Your text is kept as typed but isn't a valid";
+pub const EN_KIND_NUMBER: &str = "number";
+pub const EN_KIND_INTEGER: &str = "integer";
+pub const EN_BTN_REVERT: &str = "Revert";
+pub const EN_WARN_MALFORMED_ENTRIES: &str = "malformed entries skipped";
+pub const EN_HINT_MALFORMED_ENTRIES: &str =
+    "Group entries that are not objects or have no integer Key ID are not listed as objects";
+pub const EN_BTN_SHOW: &str = "Show";
+pub const EN_REF_MODE_OBJECTS: &str = "Objects";
+pub const EN_REF_MODE_MISSING: &str = "Missing targets";
+pub const EN_HINT_REF_MODE_MISSING: &str =
+    "IDs that references point at but no object has, e.g. after corruption or pruning.";
+pub const EN_LABEL_MISSING_TARGETS: &str = "missing target(s)";
+pub const EN_LABEL_REFERENCES: &str = "reference(s)";
+pub const EN_COL_REF_COUNT: &str = "Refs";
+pub const EN_COL_REFERRER: &str = "Referenced by (first)";
+pub const EN_HINT_GO_TO_REFERRER: &str = "Go to the first object referencing this ID";
+pub const EN_REF_PEEK_MORE: &str = "more (Go to see all)";
+pub const EN_WINDOW_NON_FINITE: &str = "NaN / Infinity Check";
+pub const EN_BTN_NON_FINITE: &str = "NaN/∞ Check";
+pub const EN_HINT_NON_FINITE: &str =
+    "List numbers that are NaN or infinite, which can break fields the game expects to be finite.";
+pub const EN_LABEL_NON_FINITE_FOUND: &str = "NaN/Infinity value(s) found";
+pub const EN_BTN_RESCAN: &str = "Rescan";
+pub const EN_BTN_ZERO: &str = "Set to 0";
+pub const EN_BTN_ZERO_ALL: &str = "Set All to 0";
+pub const EN_STATUS_NON_FINITE_ZEROED: &str = "NaN/Infinity values set to 0:";
+pub const EN_WINDOW_MALFORMED_ENTRIES: &str = "Malformed Entries";
+pub const EN_LABEL_ENTRY_INDEX: &str = "Array index";
+pub const EN_OTHER_FOLLOW_SELECTION: &str = "Same ID as selection";
+pub const EN_BTN_COPY_TO_MAIN: &str = "Copy Into Main Save";
+pub const EN_HINT_COPY_TO_MAIN: &str =
+    "Add a copy of this object to the main save under a new ID (undoable)";
+pub const EN_COPY_REMAP_REFS: &str = "Remap references by name";
+pub const EN_HINT_COPY_REMAP_REFS: &str = "Point references at the object with the same group and name in the main save, where there is exactly one";
+pub const EN_PREFIX_COPIED_OBJECT: &str = "Copied object";
+pub const EN_COPY_REFS_REMAPPED: &str = "references remapped";
+pub const EN_COPY_REFS_UNRESOLVED: &str = "left at their original IDs";
+pub const EN_STATUS_OPENED_OTHER: &str = "Comparing with";
+pub const EN_STATUS_SAME_CONTENT: &str = "(same content as the open save, ignoring key order)";
 pub const EN_BTN_CHANGE_TYPE: &str = "Change Type...";
+pub const EN_BTN_PREV_OBJECT: &str = "◀";
+pub const EN_BTN_NEXT_OBJECT: &str = "▶";
+pub const EN_HINT_PREV_OBJECT: &str = "Previous object in this group (Ctrl+PageUp)";
+pub const EN_HINT_NEXT_OBJECT: &str = "Next object in this group (Ctrl+PageDown)";
+pub const EN_BTN_SELECT_ALL: &str = "Select All";
+pub const EN_LABEL_WORD_WRAP: &str = "Word wrap";
+pub const EN_LABEL_MULTILINE: &str = "Multiline";
+pub const EN_LABEL_STORED_AS: &str = "Stored as:";
+pub const EN_BTN_COPY_PATH: &str = "Copy Path";
+pub const EN_BTN_MATCH_KEY_ORDER: &str = "Match Key Order...";
+pub const EN_HINT_MATCH_KEY_ORDER: &str =
+    "Reorder this object's keys like another object of the group; keys it lacks go last";
+pub const EN_WINDOW_MATCH_KEY_ORDER: &str = "Match Key Order";
+pub const EN_LABEL_MATCH_KEY_ORDER: &str = "Reorder the keys of object";
+pub const EN_LABEL_MATCH_KEY_ORDER_REF: &str = "to match:";
+pub const EN_HINT_REFERENCE_ID: &str = "reference object ID";
+pub const EN_LABEL_NO_REFERENCE_IN_GROUP: &str = "Enter the ID of another object in this group.";
+/// Prefix of the object ID in change descriptions, e.g. "ID 42: ...".
+pub const EN_DESC_OBJECT_ID: &str = "ID";
+pub const EN_DESC_MATCH_KEY_ORDER: &str = "matched key order to";
+pub const EN_STATUS_KEY_ORDER_UNCHANGED: &str = "Key order already matches";
+pub const EN_BTN_COPY_ENTRY: &str = "Copy Entry";
+pub const EN_BTN_COPY_ALL: &str = "Copy all";
+pub const EN_HINT_COPY_ENTRY: &str =
+    "Copy the whole {Key, Value} entry, formatted as in the save, ready to paste into a group";
+pub const EN_STATUS_COPIED_ENTRY: &str = "Copied entry of object";
+pub const EN_PREFIX_COPIED: &str = "Copied";
 
 pub const EN_WINDOW_CHANGE_TYPE: &str = "Change Type";
 
 pub const EN_LABEL_REFERENCE_ID: &str = "Reference ID:";
 pub const EN_LABEL_VALUE: &str = "Value";
 pub const EN_PREFIX_VALUE: &str = "Value: ";
+pub const EN_SUFFIX_PERCENT: &str = "%";
+pub const EN_LABEL_EDIT_AS_PERCENT: &str = "Edit as %";
+pub const EN_HINT_EDIT_AS_PERCENT: &str =
+    "Type 30 for 0.3; the value is divided by 100 when stored. The stored value is shown below.";
 pub const EN_HINT_VALUE: &str = "Value";
 pub const EN_CHECKBOX_RAW_JSON5: &str = "Raw JSON5";
 
@@ -98,39 +282,132 @@ pub const EN_PUBLIC_OPINION_CHART_HINT: &str =
 pub const EN_PUBLIC_OPINION_ERR_TOTAL_EXCEEDS: &str =
     "Total exceeds 1.0 (Undecided would be negative)";
 pub const EN_BTN_APPLY_PUBLIC_OPINION: &str = "Apply Public Opinion";
+pub const EN_COUNCILOR_EDITOR: &str = "Councilor";
+pub const EN_LABEL_PERSONAL_NAME: &str = "Personal name";
+pub const EN_LABEL_FAMILY_NAME: &str = "Family name";
+pub const EN_LABEL_DISPLAY_NAME: &str = "Display name";
+pub const EN_BTN_COMPOSE_DISPLAY_NAME: &str = "From personal + family";
+pub const EN_LABEL_LOCATION: &str = "Location";
+pub const EN_LABEL_ATTRIBUTES: &str = "Attributes";
+pub const EN_LABEL_TRAITS: &str = "Traits";
+pub const EN_HINT_NEW_TRAIT: &str = "Trait template name";
+pub const EN_BTN_REMOVE: &str = "❌";
+pub const EN_BTN_EDIT: &str = "Edit";
+pub const EN_BTN_APPLY_COUNCILOR: &str = "Apply Councilor";
+pub const EN_DATE_EDITOR: &str = "Date";
+pub const EN_DATE_YEAR: &str = "Year";
+pub const EN_DATE_MONTH: &str = "Month";
+pub const EN_DATE_DAY: &str = "Day";
+pub const EN_DATE_HOUR: &str = "Hour";
+pub const EN_DATE_MINUTE: &str = "Min";
+pub const EN_DATE_SECOND: &str = "Sec";
+pub const EN_DATE_MILLISECOND: &str = "Ms";
+pub const EN_HINT_DATE: &str = "YYYY-MM-DD HH:MM:SS";
 pub const EN_SIMPLE_OBJECT_EDITOR: &str = "Simple object editor";
+pub const EN_LABEL_SUM: &str = "Sum =";
 pub const EN_SIMPLE_LIST_EDITOR: &str = "Simple list editor";
 pub const EN_MIXED_OBJECT_EDITOR: &str = "Mixed object editor";
+pub const EN_OBJECT_LIST_EDITOR: &str = "Object list editor";
 
 pub const EN_COL_INDEX: &str = "Index";
 
 pub const EN_BTN_ADD_ITEM: &str = "Add item";
+pub const EN_LABEL_INSERT_TYPE: &str = "New items:";
 pub const EN_BTN_DELETE: &str = "Delete";
 pub const EN_BTN_INSERT: &str = "Insert";
+pub const EN_BTN_DUP: &str = "Dup";
+pub const EN_DRAG_HANDLE: &str = "☰";
 pub const EN_BTN_UP: &str = "Up";
 pub const EN_BTN_DOWN: &str = "Down";
 pub const EN_BTN_APPLY: &str = "Apply";
 pub const EN_BTN_RESET: &str = "Reset";
 pub const EN_BTN_CLEAR: &str = "Clear";
+pub const EN_BTN_CLOSE: &str = "Close";
 
 pub const EN_LABEL_JSON5: &str = "JSON5";
 pub const EN_LABEL_PREVIEW: &str = "Preview";
 pub const EN_LABEL_PICK_TYPE: &str = "Pick a type:";
+pub const EN_WARN_LOSS_ARRAY_ELEMENTS: &str =
+    "element(s) of the array will be discarded or rewritten.";
+pub const EN_WARN_LOSS_OBJECT_FIELDS: &str =
+    "field(s) of the object will be discarded or rewritten.";
+pub const EN_WARN_LOSS_NOT_REPRESENTABLE: &str = "can't be represented as";
+pub const EN_WARN_LOSS_BECOMES: &str = "it will become";
+pub const EN_CHECK_CONFIRM_DATA_LOSS: &str = "I understand this data will be lost";
+pub const EN_CHECK_CONFIRM_SUBTYPE: &str = "Change the number subtype anyway";
+pub const EN_LABEL_NUMBER_SUBTYPE: &str = "Number subtype:";
+pub const EN_WARN_LARGE_NUMBER_SUBTYPE: &str = "is beyond 2^53, past which f64 can't hold every \
+    integer; the game may not read the converted value back exactly.";
+/// 2^53: integers above this magnitude aren't all representable as f64.
+pub const F64_EXACT_INT_LIMIT: f64 = 9_007_199_254_740_992.0;
 
 pub const EN_HISTORY_LABEL: &str = "history:";
 pub const EN_HISTORY_BACK: &str = "<-";
 pub const EN_HISTORY_FORWARD: &str = "->";
 
+pub const EN_BTN_UNDO_ALL: &str = "Undo all";
+pub const EN_BTN_REDO_ALL: &str = "Redo all";
+pub const EN_BTN_EXPORT_CHANGES: &str = "Export changes";
+pub const EN_BTN_REPLAY_LOG: &str = "Replay log";
+pub const EN_LOG_HEADER: &str = "TISE edit log";
+pub const EN_LOG_ABSENT: &str = "(absent)";
+pub const EN_LOG_NOT_REPLAYABLE: &str = "whole-object change, not recorded for replay";
 pub const EN_CHANGES_NONE: &str = "No changes.";
 pub const EN_CHANGES_TIP: &str = "Tip: Undo/Redo also works with Ctrl+Z / Ctrl+Y";
+pub const EN_CHANGES_MODE_CHRONOLOGICAL: &str = "Chronological";
+pub const EN_CHANGES_MODE_BY_OBJECT: &str = "By object";
+pub const EN_CHANGES_WHOLE_OBJECT: &str = "(whole object)";
+pub const EN_CHANGES_NO_MATCH: &str = "No changes match the filter.";
+pub const EN_HINT_CHANGES_FILTER: &str = "ID, property or description";
 
 pub const EN_PREFIX_UNDO: &str = "Undo:";
 pub const EN_PREFIX_REDO: &str = "Redo:";
 pub const EN_LABEL_CHANGES_COUNT: &str = "changes:";
 
 pub const EN_LITERAL_MISSING: &str = "<missing>";
+pub const EN_ANON_NAME_PREFIX: &str = "Anon";
+
+// Save preview window
+pub const EN_SAVE_PREVIEW_TARGET: &str = "Writing to:";
+pub const EN_SAVE_PREVIEW_NO_CHANGES: &str =
+    "No lines change; the file will be identical to the original.";
 pub const EN_EMPTY: &str = "";
 
+// Command-line subcommands (the GUI starts when no arguments are given).
+pub const CLI_CMD_SCRAMBLE: &str = "scramble";
+pub const CLI_CMD_BATCH: &str = "batch";
+pub const CLI_FLAG_SEED: &str = "--seed";
+pub const CLI_FLAG_SUFFIX: &str = "--suffix";
+pub const EN_CLI_USAGE: &str = "Usage:\n  tise                                      Start the GUI\n  tise scramble <input> <output> [--seed N] Write an anonymized, value-scrambled copy of a save\n  tise batch <dir> <ops.json5> [--suffix S] Apply a list of edits to every save in a directory";
+
+// Batch edit files: a JSON5 array of { op, group, id, prop?, value? } objects.
+pub const BATCH_KEY_OP: &str = "op";
+pub const BATCH_KEY_GROUP: &str = "group";
+pub const BATCH_KEY_ID: &str = "id";
+pub const BATCH_KEY_PROP: &str = "prop";
+pub const BATCH_KEY_VALUE: &str = "value";
+pub const BATCH_OP_SET: &str = "set";
+pub const BATCH_OP_REMOVE: &str = "remove";
+pub const BATCH_OP_MERGE: &str = "merge";
+pub const LOG_KEY_BEFORE: &str = "before";
+pub const LOG_KEY_AFTER: &str = "after";
+pub const BATCH_DEFAULT_SUFFIX: &str = "_patched";
+pub const BATCH_SAVE_EXTENSIONS: [&str; 3] = ["json", "json5", "gz"];
+// Listed in the open/save dialogs; `json.gz` spells out the usual compressed save name.
+pub const SAVE_DIALOG_EXTENSIONS: [&str; 4] = ["json", "json5", "json.gz", "gz"];
+
+// Pseudo-group listing the save's top-level keys other than `gamestates`, as one object.
+// The ID is negative so it can't collide with the game's object IDs.
+pub const ROOT_GROUP: &str = "(Root)";
+pub const ROOT_OBJECT_ID: i64 = -1;
+pub const EN_ROOT_OBJECT_NAME: &str = "Top-level keys";
+
+// Settings file, kept in the user's config directory (see `crate::settings`).
+pub const SETTINGS_DIR_NAME: &str = "tise";
+pub const SETTINGS_FILE_NAME: &str = "settings.json5";
+/// Appended to a save's path to name its object notes file.
+pub const NOTES_FILE_SUFFIX: &str = ".tise-notes.json";
+
 // Newline constants (used for save formatting; keep out of save/value code).
 pub const NL_LF: &str = "\n";
 pub const NL_CRLF: &str = "\r\n";
@@ -148,6 +425,7 @@ pub const EN_TYPE_REFERENCE: &str = "reference";
 pub const EN_LITERAL_NULL: &str = "null";
 
 pub const EN_ERR_LOCATE_SELECTED_OBJECT: &str = "Could not locate selected object";
+pub const EN_ERR_CLIPBOARD: &str = "Clipboard unavailable, nothing may have been copied";
 pub const EN_ERR_INVALID_ID_INTEGER: &str = "Invalid ID (must be an integer)";
 
 pub const EN_ERR_OBJECT_VALUE_MISSING: &str = "Could not locate object value";
@@ -182,6 +460,7 @@ pub const TI_PUBLIC_OPINION_DESTROY: &str = "Destroy";
 
 // Other known properties.
 pub const TI_PROP_DISPLAY_NAME: &str = "displayName";
+pub const TI_PROP_DISPLAY_NAME_WITH_ARTICLE: &str = "displayNameWithArticle";
 pub const TI_PROP_NAME: &str = "name";
 pub const TI_PROP_EVENT_NAME: &str = "eventName";
 pub const TI_PROP_CURRENT_ID: &str = "currentID";
@@ -190,5 +469,65 @@ pub const TI_PROP_CURRENT_ID: &str = "currentID";
 pub const TI_PROP_FAMILY_NAME: &str = "familyName";
 pub const TI_PROP_PERSONAL_NAME: &str = "personalName";
 
+// Fields of a date object (see `crate::date`).
+pub const TI_DATE_YEAR: &str = "year";
+pub const TI_DATE_MONTH: &str = "month";
+pub const TI_DATE_DAY: &str = "day";
+pub const TI_DATE_HOUR: &str = "hour";
+pub const TI_DATE_MINUTE: &str = "minute";
+pub const TI_DATE_SECOND: &str = "second";
+pub const TI_DATE_MILLISECOND: &str = "millisecond";
+
+// Unit hints shown next to numeric editors for known properties. Fractions and 0-10 scales
+// also limit the drag range; names are as they appear in saves.
+pub const EN_UNIT_FRACTION: &str = "0-1";
+pub const EN_UNIT_SCALE_10: &str = "0-10";
+pub const EN_UNIT_DAYS: &str = "days";
+pub const EN_UNIT_SECONDS: &str = "seconds";
+pub const TI_UNIT_HINTS: [(&str, &str); 16] = [
+    ("autofailMissionsValue", EN_UNIT_FRACTION),
+    ("bankingPercentage", EN_UNIT_FRACTION),
+    ("desiredStaticFleetFraction", EN_UNIT_FRACTION),
+    ("cohesion", EN_UNIT_SCALE_10),
+    ("democracy", EN_UNIT_SCALE_10),
+    ("education", EN_UNIT_SCALE_10),
+    ("inequality", EN_UNIT_SCALE_10),
+    ("sustainability", EN_UNIT_SCALE_10),
+    ("unrest", EN_UNIT_SCALE_10),
+    ("baseBuildDuration_days", EN_UNIT_DAYS),
+    ("completionTime_days", EN_UNIT_DAYS),
+    ("daysToCompletion", EN_UNIT_DAYS),
+    ("annexationDaysLeft", EN_UNIT_DAYS),
+    ("burnDuration_s", EN_UNIT_SECONDS),
+    ("loiterDuration_s", EN_UNIT_SECONDS),
+    ("boostDuration_s", EN_UNIT_SECONDS),
+];
+
+// Values the game gives known fields, offered by "Reset to default": (group, property, JSON5).
+// Add a row to cover another field.
+pub const TI_FIELD_DEFAULTS: [(&str, &str, &str); 5] = [
+    (TI_GROUP_COUNCILOR_STATE, "XP", "0"),
+    (TI_GROUP_COUNCILOR_STATE, "autofailMissionsValue", "0.5"),
+    (TI_GROUP_COUNCILOR_STATE, "repeatOrder", "false"),
+    (TI_GROUP_COUNCILOR_STATE, "permanentAssignment", "false"),
+    (TI_GROUP_NATION_STATE, "unrest", "0.0"),
+];
+
+// Councilor fields surfaced by the councilor editor.
+pub const TI_PROP_ATTRIBUTES: &str = "attributes";
+pub const TI_PROP_TRAIT_TEMPLATE_NAMES: &str = "traitTemplateNames";
+pub const TI_PROP_LOCATION: &str = "location";
+
 // Common group names.
 pub const TI_GROUP_COUNCILOR_STATE: &str = "PavonisInteractive.TerraInvicta.TICouncilorState";
+pub const TI_GROUP_NATION_STATE: &str = "PavonisInteractive.TerraInvicta.TINationState";
+pub const TI_GROUP_TIME_STATE: &str = "PavonisInteractive.TerraInvicta.TITimeState";
+pub const TI_GROUP_METADATA_STATE: &str = "PavonisInteractive.TerraInvicta.TIMetadataState";
+pub const TI_GROUP_GLOBAL_VALUES_STATE: &str =
+    "PavonisInteractive.TerraInvicta.TIGlobalValuesState";
+
+// Game clock fields (time state and save metadata).
+pub const TI_PROP_CURRENT_DATE_TIME: &str = "currentDateTime";
+pub const TI_PROP_DAYS_IN_CAMPAIGN: &str = "daysInCampaign";
+pub const TI_PROP_GAME_TIME_STRING: &str = "gameTimeString";
+pub const TI_PROP_LATEST_SAVE_VERSION: &str = "latestSaveVersion";
@@ -1,6 +1,7 @@
 use crate::statics;
 use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::collections::HashMap;
 
 /// Represents a number that can preserve distinction between I64, U64, and F64 for round-tripping.
 /// Terra Invicta saves are sensitive to integer vs float formatting in some fields.
@@ -21,6 +22,72 @@ impl TiNumber {
     }
 }
 
+/// How a document spelled float values that our formatter writes differently (e.g. `0.5000000`
+/// for `0.5`, or `+Infinity`), keyed by value, so a save's unedited numbers can be written back
+/// as they were (see `SaveStyle::float_literals`). A value spelled more than one way in the
+/// document gets our own formatting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FloatLiterals(HashMap<u64, Option<Box<str>>>);
+
+impl FloatLiterals {
+    /// Collect the float spellings of a JSON5 document.
+    pub fn scan(text: &str) -> Self {
+        let mut spellings: HashMap<u64, Option<Box<str>>> = HashMap::new();
+        for lit in scan_number_literals(text) {
+            // Integer tokens are parsed as `I64`/`U64`, which are always written as parsed.
+            if lit.parse::<i64>().is_ok() || lit.parse::<u64>().is_ok() {
+                continue;
+            }
+            let Ok(v) = lit.parse::<f64>() else {
+                continue;
+            };
+            spellings
+                .entry(float_key(v))
+                .and_modify(|known| {
+                    if known.as_deref() != Some(lit) {
+                        *known = None;
+                    }
+                })
+                .or_insert_with(|| Some(lit.into()));
+        }
+
+        let mut formatted = String::new();
+        spellings.retain(|bits, lit| {
+            let Some(lit) = lit else {
+                return true;
+            };
+            formatted.clear();
+            TiNumber::F64(f64::from_bits(*bits)).write_ti_save(&mut formatted, None);
+            formatted != **lit
+        });
+        Self(spellings)
+    }
+
+    /// The document's spelling of `v`, if it had a single one unlike our own.
+    pub fn get(&self, v: f64) -> Option<&str> {
+        self.0.get(&float_key(v))?.as_deref()
+    }
+
+    /// Whether the document spelled `v` more than one way, so some occurrences of it are
+    /// written back with our formatting instead of their own spelling.
+    pub fn is_ambiguous(&self, v: f64) -> bool {
+        matches!(self.0.get(&float_key(v)), Some(None))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// All NaNs share one key; the rest are keyed by bit pattern, so `-0.0` and `0.0` stay apart.
+fn float_key(v: f64) -> u64 {
+    if v.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        v.to_bits()
+    }
+}
+
 impl Serialize for TiNumber {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
@@ -115,6 +182,33 @@ impl TiValue {
         self.as_object_mut().and_then(|m| m.get_mut(key))
     }
 
+    /// Look up a value by JSON Pointer (RFC 6901), e.g. `/gamestates/Group/0/Value/displayName`.
+    /// The empty pointer refers to `self`; `~1` and `~0` escape `/` and `~` inside keys.
+    pub fn pointer(&self, pointer: &str) -> Option<&TiValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        let rest = pointer.strip_prefix('/')?;
+        rest.split('/').try_fold(self, |cur, token| match cur {
+            TiValue::Object(map) => map.get(&unescape_pointer_token(token)),
+            TiValue::Array(values) => values.get(token.parse::<usize>().ok()?),
+            _ => None,
+        })
+    }
+
+    /// Mutable variant of [`TiValue::pointer`].
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut TiValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        let rest = pointer.strip_prefix('/')?;
+        rest.split('/').try_fold(self, |cur, token| match cur {
+            TiValue::Object(map) => map.get_mut(&unescape_pointer_token(token)),
+            TiValue::Array(values) => values.get_mut(token.parse::<usize>().ok()?),
+            _ => None,
+        })
+    }
+
     pub fn type_name(&self) -> &'static str {
         match self {
             TiValue::Null => "null",
@@ -127,7 +221,16 @@ impl TiValue {
     }
 
     pub fn parse_json5(text: &str) -> anyhow::Result<TiValue> {
-        Ok(json5::from_str::<TiValue>(text)?)
+        match json5::from_str::<TiValue>(text) {
+            Ok(value) => Ok(value),
+            Err(e) => match trailing_data_offset(text) {
+                Some(offset) => anyhow::bail!("unexpected trailing data at offset {offset}"),
+                None => match ParseError::from_json5(text, &e) {
+                    Some(located) => Err(located.into()),
+                    None => Err(e.into()),
+                },
+            },
+        }
     }
 
     pub fn to_json5_pretty(&self) -> String {
@@ -147,17 +250,96 @@ impl TiValue {
     }
 
     pub fn to_ti_save_pretty_with_newline(&self, newline: &str) -> String {
+        self.to_ti_save_styled(0, SaveStyle::game(newline))
+    }
+
+    /// As `to_ti_save_pretty`, with the given style, for a value written on a line indented by
+    /// `indent` spaces: nested lines are indented relative to it, the first line is not indented.
+    pub fn to_ti_save_styled(&self, indent: usize, style: SaveStyle) -> String {
         let mut out = String::new();
-        self.write_ti_save(&mut out, 0, newline);
+        self.write_ti_save(&mut out, indent, style);
         out
     }
 
+    /// Whether writing this value, parsing the text back and writing it again gives identical
+    /// text. Values that fail would drift on every load/save cycle.
+    pub fn round_trips(&self) -> bool {
+        let text = self.to_ti_save_pretty();
+        TiValue::parse_json5(&text).is_ok_and(|v| v.to_ti_save_pretty() == text)
+    }
+
+    /// Whether this value holds a float that may not be written back as the source spelled it,
+    /// see `FloatLiterals::is_ambiguous`.
+    pub fn has_respelled_float(&self, literals: &FloatLiterals) -> bool {
+        match self {
+            TiValue::Number(TiNumber::F64(v)) => literals.is_ambiguous(*v),
+            TiValue::Array(values) => values.iter().any(|v| v.has_respelled_float(literals)),
+            TiValue::Object(map) => map.values().any(|v| v.has_respelled_float(literals)),
+            _ => false,
+        }
+    }
+
+    /// A hash that ignores object key order and how floats were spelled in the source, so
+    /// logically identical values hash the same. It is 64-bit FNV-1a over a canonical compact
+    /// serialization, so it is stable across runs and platforms.
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = Fnv1a::default();
+        self.write_canonical(&mut hash, &mut String::new());
+        hash.0
+    }
+
+    fn write_canonical(&self, hash: &mut Fnv1a, scratch: &mut String) {
+        match self {
+            TiValue::Null | TiValue::Bool(_) => hash.write(self.to_json5_compact().as_bytes()),
+            TiValue::Number(n) => {
+                scratch.clear();
+                n.write_json5(scratch);
+                hash.write(scratch.as_bytes());
+            }
+            TiValue::String(s) => {
+                scratch.clear();
+                write_escaped_string(scratch, s);
+                hash.write(scratch.as_bytes());
+            }
+            TiValue::Array(values) => {
+                hash.write(b"[");
+                for v in values {
+                    v.write_canonical(hash, scratch);
+                    hash.write(b",");
+                }
+                hash.write(b"]");
+            }
+            TiValue::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                hash.write(b"{");
+                for (k, v) in entries {
+                    scratch.clear();
+                    write_escaped_string(scratch, k);
+                    hash.write(scratch.as_bytes());
+                    hash.write(b":");
+                    v.write_canonical(hash, scratch);
+                    hash.write(b",");
+                }
+                hash.write(b"}");
+            }
+        }
+    }
+
     pub fn to_json5_compact(&self) -> String {
         let mut out = String::new();
         self.write_json5(&mut out, 0, false);
         out
     }
 
+    /// `s` as a quoted string literal, escaped exactly as `to_json5_compact` writes strings.
+    /// `parse_json5` turns the result back into `s`.
+    pub fn escape_string(s: &str) -> String {
+        let mut out = String::with_capacity(s.len() + 2);
+        write_escaped_string(&mut out, s);
+        out
+    }
+
     fn write_json5(&self, out: &mut String, indent: usize, pretty: bool) {
         match self {
             TiValue::Null => out.push_str("null"),
@@ -220,11 +402,12 @@ impl TiValue {
         }
     }
 
-    fn write_ti_save(&self, out: &mut String, indent: usize, newline: &str) {
+    fn write_ti_save(&self, out: &mut String, indent: usize, style: SaveStyle) {
+        let newline = style.newline;
         match self {
             TiValue::Null => out.push_str("null"),
             TiValue::Bool(v) => out.push_str(if *v { "true" } else { "false" }),
-            TiValue::Number(n) => n.write_ti_save(out),
+            TiValue::Number(n) => n.write_ti_save(out, style.float_literals),
             TiValue::String(s) => write_escaped_string_ascii(out, s),
             TiValue::Array(values) => {
                 out.push('[');
@@ -232,7 +415,7 @@ impl TiValue {
                     out.push_str(newline);
                     for (i, v) in values.iter().enumerate() {
                         out.push_str(&" ".repeat(indent + 4));
-                        v.write_ti_save(out, indent + 4, newline);
+                        v.write_ti_save(out, indent + 4, style);
                         if i + 1 != values.len() {
                             out.push(',');
                         }
@@ -245,10 +428,12 @@ impl TiValue {
             TiValue::Object(map) => {
                 out.push('{');
                 if map.is_empty() {
-                    // Match the game's odd formatting for empty objects.
-                    out.push_str(newline);
-                    out.push_str(newline);
-                    out.push_str(&" ".repeat(indent));
+                    if style.empty_object_quirk {
+                        // Match the game's odd formatting for empty objects.
+                        out.push_str(newline);
+                        out.push_str(newline);
+                        out.push_str(&" ".repeat(indent));
+                    }
                     out.push('}');
                     return;
                 }
@@ -258,7 +443,7 @@ impl TiValue {
                     out.push_str(&" ".repeat(indent + 4));
                     write_escaped_string_ascii(out, k);
                     out.push_str(": ");
-                    v.write_ti_save(out, indent + 4, newline);
+                    v.write_ti_save(out, indent + 4, style);
                     if i + 1 != map.len() {
                         out.push(',');
                     }
@@ -270,6 +455,15 @@ impl TiValue {
         }
     }
 
+    /// Recursively merge `patch` into `self`. Objects are merged key by key (existing key order is
+    /// kept, new keys are appended); any other patch value replaces the target outright.
+    pub fn deep_merge(&mut self, patch: &TiValue) {
+        match (self, patch) {
+            (TiValue::Object(target), TiValue::Object(patch)) => deep_merge_object(target, patch),
+            (target, patch) => *target = patch.clone(),
+        }
+    }
+
     pub fn is_relational_ref(&self) -> Option<i64> {
         // Matches {"value": <int>} with optional "$type".
         let obj = self.as_object()?;
@@ -309,10 +503,15 @@ impl TiNumber {
         }
     }
 
-    fn write_ti_save(&self, out: &mut String) {
+    /// As the game writes numbers, or as `literals` spelled this value if it has it.
+    fn write_ti_save(&self, out: &mut String, literals: Option<&FloatLiterals>) {
         match self {
             TiNumber::I64(_) | TiNumber::U64(_) => self.write_json5(out),
             TiNumber::F64(v) => {
+                if let Some(lit) = literals.and_then(|l| l.get(*v)) {
+                    out.push_str(lit);
+                    return;
+                }
                 if v.is_nan() {
                     out.push_str("NaN");
                     return;
@@ -375,6 +574,386 @@ impl TiNumber {
     }
 }
 
+/// Object-level half of `TiValue::deep_merge`, usable on a bare map (e.g. a save object's `Value`).
+pub(crate) fn deep_merge_object(
+    target: &mut IndexMap<String, TiValue>,
+    patch: &IndexMap<String, TiValue>,
+) {
+    for (k, v) in patch {
+        match target.get_mut(k) {
+            Some(existing) => existing.deep_merge(v),
+            None => {
+                target.insert(k.clone(), v.clone());
+            }
+        }
+    }
+}
+
+/// Escape an object key for use as one JSON Pointer path segment.
+pub fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Reverse of [`escape_pointer_token`].
+pub fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Collect the source text of every number token in a JSON5 document, in document order.
+/// This is a minimal lexer: it only needs to skip strings/comments and tell identifier keys apart
+/// from the `Infinity`/`NaN` number keywords, since `json5` has already validated the syntax.
+fn scan_number_literals(text: &str) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::new();
+    // Tracks whether each open container is an object, and whether we're expecting a key there.
+    let mut in_object: Vec<bool> = Vec::new();
+    let mut expect_key = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        match b {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 2;
+            }
+            b'"' | b'\'' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'{' => {
+                in_object.push(true);
+                expect_key = true;
+                i += 1;
+            }
+            b'[' => {
+                in_object.push(false);
+                expect_key = false;
+                i += 1;
+            }
+            b'}' | b']' => {
+                in_object.pop();
+                expect_key = false;
+                i += 1;
+            }
+            b',' => {
+                expect_key = in_object.last().copied().unwrap_or(false);
+                i += 1;
+            }
+            b':' => {
+                expect_key = false;
+                i += 1;
+            }
+            _ if b.is_ascii_whitespace() => i += 1,
+            _ => {
+                let start = i;
+                while i < bytes.len()
+                    && !matches!(
+                        bytes[i],
+                        b',' | b':' | b'{' | b'}' | b'[' | b']' | b'"' | b'\'' | b'/'
+                    )
+                    && !bytes[i].is_ascii_whitespace()
+                {
+                    i += 1;
+                }
+                if i == start {
+                    // Stray delimiter (e.g. a lone '/'); json5 would have rejected it already.
+                    i += 1;
+                    continue;
+                }
+                // Only ASCII delimiters end a token, so `i` is always on a char boundary.
+                let token = &text[start..i];
+                let is_number = matches!(
+                    token.trim_start_matches(['+', '-']).as_bytes().first(),
+                    Some(b'0'..=b'9' | b'.' | b'I' | b'N')
+                );
+                if is_number && !expect_key {
+                    out.push(token);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Count strings and keys written with single quotes. They parse like any other string, but
+/// serialization always writes double quotes, so such text doesn't round-trip byte for byte.
+pub fn count_single_quoted_strings(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 2;
+            }
+            quote @ (b'"' | b'\'') => {
+                if quote == b'\'' {
+                    count += 1;
+                }
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    count
+}
+
+/// Layout choices for `TiValue::to_ti_save_styled` beyond the fixed indentation and quoting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveStyle<'a> {
+    pub newline: &'a str,
+    /// Write empty objects as the game does, `{` and `}` with a blank line between, rather
+    /// than `{}`. Other JSON5 readers accept both.
+    pub empty_object_quirk: bool,
+    /// Write floats the way the source document spelled them, where it did so differently.
+    pub float_literals: Option<&'a FloatLiterals>,
+}
+
+impl<'a> SaveStyle<'a> {
+    /// The game's own layout.
+    pub fn game(newline: &'a str) -> Self {
+        Self {
+            newline,
+            empty_object_quirk: true,
+            float_literals: None,
+        }
+    }
+}
+
+/// A JSON5 syntax error located in the source. Its message ends with the offending line and a
+/// caret under the error, so it can be shown as is (in a monospace font for the caret to line up).
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{message} at line {line} column {column} (offset {offset})\n{snippet}")]
+pub struct ParseError {
+    pub message: String,
+    /// 1-based, counting lines as `json5` does (CR LF is one line break).
+    pub line: usize,
+    /// 1-based, in characters.
+    pub column: usize,
+    /// Byte offset into the source text.
+    pub offset: usize,
+    /// The source line, shortened around the error if long, and a caret line under it.
+    pub snippet: String,
+}
+
+impl ParseError {
+    /// Characters of the source line kept on each side of the error in `snippet`.
+    const SNIPPET_CONTEXT: usize = 40;
+
+    /// Locate a `json5` error in `text`; `None` if it has no position.
+    fn from_json5(text: &str, err: &json5::Error) -> Option<Self> {
+        let pos = err.position()?;
+        let full = err.to_string();
+        let message = full
+            .strip_suffix(&format!(" at {pos}"))
+            .unwrap_or(&full)
+            .to_string();
+
+        let is_break = |c: char| matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}');
+        let mut line_start = 0;
+        let mut line = 0;
+        let mut chars = text.char_indices().peekable();
+        while line < pos.line {
+            let (_, c) = chars.next()?;
+            if is_break(c) {
+                if c == '\r' && matches!(chars.peek(), Some((_, '\n'))) {
+                    chars.next();
+                }
+                line += 1;
+                line_start = chars.peek().map_or(text.len(), |(i, _)| *i);
+            }
+        }
+        let line_text = text[line_start..].split(is_break).next().unwrap_or("");
+        let offset = line_start
+            + line_text
+                .char_indices()
+                .nth(pos.column)
+                .map_or(line_text.len(), |(i, _)| i);
+
+        let chars: Vec<char> = line_text.chars().collect();
+        let from = pos.column.saturating_sub(Self::SNIPPET_CONTEXT);
+        let to = (pos.column + Self::SNIPPET_CONTEXT).min(chars.len());
+        let cut_start = if from > 0 { "..." } else { "" };
+        let cut_end = if to < chars.len() { "..." } else { "" };
+        let excerpt: String = chars[from.min(to)..to].iter().collect();
+        let caret_at = cut_start.len() + pos.column - from;
+        let snippet = format!("{cut_start}{excerpt}{cut_end}\n{}^", " ".repeat(caret_at));
+
+        Some(Self {
+            message,
+            line: pos.line + 1,
+            column: pos.column + 1,
+            offset,
+            snippet,
+        })
+    }
+}
+
+/// 64-bit FNV-1a, for `TiValue::content_hash` and the scrambler's stable name hashes.
+pub(crate) struct Fnv1a(pub(crate) u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 = (self.0 ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Byte range of a value in source text, with the ranges of its members or elements in order.
+#[derive(Debug, Clone)]
+pub struct ValueSpan {
+    pub start: usize,
+    pub end: usize,
+    /// Empty for scalars, empty containers and containers below the scanned depth.
+    pub children: Vec<ValueSpan>,
+}
+
+/// Locate the value in `text` and the values nested in it, down to `depth` levels below it.
+/// This is only a lexical scan: `None` if the text isn't shaped like one JSON5 value, but it
+/// doesn't check what `parse_json5` would (e.g. duplicate keys).
+pub fn scan_value_spans(text: &str, depth: usize) -> Option<ValueSpan> {
+    let bytes = text.as_bytes();
+    let mut i = skip_trivia(bytes, 0);
+    let span = scan_span(bytes, &mut i, depth)?;
+    (skip_trivia(bytes, i) == bytes.len()).then_some(span)
+}
+
+/// Where non-whitespace, non-comment text follows a complete value (e.g. junk appended to a
+/// save), if it does.
+fn trailing_data_offset(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = skip_trivia(bytes, 0);
+    scan_span(bytes, &mut i, 0)?;
+    let rest = skip_trivia(bytes, i);
+    (rest < bytes.len()).then_some(rest)
+}
+
+fn scan_span(bytes: &[u8], i: &mut usize, depth: usize) -> Option<ValueSpan> {
+    let start = *i;
+    let mut children = Vec::new();
+    match *bytes.get(start)? {
+        open @ (b'{' | b'[') => {
+            let close = if open == b'{' { b'}' } else { b']' };
+            *i = skip_trivia(bytes, start + 1);
+            while *bytes.get(*i)? != close {
+                if open == b'{' {
+                    *i = match bytes[*i] {
+                        b'"' | b'\'' => skip_string(bytes, *i)?,
+                        _ => token_end(bytes, *i),
+                    };
+                    *i = skip_trivia(bytes, *i);
+                    if bytes.get(*i) != Some(&b':') {
+                        return None;
+                    }
+                    *i = skip_trivia(bytes, *i + 1);
+                }
+                let child = scan_span(bytes, i, depth.saturating_sub(1))?;
+                if depth > 0 {
+                    children.push(child);
+                }
+                *i = skip_trivia(bytes, *i);
+                match *bytes.get(*i)? {
+                    b',' => *i = skip_trivia(bytes, *i + 1),
+                    b if b == close => {}
+                    _ => return None,
+                }
+            }
+            *i += 1;
+        }
+        b'"' | b'\'' => *i = skip_string(bytes, start)?,
+        _ => {
+            *i = token_end(bytes, start);
+            if *i == start {
+                return None;
+            }
+        }
+    }
+    Some(ValueSpan {
+        start,
+        end: *i,
+        children,
+    })
+}
+
+/// Skip whitespace and comments from `i`.
+fn skip_trivia(bytes: &[u8], mut i: usize) -> usize {
+    loop {
+        match bytes.get(i) {
+            Some(b) if b.is_ascii_whitespace() => i += 1,
+            Some(b'/') if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
+                    i += 1;
+                }
+            }
+            Some(b'/') if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 2;
+            }
+            _ => return i.min(bytes.len()),
+        }
+    }
+}
+
+/// End of the string whose opening quote is at `i`, or `None` if it isn't closed.
+fn skip_string(bytes: &[u8], i: usize) -> Option<usize> {
+    let quote = bytes[i];
+    let mut i = i + 1;
+    while i < bytes.len() && bytes[i] != quote {
+        i += if bytes[i] == b'\\' { 2 } else { 1 };
+    }
+    (i < bytes.len()).then_some(i + 1)
+}
+
+/// End of an unquoted token (number, literal or key) starting at `i`.
+fn token_end(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len()
+        && !matches!(
+            bytes[i],
+            b',' | b':' | b'{' | b'}' | b'[' | b']' | b'"' | b'\'' | b'/'
+        )
+        && !bytes[i].is_ascii_whitespace()
+    {
+        i += 1;
+    }
+    i
+}
+
 fn write_escaped_string(out: &mut String, s: &str) {
     out.push('"');
     for ch in s.chars() {
@@ -506,7 +1085,7 @@ impl<'de> Deserialize<'de> for TiValue {
 
 #[cfg(test)]
 mod tests {
-    use super::{TiNumber, TiValue};
+    use super::{FloatLiterals, SaveStyle, TiNumber, TiValue};
     use crate::statics;
     use indexmap::IndexMap;
 
@@ -533,12 +1112,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deep_merge_merges_objects_and_replaces_leaves() {
+        let mut v = TiValue::parse_json5("{ a: 1, b: { c: 2, d: [1, 2] }, e: \"x\" }").unwrap();
+        let patch = TiValue::parse_json5("{ b: { d: [3], f: null }, e: { g: true } }").unwrap();
+        v.deep_merge(&patch);
+        assert_eq!(
+            v.to_json5_compact(),
+            r#"{"a":1, "b":{"c":2, "d":[3], "f":null}, "e":{"g":true}}"#
+        );
+    }
+
+    #[test]
+    fn pointer_navigates_objects_arrays_and_escaped_keys() {
+        let mut v = TiValue::parse_json5(r#"{ a: [ { "b/c": 1, "d~e": 2 } ] }"#).unwrap();
+        assert_eq!(v.pointer(""), Some(&v.clone()));
+        assert_eq!(
+            v.pointer("/a/0/b~1c")
+                .map(TiValue::to_json5_compact)
+                .as_deref(),
+            Some("1")
+        );
+        assert_eq!(
+            v.pointer("/a/0/d~0e")
+                .map(TiValue::to_json5_compact)
+                .as_deref(),
+            Some("2")
+        );
+        assert!(v.pointer("/a/1").is_none());
+        assert!(v.pointer("a").is_none());
+
+        *v.pointer_mut("/a/0/b~1c").unwrap() = TiValue::Bool(true);
+        assert_eq!(v.pointer("/a/0/b~1c"), Some(&TiValue::Bool(true)));
+    }
+
     #[test]
     fn to_ti_save_pretty_empty_object_has_double_newline() {
         let v = TiValue::Object(IndexMap::new());
         assert_eq!(v.to_ti_save_pretty(), "{\n\n}");
     }
 
+    #[test]
+    fn empty_object_quirk_can_be_turned_off() {
+        let v = TiValue::parse_json5("{ a: {}, b: [{}] }").unwrap();
+        let style = super::SaveStyle {
+            empty_object_quirk: false,
+            ..super::SaveStyle::game(statics::NL_LF)
+        };
+        assert_eq!(
+            v.to_ti_save_styled(0, style),
+            "{\n    \"a\": {},\n    \"b\": [\n        {}\n    ]\n}"
+        );
+    }
+
     #[test]
     fn to_ti_save_pretty_escapes_non_ascii_as_u16() {
         let v = TiValue::String("caf\u{00E9}".to_string());
@@ -575,6 +1201,167 @@ mod tests {
         assert_eq!(v.to_ti_save_pretty(), "1E-07");
     }
 
+    fn write_with_literals(v: &TiValue, literals: &FloatLiterals) -> String {
+        v.to_ti_save_styled(
+            0,
+            SaveStyle {
+                float_literals: Some(literals),
+                ..SaveStyle::game(statics::NL_LF)
+            },
+        )
+    }
+
+    #[test]
+    fn float_literals_keep_source_spelling() {
+        let text = "{ a: 0.5000000, b: 2.5, c: +Infinity, Infinity: 1.50 }";
+        let v = TiValue::parse_json5(text).unwrap();
+        let literals = FloatLiterals::scan(text);
+        let s = write_with_literals(&v, &literals);
+        assert!(s.contains("\"a\": 0.5000000,"), "{s}");
+        assert!(s.contains("\"b\": 2.5,"), "{s}");
+        assert!(s.contains("\"c\": +Infinity,"), "{s}");
+        assert!(s.contains("\"Infinity\": 1.50\n"), "{s}");
+
+        // Values are still compared by value only.
+        assert_eq!(v.get("a"), Some(&TiValue::Number(TiNumber::F64(0.5))));
+        // The default style writes the game's formatting.
+        assert!(v.to_ti_save_pretty().contains("\"a\": 0.5,"));
+    }
+
+    #[test]
+    fn float_literals_skip_ambiguous_and_matching_spellings() {
+        let literals = FloatLiterals::scan("{ a: 0.5000000, b: 0.50, c: 2.0, d: [1, 2.5] }");
+        assert_eq!(literals.get(0.5), None);
+        assert_eq!(literals.get(2.5), None);
+        assert_eq!(literals.get(1.0), None);
+        assert!(FloatLiterals::scan("{ a: 0.5, b: 2.5 }").is_empty());
+    }
+
+    #[test]
+    fn edited_float_uses_our_formatting() {
+        let text = "{ a: 0.5000000 }";
+        let mut v = TiValue::parse_json5(text).unwrap();
+        let literals = FloatLiterals::scan(text);
+        if let Some(TiValue::Number(TiNumber::F64(x))) = v.get_mut("a") {
+            *x = 0.25;
+        }
+        assert_eq!(write_with_literals(&v, &literals), "{\n    \"a\": 0.25\n}");
+
+        *v.get_mut("a").unwrap() = TiValue::Number(TiNumber::F64(0.5));
+        assert_eq!(
+            write_with_literals(&v, &literals),
+            "{\n    \"a\": 0.5000000\n}"
+        );
+    }
+
+    #[test]
+    fn round_trips_is_stable_for_floats_written_differently() {
+        let text = "{ a: 1e5, b: [1E-05, -0.0, NaN], c: 'x' }";
+        let v = TiValue::parse_json5(text).unwrap();
+        let written = v.to_ti_save_pretty();
+        assert!(written.contains("\"a\": 100000.0,"), "{written}");
+        assert!(v.round_trips());
+
+        // With the source's spellings the save writes `1e5` back as it was.
+        let s = write_with_literals(&v, &FloatLiterals::scan(text));
+        assert!(s.contains("\"a\": 1e5,"), "{s}");
+    }
+
+    #[test]
+    fn respelled_floats_are_those_spelled_more_than_one_way() {
+        let text = "{ a: 0.50, b: [1, 0.5], c: 1e5, d: 2.5 }";
+        let v = TiValue::parse_json5(text).unwrap();
+        let literals = FloatLiterals::scan(text);
+        assert!(literals.is_ambiguous(0.5));
+        assert!(v.get("a").unwrap().has_respelled_float(&literals));
+        assert!(v.get("b").unwrap().has_respelled_float(&literals));
+        assert!(!v.get("c").unwrap().has_respelled_float(&literals));
+        assert!(!v.get("d").unwrap().has_respelled_float(&literals));
+    }
+
+    #[test]
+    fn strings_with_newlines_round_trip_escaped() {
+        let text = "line one\n\tline \"two\"\r\nC:\\path";
+        let v = TiValue::String(text.to_string());
+        for written in [v.to_json5_compact(), v.to_ti_save_pretty()] {
+            assert!(!written.contains('\n'), "{written}");
+            assert_eq!(TiValue::parse_json5(&written).unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn escape_string_handles_quotes_backslashes_and_whitespace() {
+        let text = "say \"hi\"\tC:\\dir\n\u{1}";
+        let escaped = TiValue::escape_string(text);
+        assert_eq!(escaped, r#""say \"hi\"\tC:\\dir\n\u0001""#);
+        assert_eq!(
+            TiValue::parse_json5(&escaped).unwrap(),
+            TiValue::String(text.to_string())
+        );
+    }
+
+    #[test]
+    fn single_quoted_strings_are_counted_outside_comments_and_other_strings() {
+        let text = "{ 'a': 'x', b: \"it's\", // don't\n c: 'it\\'s', /* 'no' */ d: \"'\" }";
+        assert!(TiValue::parse_json5(text).is_ok());
+        assert_eq!(super::count_single_quoted_strings(text), 3);
+        assert_eq!(super::count_single_quoted_strings(r#"{"a": "b"}"#), 0);
+    }
+
+    #[test]
+    fn value_spans_cover_members_down_to_the_requested_depth() {
+        let text = " { a: [1, 'x]'], /* c */ \"b\": { c: {} } } \n";
+        let span = super::scan_value_spans(text, 1).expect("spans");
+        assert_eq!(&text[span.start..span.end], text.trim());
+        let members: Vec<&str> = span
+            .children
+            .iter()
+            .map(|c| &text[c.start..c.end])
+            .collect();
+        assert_eq!(members, ["[1, 'x]']", "{ c: {} }"]);
+        assert!(span.children[0].children.is_empty());
+
+        assert!(super::scan_value_spans("{ a: 1 } }", 1).is_none());
+        assert!(super::scan_value_spans("{ a 1 }", 1).is_none());
+    }
+
+    #[test]
+    fn syntax_errors_carry_line_column_and_a_caret() {
+        let err = TiValue::parse_json5("{\r\n  a: 1,\r\n  b: tru\r\n}").unwrap_err();
+        let located = err.downcast_ref::<super::ParseError>().expect("located");
+        assert_eq!((located.line, located.column), (3, 9));
+        assert_eq!(located.offset, 20);
+        assert_eq!(located.snippet, "  b: tru\n        ^");
+        assert!(
+            err.to_string()
+                .ends_with("at line 3 column 9 (offset 20)\n  b: tru\n        ^")
+        );
+
+        let long = format!("{{ {} x }}", "a: 1, ".repeat(20));
+        let err = TiValue::parse_json5(&long).unwrap_err();
+        let located = err.downcast_ref::<super::ParseError>().expect("located");
+        let (excerpt, caret) = located.snippet.split_once('\n').unwrap();
+        assert!(excerpt.starts_with("...") && excerpt.ends_with('}'));
+        // `x` is missing its colon; the error is at the closing brace.
+        assert_eq!(excerpt[caret.len() - 1..].chars().next(), Some('}'));
+    }
+
+    #[test]
+    fn trailing_data_after_root_is_reported_with_its_offset() {
+        let err = TiValue::parse_json5("{ a: 1 }\n// end\n{ b: 2 }").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected trailing data at offset 16");
+        let err = TiValue::parse_json5("{ a: 1 }\n\0\0").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected trailing data at offset 9");
+        assert!(TiValue::parse_json5("{ a: 1 } // end\n").is_ok());
+        // Other errors are left to json5.
+        assert!(
+            !TiValue::parse_json5("{ a: }")
+                .unwrap_err()
+                .to_string()
+                .contains("trailing")
+        );
+    }
+
     #[test]
     fn is_relational_ref_requires_integer_value_field() {
         let v = TiValue::parse_json5("{ value: 42 }").unwrap();
@@ -0,0 +1,123 @@
+use pretty_assertions::assert_eq;
+
+use tise::{LoadedSave, TiValue, batch, statics};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const SAVE: &str = r#"{
+  gamestates: {
+    "PavonisInteractive.TerraInvicta.TICouncilorState": [
+      {
+        Key: { value: 10 },
+        Value: {
+          displayName: "Alice",
+          XP: 5,
+          attributes: { Persuasion: 3, Command: 4 },
+          status: "Active",
+        },
+      },
+    ],
+  },
+}"#;
+
+const OPS: &str = r#"[
+  { op: "set", group: "TICouncilorState", id: 10, prop: "XP", value: 500 },
+  { op: "remove", group: "TICouncilorState", id: 10, prop: "status" },
+  { op: "merge", group: "PavonisInteractive.TerraInvicta.TICouncilorState", id: 10,
+    value: { attributes: { Command: 9 } } },
+]"#;
+
+#[test]
+fn batch_applies_ops_to_every_save_and_reports_failures() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a.json"), SAVE)?;
+    std::fs::write(dir.path().join("b.json"), SAVE)?;
+    std::fs::write(dir.path().join("broken.json"), "{ not json")?;
+    std::fs::write(dir.path().join("notes.txt"), "ignored")?;
+
+    let ops = batch::parse_ops(OPS)?;
+    assert_eq!(ops.len(), 3);
+
+    let outcomes = batch::run_batch(dir.path(), &ops, statics::BATCH_DEFAULT_SUFFIX)?;
+    let summary: Vec<_> = outcomes
+        .iter()
+        .map(|o| {
+            let name = o.input.file_name().unwrap().to_string_lossy().to_string();
+            (name, o.result.is_ok())
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("a.json".to_string(), true),
+            ("b.json".to_string(), true),
+            ("broken.json".to_string(), false),
+        ]
+    );
+
+    let patched = LoadedSave::load_path(&dir.path().join("a_patched.json"))?;
+    let group = "PavonisInteractive.TerraInvicta.TICouncilorState";
+    let obj = patched.get_object_value(group, 10).expect("object");
+    assert_eq!(
+        obj.get("XP").map(TiValue::to_json5_compact).as_deref(),
+        Some("500")
+    );
+    assert!(obj.get("status").is_none());
+    assert_eq!(
+        obj.get("attributes")
+            .map(TiValue::to_json5_compact)
+            .as_deref(),
+        Some(r#"{"Persuasion":3, "Command":9}"#)
+    );
+
+    // Re-running skips outputs from the previous run.
+    let rerun = batch::run_batch(dir.path(), &ops, statics::BATCH_DEFAULT_SUFFIX)?;
+    assert_eq!(rerun.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn json_gz_saves_keep_their_double_extension() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let input = dir.path().join("Autosave.json.gz");
+    let output = batch::output_path_for(&input, statics::BATCH_DEFAULT_SUFFIX);
+    assert_eq!(output, dir.path().join("Autosave_patched.json.gz"));
+    assert_eq!(
+        batch::output_path_for(&dir.path().join("a.json"), "_x"),
+        dir.path().join("a_x.json")
+    );
+
+    let mut save = {
+        std::fs::write(dir.path().join("plain.json"), SAVE)?;
+        LoadedSave::load_path(&dir.path().join("plain.json"))?
+    };
+    save.save_to_path(&input)?;
+    assert_eq!(save.format, tise::SaveFormat::GzipJson5);
+    save.save_to_path(&output)?;
+    std::fs::remove_file(dir.path().join("plain.json"))?;
+
+    // The patched copy is recognised as an output and skipped.
+    let found = batch::find_saves(dir.path(), statics::BATCH_DEFAULT_SUFFIX)?;
+    assert_eq!(found, vec![input.clone()]);
+    assert_eq!(
+        LoadedSave::load_path(&input)?.format,
+        tise::SaveFormat::GzipJson5
+    );
+    Ok(())
+}
+
+#[test]
+fn batch_reports_missing_objects() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("a.json");
+    std::fs::write(&path, SAVE)?;
+
+    let ops =
+        batch::parse_ops(r#"[{ op: "remove", group: "TICouncilorState", id: 99, prop: "XP" }]"#)?;
+    let mut save = LoadedSave::load_path(&path)?;
+    let err = batch::apply_ops(&mut save, &ops).unwrap_err();
+    assert!(format!("{err:#}").contains("object 99 not found"));
+
+    assert!(batch::parse_ops(r#"[{ op: "rename", group: "X", id: 1 }]"#).is_err());
+    Ok(())
+}
@@ -95,3 +95,219 @@ fn load_example_resistance_builds_index() -> Result<()> {
     assert!(!save.index.id_lookup.is_empty());
     Ok(())
 }
+
+#[test]
+fn game_date_prefers_time_state_and_falls_back_to_metadata() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json5");
+    let metadata = r#""PavonisInteractive.TerraInvicta.TIMetadataState": [
+      { Key: { value: 1 }, Value: { gameTimeString: "16/04/2031 12:00:00" } },
+    ]"#;
+    let time = r#""PavonisInteractive.TerraInvicta.TITimeState": [
+      { Key: { value: 2 }, Value: { daysInCampaign: 40,
+        currentDateTime: { year: 2031, month: 5, day: 2, hour: 0, minute: 0, second: 0, millisecond: 0 } } },
+    ]"#;
+
+    std::fs::write(
+        &path,
+        format!("{{ gamestates: {{ {metadata}, {time} }} }}").as_bytes(),
+    )?;
+    let save = tise::LoadedSave::load_path(&path)?;
+    assert_eq!(
+        save.game_date().map(|d| d.format_day()),
+        Some("2031-05-02".to_string())
+    );
+    assert_eq!(save.days_in_campaign(), Some(40));
+
+    std::fs::write(
+        &path,
+        format!("{{ gamestates: {{ {metadata} }} }}").as_bytes(),
+    )?;
+    let save = tise::LoadedSave::load_path(&path)?;
+    assert_eq!(
+        save.game_date().map(|d| d.format()),
+        Some("2031-04-16 12:00:00.000".to_string())
+    );
+    assert_eq!(save.days_in_campaign(), None);
+
+    std::fs::write(&path, b"{ gamestates: {} }")?;
+    assert_eq!(tise::LoadedSave::load_path(&path)?.game_date(), None);
+    Ok(())
+}
+
+#[test]
+fn current_id_below_used_ids_is_detected_and_bumped() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json5");
+    std::fs::write(
+        &path,
+        br#"{ currentID: { value: 100 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 99 }, Value: {} },
+            { Key: { value: 150 }, Value: {} },
+        ] } }"#,
+    )?;
+
+    let mut save = tise::LoadedSave::load_path(&path)?;
+    assert_eq!(save.index.max_id, Some(150));
+    assert_eq!(save.current_id_too_low(), Some((100, 150)));
+    assert_eq!(save.bump_current_id()?, 100);
+    assert_eq!(save.game_id(), Some(150));
+    assert_eq!(save.current_id_too_low(), None);
+    assert!(save.bump_current_id().is_err());
+    Ok(())
+}
+
+#[test]
+fn missing_ref_targets_are_counted_with_a_referrer() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json5");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 9 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 1 }, Value: { ID: { value: 1 }, owner: { value: 77 }, fleet: [{ value: 2 }, { value: 88 }] } },
+            { Key: { value: 2 }, Value: { ID: { value: 2 }, target: { $type: "X", value: 77 } } },
+        ] } }"#,
+    )?;
+    let save = tise::LoadedSave::load_path(&path)?;
+
+    let missing = save.missing_ref_targets();
+    let summary: Vec<_> = missing.iter().map(|m| (m.id, m.count)).collect();
+    assert_eq!(summary, [(77, 2), (88, 1)]);
+    let group = "PavonisInteractive.TerraInvicta.TITest".to_string();
+    assert_eq!(missing[0].referrer, (group.clone(), 1, "owner".to_string()));
+    assert_eq!(missing[1].referrer, (group, 1, "fleet".to_string()));
+    Ok(())
+}
+
+#[test]
+fn group_property_keys_counts_objects_per_property() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json5");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 9 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 1 }, Value: { ID: { value: 1 }, name: "a", loyalty: 3 } },
+            { Key: { value: 2 }, Value: { ID: { value: 2 }, name: "b" } },
+            { Key: { value: 3 }, Value: { ID: { value: 3 }, name: "c", apathy: 1, loyalty: 0 } },
+        ] } }"#,
+    )?;
+    let save = tise::LoadedSave::load_path(&path)?;
+
+    let keys = save.group_property_keys("PavonisInteractive.TerraInvicta.TITest");
+    let expected = [("ID", 3), ("name", 3), ("loyalty", 2), ("apathy", 1)];
+    assert_eq!(
+        keys,
+        expected
+            .iter()
+            .map(|(k, n)| (k.to_string(), *n))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        save.group_property_keys(statics::ROOT_GROUP),
+        [("currentID".to_string(), 1)]
+    );
+    assert!(save.group_property_keys("Nope").is_empty());
+
+    // Across all groups, the root's keys count too.
+    let all = save.all_property_keys();
+    assert_eq!(all.len(), 5);
+    assert_eq!(all[0], ("ID".to_string(), 3));
+    assert!(all.contains(&("currentID".to_string(), 1)));
+    Ok(())
+}
+
+#[test]
+fn objects_with_property_lists_holders_in_one_or_all_groups() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json5");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 9 }, gamestates: {
+            "PavonisInteractive.TerraInvicta.TIB": [
+                { Key: { value: 5 }, Value: { ID: { value: 5 }, loyalty: null } },
+            ],
+            "PavonisInteractive.TerraInvicta.TIA": [
+                { Key: { value: 3 }, Value: { ID: { value: 3 }, loyalty: 0 } },
+                { Key: { value: 1 }, Value: { ID: { value: 1 }, loyalty: 3 } },
+                { Key: { value: 2 }, Value: { ID: { value: 2 }, name: "b" } },
+            ],
+        } }"#,
+    )?;
+    let save = tise::LoadedSave::load_path(&path)?;
+    let a = "PavonisInteractive.TerraInvicta.TIA".to_string();
+    let b = "PavonisInteractive.TerraInvicta.TIB".to_string();
+
+    assert_eq!(
+        save.objects_with_property(None, "loyalty"),
+        [(a.clone(), 1), (a.clone(), 3), (b, 5)]
+    );
+    assert_eq!(
+        save.objects_with_property(Some(&a), "name"),
+        [(a.clone(), 2)]
+    );
+    assert_eq!(
+        save.objects_with_property(None, "currentID"),
+        [(statics::ROOT_GROUP.to_string(), statics::ROOT_OBJECT_ID)]
+    );
+    assert!(save.objects_with_property(Some(&a), "apathy").is_empty());
+    Ok(())
+}
+
+#[test]
+fn groups_are_found_under_a_renamed_gamestates_key() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json5");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 2 }, settings: { volume: 3 }, states: {
+            "PavonisInteractive.TerraInvicta.TITest": [
+                { Key: { value: 1 }, Value: { displayName: "a" } },
+                { Key: { value: 2 }, Value: { displayName: "b" } },
+            ],
+        } }"#,
+    )?;
+    let mut save = tise::LoadedSave::load_path(&path)?;
+    let group = "PavonisInteractive.TerraInvicta.TITest";
+    assert_eq!(save.gamestates_key(), "states");
+    assert_eq!(save.index.groups, [group]);
+    assert!(save.gamestates_report().unwrap().contains("\"states\""));
+    assert_eq!(
+        save.pointer_for_property(group, 2, "displayName")
+            .as_deref(),
+        Some("/states/PavonisInteractive.TerraInvicta.TITest/1/Value/displayName")
+    );
+    assert_eq!(
+        save.object_prop_for_pointer("/states/PavonisInteractive.TerraInvicta.TITest/1/Value/x"),
+        Some((group.to_string(), 2, "x".to_string()))
+    );
+    let root_props = save
+        .object_properties(statics::ROOT_GROUP, statics::ROOT_OBJECT_ID)
+        .unwrap();
+    assert!(!root_props.contains_key("states"));
+    assert!(
+        save.set_property(
+            statics::ROOT_GROUP,
+            statics::ROOT_OBJECT_ID,
+            "states",
+            tise::TiValue::Null
+        )
+        .is_err()
+    );
+
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 2 }, settings: { volume: 3 } }"#,
+    )?;
+    let save = tise::LoadedSave::load_path(&path)?;
+    assert!(save.index.groups.is_empty());
+    assert!(save.gamestates_report().is_some());
+
+    let example = tise::LoadedSave::load_path(
+        &Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("examples")
+            .join("PrunedGame.json"),
+    )?;
+    assert_eq!(example.gamestates_key(), statics::TI_GAMESTATES);
+    assert_eq!(example.gamestates_report(), None);
+    Ok(())
+}
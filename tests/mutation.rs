@@ -1,4 +1,4 @@
-use tise::{LoadedSave, SaveFormat, TiValue, statics};
+use tise::{LoadedSave, SaveFormat, TiValue, diff::DiffLine, statics};
 
 #[test]
 fn modifying_a_value_marks_dirty_and_changes_bytes() {
@@ -36,3 +36,374 @@ fn modifying_a_value_marks_dirty_and_changes_bytes() {
     let text = std::str::from_utf8(&bytes).expect("utf8");
     TiValue::parse_json5(text).expect("saved json5 parses");
 }
+
+#[test]
+fn pending_line_changes_show_only_the_edited_line() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sample.json");
+    let input = TiValue::parse_json5(
+        r#"{ gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 1 }, Value: { displayName: "One", a: 1, b: 2, c: 3, d: 4 } },
+            { Key: { value: 2 }, Value: { displayName: "Two", a: 1, b: 2, c: 3, d: 4 } },
+        ] } }"#,
+    )
+    .expect("parse")
+    .to_ti_save_pretty();
+    std::fs::write(&path, input).expect("write");
+
+    let mut save = LoadedSave::load_path(&path).expect("load");
+    assert!(save.pending_line_changes(3).expect("diff").is_empty());
+
+    save.set_property(
+        "TITest",
+        2,
+        statics::TI_PROP_DISPLAY_NAME,
+        TiValue::String("Renamed".to_string()),
+    )
+    .expect("set");
+
+    let hunks = save.pending_line_changes(3).expect("diff");
+    assert_eq!(hunks.len(), 1);
+    let changed: Vec<_> = hunks[0]
+        .lines
+        .iter()
+        .filter(|l| !matches!(l, DiffLine::Context(_)))
+        .collect();
+    assert_eq!(changed.len(), 2);
+    assert!(matches!(changed[0], DiffLine::Removed(l) if l.contains("\"Two\"")));
+    assert!(matches!(changed[1], DiffLine::Added(l) if l.contains("\"Renamed\"")));
+}
+
+#[test]
+fn flat_import_paths_map_to_object_properties() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sample.json");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 9 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 7 }, Value: { displayName: "Seven", stats: { hp: 1 } } },
+        ] } }"#,
+    )
+    .expect("write");
+    let mut save = LoadedSave::load_path(&path).expect("load");
+
+    let group = "PavonisInteractive.TerraInvicta.TITest";
+    let pointer = format!("/gamestates/{group}/0/Value/stats/hp");
+    assert_eq!(
+        save.object_prop_for_pointer(&pointer),
+        Some((group.to_string(), 7, "stats".to_string()))
+    );
+    assert_eq!(save.object_prop_for_pointer("/currentID/value"), None);
+    assert_eq!(
+        save.pointer_for_property(group, 7, "stats").as_deref(),
+        Some(format!("/gamestates/{group}/0/Value/stats").as_str())
+    );
+    assert_eq!(save.pointer_for_property("Other", 7, "stats"), None);
+
+    let (edits, problems) = tise::flatten::parse_flat(&format!("{pointer} = 5\n"));
+    assert!(problems.is_empty());
+    assert!(tise::flatten::apply_flat(&mut save.root, &edits).is_empty());
+    assert_eq!(
+        save.root.pointer(&pointer).map(TiValue::to_json5_compact),
+        Some("5".to_string())
+    );
+}
+
+#[test]
+fn edit_log_replays_matching_entries_and_reports_stale_ones() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sample.json");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 9 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 7 }, Value: { displayName: "Seven", hp: 1, tag: "x" } },
+        ] } }"#,
+    )
+    .expect("write");
+    let group = "PavonisInteractive.TerraInvicta.TITest";
+    let entry = |prop: &str, before: Option<TiValue>, after: Option<TiValue>| {
+        tise::editlog::LogEntry {
+            group: "TITest".to_string(),
+            id: 7,
+            prop: prop.to_string(),
+            before,
+            after,
+        }
+        .to_record()
+    };
+    let log = [
+        entry(
+            "hp",
+            Some(TiValue::parse_json5("1").unwrap()),
+            Some(TiValue::parse_json5("2").unwrap()),
+        ),
+        entry("tag", Some(TiValue::String("x".into())), None),
+        entry("displayName", Some(TiValue::String("Eight".into())), None),
+        entry("", Some(TiValue::parse_json5("{}").unwrap()), None),
+    ]
+    .join("\n");
+
+    let mut save = LoadedSave::load_path(&path).expect("load");
+    let (entries, problems) = tise::editlog::parse_log(&log);
+    assert!(problems.is_empty());
+    let (applied, problems) = tise::editlog::replay(&mut save, &entries);
+
+    assert_eq!(applied.len(), 2);
+    assert!(applied.iter().all(|e| e.group == group));
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].starts_with("line 3:"));
+    assert!(problems[1].starts_with("line 4: whole-object"));
+    let obj = save.get_object_value(group, 7).expect("object");
+    assert_eq!(
+        obj.get("hp").map(TiValue::to_json5_compact),
+        Some("2".into())
+    );
+    assert!(obj.get("tag").is_none());
+    assert!(obj.get("displayName").is_some());
+}
+
+#[test]
+fn copied_object_gets_a_fresh_id_and_refs_follow_names() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let write = |name: &str, text: &str| {
+        let path = dir.path().join(name);
+        std::fs::write(&path, text).expect("write");
+        LoadedSave::load_path(&path).expect("load")
+    };
+    let source = write(
+        "source.json",
+        r#"{ currentID: { value: 30 }, gamestates: {
+            "PavonisInteractive.TerraInvicta.TICouncilorState": [
+                { Key: { value: 10 }, Value: { ID: { value: 10 }, displayName: "Ada",
+                    faction: { value: 20 }, location: { value: 30 } } },
+            ],
+            "PavonisInteractive.TerraInvicta.TIFactionState": [
+                { Key: { value: 20 }, Value: { displayName: "Resistance" } },
+            ],
+            "PavonisInteractive.TerraInvicta.TIRegionState": [
+                { Key: { value: 30 }, Value: { displayName: "Nowhere" } },
+            ],
+        } }"#,
+    );
+    let mut dest = write(
+        "dest.json",
+        r#"{ currentID: { value: 8 }, gamestates: {
+            "PavonisInteractive.TerraInvicta.TICouncilorState": [
+                { Key: { value: 9 }, Value: { displayName: "Bob" } },
+            ],
+            "PavonisInteractive.TerraInvicta.TIFactionState": [
+                { Key: { value: 5 }, Value: { displayName: "Resistance" } },
+            ],
+        } }"#,
+    );
+    let group = "PavonisInteractive.TerraInvicta.TICouncilorState";
+
+    let copied = dest
+        .copy_object_from(&source, group, 10, true)
+        .expect("copy");
+    assert_eq!(copied.id, 10);
+    assert_eq!(copied.remapped_refs, 1);
+    assert_eq!(copied.unresolved_refs, 1);
+    assert_eq!(dest.game_id(), Some(10));
+    assert!(dest.dirty);
+
+    let obj = dest.get_object_value(group, 10).expect("copied object");
+    let ref_id = |prop: &str| obj.get(prop).and_then(TiValue::is_relational_ref);
+    assert_eq!(ref_id("ID"), Some(10));
+    assert_eq!(ref_id("faction"), Some(5));
+    assert_eq!(ref_id("location"), Some(30));
+
+    let removed = dest.remove_object(group, 10).expect("remove");
+    assert_eq!(
+        removed.get("displayName").and_then(TiValue::as_str),
+        Some("Ada")
+    );
+    dest.insert_object(group, 10, removed.clone())
+        .expect("insert again");
+    assert!(dest.insert_object(group, 10, removed).is_err());
+}
+
+#[test]
+fn lookups_follow_entries_shifted_by_structural_edits() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sample.json");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 3 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 1 }, Value: { displayName: "first" } },
+            { Key: { value: 2 }, Value: { displayName: "second" } },
+            { Key: { value: 3 }, Value: { displayName: "third" } },
+        ] } }"#,
+    )
+    .expect("write");
+    let mut save = LoadedSave::load_path(&path).expect("load");
+    let group = "PavonisInteractive.TerraInvicta.TITest";
+    let name = |save: &LoadedSave, id| {
+        save.get_object_value(group, id)
+            .and_then(|o| o.get("displayName"))
+            .and_then(TiValue::as_str)
+            .map(str::to_string)
+    };
+
+    // The index still places 3 at position 2 until it is rebuilt.
+    save.remove_object(group, 1).expect("remove");
+    assert_eq!(name(&save, 3).as_deref(), Some("third"));
+    assert_eq!(name(&save, 1), None);
+    assert_eq!(
+        save.pointer_for_property(group, 3, "displayName")
+            .as_deref(),
+        Some("/gamestates/PavonisInteractive.TerraInvicta.TITest/1/Value/displayName")
+    );
+    let entry = save.object_entry(group, 3).expect("entry");
+    assert_eq!(
+        entry.to_json5_compact(),
+        r#"{"Key":{"value":3}, "Value":{"displayName":"third"}}"#
+    );
+    assert!(
+        save.object_entry(statics::ROOT_GROUP, statics::ROOT_OBJECT_ID)
+            .is_none()
+    );
+    save.get_object_value_mut(group, 2)
+        .expect("second")
+        .insert("displayName".to_string(), TiValue::String("edited".into()));
+    assert_eq!(name(&save, 2).as_deref(), Some("edited"));
+    assert_eq!(name(&save, 3).as_deref(), Some("third"));
+
+    save.rebuild_index();
+    assert_eq!(save.index.id_lookup.get(&3), Some(&(group.to_string(), 1)));
+    assert_eq!(name(&save, 3).as_deref(), Some("third"));
+    assert_eq!(save.object_group(1), None);
+}
+
+#[test]
+fn relocate_object_follows_an_object_moved_to_another_group() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sample.json");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 2 }, gamestates: {
+            "PavonisInteractive.TerraInvicta.TIA": [ { Key: { value: 1 }, Value: { x: 1 } } ],
+            "PavonisInteractive.TerraInvicta.TIB": [ { Key: { value: 2 }, Value: { x: 2 } } ],
+        } }"#,
+    )
+    .expect("write");
+    let mut save = LoadedSave::load_path(&path).expect("load");
+    let a = "PavonisInteractive.TerraInvicta.TIA";
+    let b = "PavonisInteractive.TerraInvicta.TIB";
+    assert_eq!(save.relocate_object(a, 1).as_deref(), Some(a));
+
+    // Moved without rebuilding the index, which still places 1 in A.
+    let value = save.remove_object(a, 1).expect("remove");
+    save.insert_object(b, 1, value).expect("insert");
+    assert!(save.get_object_value(b, 1).is_none());
+    assert_eq!(save.relocate_object(a, 1).as_deref(), Some(b));
+    assert!(save.get_object_value(b, 1).is_some());
+
+    save.remove_object(b, 1).expect("remove");
+    assert_eq!(save.relocate_object(b, 1), None);
+}
+
+#[test]
+fn match_key_order_follows_reference_and_keeps_extras_last() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sample.json");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 2 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 1 }, Value: { a: 1, b: 2, c: 3 } },
+            { Key: { value: 2 }, Value: { y: 0, c: 3, x: 0, a: 1 } },
+        ] } }"#,
+    )
+    .expect("write");
+    let mut save = LoadedSave::load_path(&path).expect("load");
+    let group = "PavonisInteractive.TerraInvicta.TITest";
+
+    let (before, after) = save
+        .match_key_order(group, 2, 1)
+        .expect("reorder")
+        .expect("order changed");
+    assert_eq!(before.to_json5_compact(), r#"{"y":0, "c":3, "x":0, "a":1}"#);
+    assert_eq!(after.to_json5_compact(), r#"{"a":1, "c":3, "y":0, "x":0}"#);
+    assert!(save.dirty);
+    let keys: Vec<_> = save.get_object_value(group, 2).unwrap().keys().collect();
+    assert_eq!(keys, ["a", "c", "y", "x"]);
+
+    assert!(save.match_key_order(group, 2, 1).expect("again").is_none());
+    assert!(save.match_key_order(group, 2, 99).is_err());
+}
+
+#[test]
+fn root_pseudo_group_edits_top_level_keys_but_not_gamestates() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sample.json");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 9 }, saveVersion: 3, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 7 }, Value: { displayName: "Seven" } },
+        ] } }"#,
+    )
+    .expect("write");
+    let mut save = LoadedSave::load_path(&path).expect("load");
+    let (root, id) = (statics::ROOT_GROUP, statics::ROOT_OBJECT_ID);
+
+    assert!(save.has_root_properties());
+    assert_eq!(save.object_group(id), Some(root));
+    let props = save.object_properties(root, id).expect("root properties");
+    assert_eq!(
+        props.keys().map(String::as_str).collect::<Vec<_>>(),
+        ["currentID", "saveVersion"]
+    );
+    assert_eq!(
+        save.pointer_for_property(root, id, "saveVersion")
+            .as_deref(),
+        Some("/saveVersion")
+    );
+
+    save.set_property(root, id, "saveVersion", TiValue::parse_json5("4").unwrap())
+        .expect("set root property");
+    assert_eq!(
+        save.root.get("saveVersion").map(TiValue::to_json5_compact),
+        Some("4".into())
+    );
+    assert!(
+        save.set_property(root, id, statics::TI_GAMESTATES, TiValue::Null)
+            .is_err()
+    );
+    assert!(
+        save.remove_property(root, id, statics::TI_GAMESTATES)
+            .is_err()
+    );
+    assert!(save.get_object_value(root, 7).is_none());
+}
+
+#[test]
+fn non_finite_numbers_are_listed_and_zeroed() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("sample.json");
+    std::fs::write(
+        &path,
+        r#"{ currentID: { value: 9 }, gamestates: { "PavonisInteractive.TerraInvicta.TITest": [
+            { Key: { value: 8 }, Value: { displayName: "Eight", ok: 1.5, stats: [0.5, -Infinity] } },
+            { Key: { value: 7 }, Value: { displayName: "Seven", speed: NaN } },
+        ] } }"#,
+    )
+    .expect("write");
+    let mut save = LoadedSave::load_path(&path).expect("load");
+
+    let found = save.non_finite_numbers();
+    let location = |f: &tise::NonFiniteNumber| (f.object_id, f.prop.clone(), f.path.clone());
+    assert_eq!(
+        found.iter().map(location).collect::<Vec<_>>(),
+        [
+            (7, "speed".to_string(), String::new()),
+            (8, "stats".to_string(), "/1".to_string()),
+        ]
+    );
+
+    let (before, after) = save.zero_non_finite(&found[1]).expect("zero");
+    assert_eq!(before.to_json5_compact(), "[0.5, -Infinity]");
+    assert_eq!(after.to_json5_compact(), "[0.5, 0.0]");
+    assert!(save.dirty);
+    assert!(save.zero_non_finite(&found[1]).is_err());
+    assert_eq!(save.non_finite_numbers().len(), 1);
+}
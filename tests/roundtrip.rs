@@ -94,6 +94,45 @@ fn dirty_save_is_valid_json5() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn edited_save_passes_round_trip_check() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json");
+
+    std::fs::write(&path, b"{ a: 1 }\n")?;
+    let mut save = tise::LoadedSave::load_path(&path)?;
+    save.check_round_trip()?;
+
+    save.root = tise::TiValue::parse_json5("{ a: 2, b: Infinity, c: [-0.0, NaN, 1E-05], d: {} }")?;
+    save.mark_dirty();
+    save.check_round_trip()?;
+
+    // The check covers the text actually written, here spliced into the original.
+    save.minimize_diff = true;
+    save.check_round_trip()?;
+    Ok(())
+}
+
+#[test]
+fn dirty_save_keeps_unedited_float_literals() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json");
+
+    let input = "{\n    \"a\": 0.5000000,\n    \"b\": 1\n}";
+    std::fs::write(&path, input.as_bytes())?;
+    let mut save = tise::LoadedSave::load_path(&path)?;
+
+    *save.root.get_mut("b").expect("b exists") = tise::TiValue::String("changed".to_string());
+    save.mark_dirty();
+
+    let out = save.save_bytes_for_format(tise::SaveFormat::Json5)?;
+    assert_eq!(
+        std::str::from_utf8(&out)?,
+        "{\n    \"a\": 0.5000000,\n    \"b\": \"changed\"\n}"
+    );
+    Ok(())
+}
+
 #[test]
 // #[ignore = "Slow: reads/writes examples/LargeGame.json"]
 fn integration_large_game_edit_councilor_3896_minimal_diff() -> Result<()> {
@@ -182,3 +221,173 @@ fn integration_large_game_edit_councilor_3896_minimal_diff() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn single_quoted_strings_are_counted_and_only_rewritten_when_regenerated() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json");
+    let input = "{ currentID: { value: 1 }, gamestates: { 'G': [] }, name: 'it\\'s' }\n";
+    std::fs::write(&path, input)?;
+
+    let mut save = tise::LoadedSave::load_path(&path)?;
+    assert_eq!(save.single_quoted_strings, 2);
+    assert_eq!(
+        save.root.get("name").and_then(tise::TiValue::as_str),
+        Some("it's")
+    );
+
+    // Unedited, the original bytes are written back as they were.
+    let copy = dir.path().join("copy.json");
+    save.save_to_path(&copy)?;
+    assert_eq!(std::fs::read(&copy)?, input.as_bytes());
+    assert_eq!(save.single_quoted_strings, 2);
+
+    save.mark_dirty();
+    save.save_to_path(&copy)?;
+    let written = std::fs::read_to_string(&copy)?;
+    assert!(written.contains(r#""name": "it's""#), "{written}");
+    assert_eq!(save.single_quoted_strings, 0);
+    Ok(())
+}
+
+#[test]
+fn minimize_diff_keeps_unchanged_entries_as_written() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json");
+    let input = r#"{
+  currentID: { value: 3 },
+  gamestates: {
+    "PavonisInteractive.TerraInvicta.TITest": [
+      { Key: { value: 1 }, Value: { ID: { value: 1 }, displayName: 'One', x: 1.50 } },
+      { Key: { value: 2 }, Value: { ID: { value: 2 }, displayName: 'Two', x: 2.50 } },
+    ],
+  },
+}
+"#;
+    std::fs::write(&path, input)?;
+
+    let mut save = tise::LoadedSave::load_path(&path)?;
+    save.minimize_diff = true;
+    save.set_property(
+        "PavonisInteractive.TerraInvicta.TITest",
+        2,
+        "displayName",
+        tise::TiValue::String("Deux".to_string()),
+    )?;
+    save.refresh_dirty();
+    assert!(save.dirty);
+
+    let out = String::from_utf8(save.save_bytes_for_format(tise::SaveFormat::Json5)?)?;
+    assert!(out.starts_with("{\n  currentID: { value: 3 },\n"), "{out}");
+    assert!(
+        out.contains("      { Key: { value: 1 }, Value: { ID: { value: 1 }, displayName: 'One', x: 1.50 } },\n      {\n"),
+        "{out}"
+    );
+    assert!(out.contains(r#""displayName": "Deux","#), "{out}");
+    assert_eq!(tise::TiValue::parse_json5(&out)?, save.root);
+
+    // Undoing the edit gives back the original bytes.
+    save.set_property(
+        "PavonisInteractive.TerraInvicta.TITest",
+        2,
+        "displayName",
+        tise::TiValue::String("Two".to_string()),
+    )?;
+    save.refresh_dirty();
+    assert!(!save.dirty);
+    let out = save.generate_bytes_for_format(tise::SaveFormat::Json5)?;
+    assert_eq!(String::from_utf8(out)?, input);
+    Ok(())
+}
+
+#[test]
+// #[ignore = "Slow: reads/writes examples/LargeGame.json"]
+fn integration_large_game_councilor_3896_minimize_diff() -> Result<()> {
+    let input_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples")
+        .join("LargeGame.json");
+    let input_bytes = std::fs::read(&input_path)?;
+    let mut save = tise::LoadedSave::load_path(&input_path)?;
+    save.minimize_diff = true;
+
+    save.set_property(
+        statics::TI_GROUP_COUNCILOR_STATE,
+        3896,
+        statics::TI_PROP_DISPLAY_NAME,
+        tise::TiValue::String("Bob Ross".to_string()),
+    )?;
+    save.mark_dirty();
+
+    let out_bytes = save.save_bytes_for_format(tise::SaveFormat::Json5)?;
+    let in_text = std::str::from_utf8(&input_bytes)?;
+    let out_text = std::str::from_utf8(&out_bytes)?;
+    let hunks = tise::diff::changed_lines(in_text, out_text, 0);
+    assert_eq!(hunks.len(), 1, "{hunks:?}");
+    assert!(out_text.contains("\"displayName\": \"Bob Ross\""));
+    assert_eq!(tise::TiValue::parse_json5(out_text)?, save.root);
+    Ok(())
+}
+
+#[test]
+fn load_reports_garbage_appended_after_the_root() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json");
+    std::fs::write(
+        &path,
+        "{ currentID: { value: 1 }, gamestates: {} }\nGARBAGE",
+    )?;
+
+    let err = tise::LoadedSave::load_path(&path)
+        .err()
+        .ok_or("expected a load error")?;
+    assert!(
+        format!("{err:#}").contains("unexpected trailing data at offset 44"),
+        "{err:#}"
+    );
+    Ok(())
+}
+
+#[test]
+fn content_hash_ignores_key_order_and_float_spelling() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let a = dir.path().join("a.json");
+    let b = dir.path().join("b.json");
+    std::fs::write(
+        &a,
+        "{ currentID: { value: 1 }, gamestates: { G: [{ Key: { value: 1 }, Value: { x: 1.50, y: 'y' } }] } }",
+    )?;
+    std::fs::write(
+        &b,
+        "{\n  gamestates: { G: [{ Value: { y: \"y\", x: 1.5 }, Key: { value: 1 } }] },\n  currentID: { value: 1 },\n}\n",
+    )?;
+
+    let a = tise::LoadedSave::load_path(&a)?;
+    let mut b = tise::LoadedSave::load_path(&b)?;
+    assert_eq!(a.content_hash(), b.content_hash());
+
+    b.set_property("G", 1, "x", tise::TiValue::parse_json5("2")?)?;
+    assert_ne!(a.content_hash(), b.content_hash());
+    Ok(())
+}
+
+#[test]
+fn dirty_check_sees_data_changes_and_reordered_keys() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("sample.json");
+    std::fs::write(&path, "{\n    \"a\": 1,\n    \"b\": 2\n}")?;
+    let mut save = tise::LoadedSave::load_path(&path)?;
+
+    save.root = tise::TiValue::parse_json5("{ a: 1, b: 3 }")?;
+    save.refresh_dirty();
+    assert!(save.dirty);
+
+    // Same data as loaded, but written in a different order.
+    save.root = tise::TiValue::parse_json5("{ b: 2, a: 1 }")?;
+    save.refresh_dirty();
+    assert!(save.dirty);
+
+    save.root = tise::TiValue::parse_json5("{ a: 1, b: 2 }")?;
+    save.refresh_dirty();
+    assert!(!save.dirty);
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use pretty_assertions::assert_eq;
+
+use std::path::Path;
+
+use tise::{LoadedSave, TiValue, scramble, statics};
+
+/// Collect a structural fingerprint: one entry per value with its path and type name.
+fn shape(val: &TiValue, path: &str, out: &mut Vec<String>) {
+    out.push(format!("{path}: {}", val.type_name()));
+    if let Some(arr) = val.as_array() {
+        for (i, v) in arr.iter().enumerate() {
+            shape(v, &format!("{path}[{i}]"), out);
+        }
+    } else if let Some(map) = val.as_object() {
+        for (k, v) in map {
+            shape(v, &format!("{path}.{k}"), out);
+        }
+    }
+}
+
+#[test]
+fn scramble_preserves_structure_and_ids() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples")
+        .join("PrunedGameMore.json");
+    let original = LoadedSave::load_path(&path).expect("load example");
+    let mut save = LoadedSave::load_path(&path).expect("load example");
+
+    scramble::scramble(&mut save.root, 42);
+    save.rebuild_index();
+
+    let (mut before, mut after) = (Vec::new(), Vec::new());
+    shape(&original.root, "", &mut before);
+    shape(&save.root, "", &mut after);
+    assert_eq!(before, after);
+
+    assert_eq!(original.index.groups, save.index.groups);
+    let mut ids_before: Vec<_> = original.index.id_lookup.keys().copied().collect();
+    let mut ids_after: Vec<_> = save.index.id_lookup.keys().copied().collect();
+    ids_before.sort_unstable();
+    ids_after.sort_unstable();
+    assert_eq!(ids_before, ids_after);
+
+    // Every object-level displayName is anonymized; other strings are untouched.
+    for (group, objects) in &original.index.objects_by_group {
+        for obj in objects {
+            let before = original.get_object_value(group, obj.id).expect("object");
+            let after = save.get_object_value(group, obj.id).expect("object");
+            if let Some(name) = before
+                .get(statics::TI_PROP_DISPLAY_NAME)
+                .and_then(TiValue::as_str)
+                .filter(|n| !n.trim().is_empty())
+            {
+                let new_name = after
+                    .get(statics::TI_PROP_DISPLAY_NAME)
+                    .and_then(TiValue::as_str)
+                    .expect("still a string");
+                assert!(
+                    new_name.starts_with(statics::EN_ANON_NAME_PREFIX),
+                    "{group} {} kept its name: {name}",
+                    obj.id
+                );
+            }
+            if let Some(event) = before.get(statics::TI_PROP_EVENT_NAME) {
+                assert_eq!(Some(event), after.get(statics::TI_PROP_EVENT_NAME));
+            }
+        }
+    }
+}
+
+#[test]
+fn scramble_is_deterministic_per_seed() {
+    let input = r#"{ displayName: "Alice", ratio: 0.5, count: 3, templateName: "Foo" }"#;
+    let mut a = TiValue::parse_json5(input).expect("parse");
+    let mut b = a.clone();
+    scramble::scramble(&mut a, 7);
+    scramble::scramble(&mut b, 7);
+    assert_eq!(a.to_json5_compact(), b.to_json5_compact());
+
+    assert_ne!(
+        a.get("displayName").and_then(TiValue::as_str),
+        Some("Alice")
+    );
+    assert_ne!(
+        a.get("ratio").map(TiValue::to_json5_compact).as_deref(),
+        Some("0.5")
+    );
+    assert_eq!(
+        a.get("count").map(TiValue::to_json5_compact).as_deref(),
+        Some("3")
+    );
+    assert_eq!(a.get("templateName").and_then(TiValue::as_str), Some("Foo"));
+}